@@ -0,0 +1,136 @@
+use syn::{Attribute, Error, Generics, Lit, Meta, MetaNameValue, NestedMeta, Result};
+
+/// Parse the nested items from every attribute with the given name.
+///
+/// # Parameters
+/// - `name` - The name of the attributes to parse
+/// - `attrs` - The attributes to parse from
+///
+/// # Returns
+/// The nested items, in the order they were written, or an error if any of the attributes weren't of the form
+/// `#[name(...)]`.
+pub fn metas(name: &str, attrs: &[Attribute]) -> Result<Vec<Meta>> {
+    let mut result = vec![];
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            other => {
+                return Err(Error::new_spanned(
+                    other,
+                    format!("expected #[{}(...)]", name),
+                ))
+            }
+        };
+
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(meta) => result.push(meta),
+                NestedMeta::Lit(other) => {
+                    return Err(Error::new_spanned(
+                        other,
+                        format!("expected #[{}(...)]", name),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Parse the `key = value` pairs from every attribute with the given name.
+///
+/// # Parameters
+/// - `name` - The name of the attributes to parse
+/// - `attrs` - The attributes to parse from
+///
+/// # Returns
+/// The pairs, in the order they were written, or an error if any of the attributes weren't of the expected form.
+pub fn name_values(name: &str, attrs: &[Attribute]) -> Result<Vec<MetaNameValue>> {
+    metas(name, attrs)?
+        .into_iter()
+        .map(|meta| match meta {
+            Meta::NameValue(name_value) => Ok(name_value),
+            other => Err(Error::new_spanned(
+                other,
+                format!("expected #[{}(key = value, ...)]", name),
+            )),
+        })
+        .collect()
+}
+
+/// Collect the doc comments from a set of attributes into a single string.
+///
+/// # Parameters
+/// - `attrs` - The attributes to collect from
+///
+/// # Returns
+/// The lines of the doc comments, trimmed and joined with spaces, or `None` if there were no doc comments.
+pub fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(value),
+                ..
+            })) => Some(value.value().trim().to_owned()),
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+/// Get the name of the key of a `key = value` pair.
+///
+/// # Parameters
+/// - `name_value` - The pair
+///
+/// # Returns
+/// The name of the key, which is empty if the key isn't a single identifier.
+pub fn key(name_value: &MetaNameValue) -> String {
+    name_value
+        .path
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
+
+/// Get the value of a `key = "value"` pair.
+///
+/// # Parameters
+/// - `name_value` - The pair
+///
+/// # Returns
+/// The value, or an error if it wasn't a string literal.
+pub fn string_value(name_value: &MetaNameValue) -> Result<String> {
+    match &name_value.lit {
+        Lit::Str(value) => Ok(value.value()),
+        other => Err(Error::new_spanned(other, "expected a string literal")),
+    }
+}
+
+/// Ensure that the type being derived for has no generic parameters, since the derived code doesn't support them.
+///
+/// # Parameters
+/// - `generics` - The generic parameters of the type
+///
+/// # Returns
+/// An error if there were any generic parameters.
+pub fn no_generics(generics: &Generics) -> Result<()> {
+    if generics.params.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::new_spanned(
+            generics,
+            "cannot be derived for types with generic parameters",
+        ))
+    }
+}
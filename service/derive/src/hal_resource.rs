@@ -0,0 +1,242 @@
+use crate::attributes::{key, name_values, no_generics, string_value};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Field, Fields, Ident, PathArguments, Result, Type};
+
+/// What a single field of the struct represents in the HAL document.
+enum FieldRole {
+    /// The field is part of the data of the document.
+    Data,
+    /// The field is a link with the given link relation.
+    Link(String),
+    /// The field is an embedded resource with the given link relation.
+    Embedded(String),
+}
+
+/// How many values a field holds, as determined by its type.
+enum Cardinality {
+    /// The field holds exactly one value.
+    Single,
+    /// The field is an `Option` holding zero or one values.
+    Optional,
+    /// The field is a `Vec` holding any number of values.
+    Multiple,
+}
+
+impl FieldRole {
+    /// Determine the role of a field from its `#[hal(...)]` attributes.
+    ///
+    /// # Parameters
+    /// - `field` - The field
+    ///
+    /// # Returns
+    /// The role of the field, or an error if the attributes were invalid.
+    fn parse(field: &Field) -> Result<Self> {
+        let mut role = Self::Data;
+
+        for name_value in name_values("hal", &field.attrs)? {
+            if !matches!(role, Self::Data) {
+                return Err(Error::new_spanned(
+                    &name_value,
+                    "only one of `link` or `embedded` may be provided",
+                ));
+            }
+
+            role = match key(&name_value).as_str() {
+                "link" => Self::Link(string_value(&name_value)?),
+                "embedded" => Self::Embedded(string_value(&name_value)?),
+                _ => {
+                    return Err(Error::new_spanned(
+                        &name_value.path,
+                        "expected one of `link` or `embedded`",
+                    ))
+                }
+            };
+        }
+
+        Ok(role)
+    }
+}
+
+impl Cardinality {
+    /// Determine the cardinality of a field from the outermost type of the field.
+    ///
+    /// # Parameters
+    /// - `ty` - The type of the field
+    ///
+    /// # Returns
+    /// The cardinality of the field.
+    fn of(ty: &Type) -> Self {
+        let segment = match ty {
+            Type::Path(path) if path.qself.is_none() => path.path.segments.last(),
+            _ => None,
+        };
+
+        match segment {
+            Some(segment) if matches!(segment.arguments, PathArguments::AngleBracketed(_)) => {
+                if segment.ident == "Option" {
+                    Self::Optional
+                } else if segment.ident == "Vec" {
+                    Self::Multiple
+                } else {
+                    Self::Single
+                }
+            }
+            _ => Self::Single,
+        }
+    }
+}
+
+/// Generate the statement to add a link field to the document.
+///
+/// # Parameters
+/// - `ident` - The name of the field
+/// - `ty` - The type of the field
+/// - `rel` - The link relation to use
+///
+/// # Returns
+/// The generated statement.
+fn link_in_document(ident: &Ident, ty: &Type, rel: &str) -> TokenStream {
+    match Cardinality::of(ty) {
+        Cardinality::Single => quote! {
+            document = document.with_link(#rel, #ident);
+        },
+        Cardinality::Optional => quote! {
+            if let ::std::option::Option::Some(value) = #ident {
+                document = document.with_link(#rel, value);
+            }
+        },
+        Cardinality::Multiple => quote! {
+            document = document.with_links(#rel, #ident);
+        },
+    }
+}
+
+/// Generate the statement to add an embedded resource field to the document.
+///
+/// Lists of embedded resources are added with `with_embedded_list`, so they are always represented as arrays.
+///
+/// # Parameters
+/// - `ident` - The name of the field
+/// - `ty` - The type of the field
+/// - `rel` - The link relation to use
+///
+/// # Returns
+/// The generated statement.
+fn embed_in_document(ident: &Ident, ty: &Type, rel: &str) -> TokenStream {
+    match Cardinality::of(ty) {
+        Cardinality::Single => quote! {
+            document = document.with_embedded(#rel, #ident);
+        },
+        Cardinality::Optional => quote! {
+            if let ::std::option::Option::Some(value) = #ident {
+                document = document.with_embedded(#rel, value);
+            }
+        },
+        Cardinality::Multiple => quote! {
+            document = document.with_embedded_list(#rel, #ident);
+        },
+    }
+}
+
+/// Generate the implementation of `#[derive(HalResource)]`.
+///
+/// # Parameters
+/// - `input` - The type to derive for
+///
+/// # Returns
+/// The generated code, or an error if the type couldn't be derived for.
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    no_generics(&input.generics)?;
+
+    let ident = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &data.fields,
+                    "HalResource can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "HalResource can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let serde_attrs = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"));
+
+    let mut field_names = vec![];
+    let mut data_fields = vec![];
+    let mut data_names = vec![];
+    let mut statements = vec![];
+    let mut embedded_rels = vec![];
+
+    for field in fields {
+        let field_ident = field
+            .ident
+            .as_ref()
+            .expect("Named fields always have names");
+        let ty = &field.ty;
+        field_names.push(field_ident);
+
+        match FieldRole::parse(field)? {
+            FieldRole::Data => {
+                let field_serde_attrs = field
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path.is_ident("serde"));
+
+                data_fields.push(quote! {
+                    #(#field_serde_attrs)*
+                    #field_ident: #ty
+                });
+                data_names.push(field_ident);
+            }
+            FieldRole::Link(rel) => {
+                statements.push(link_in_document(field_ident, ty, &rel));
+            }
+            FieldRole::Embedded(rel) => {
+                if embedded_rels.contains(&rel) {
+                    return Err(Error::new_spanned(
+                        field,
+                        format!(
+                            "the link relation `{}` is already used by another embedded field",
+                            rel
+                        ),
+                    ));
+                }
+
+                statements.push(embed_in_document(field_ident, ty, &rel));
+                embedded_rels.push(rel);
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl ::std::convert::From<#ident> for crate::http::hal::HalDocument {
+            fn from(resource: #ident) -> Self {
+                #[derive(::serde::Serialize)]
+                #(#serde_attrs)*
+                struct Model {
+                    #(#data_fields,)*
+                }
+
+                let #ident { #(#field_names,)* } = resource;
+
+                #[allow(unused_mut)]
+                let mut document = Self::new(Model { #(#data_names,)* });
+                #(#statements)*
+
+                document
+            }
+        }
+    })
+}
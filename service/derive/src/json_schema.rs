@@ -0,0 +1,308 @@
+use crate::attributes::{doc_comment, key, metas, name_values, no_generics, string_value};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    Attribute, Data, DeriveInput, Error, Field, Fields, GenericArgument, Meta, PathArguments,
+    Result, Type,
+};
+
+/// The ways that Serde can rename every field of a struct, as provided by `#[serde(rename_all = "...")]`.
+enum RenameRule {
+    /// The fields are serialized with the same names as in Rust.
+    None,
+    /// The fields are serialized in `UPPERCASE` or `SCREAMING_SNAKE_CASE`.
+    Upper,
+    /// The fields are serialized in `camelCase`.
+    Camel,
+    /// The fields are serialized in `PascalCase`.
+    Pascal,
+    /// The fields are serialized in `kebab-case`.
+    Kebab,
+    /// The fields are serialized in `SCREAMING-KEBAB-CASE`.
+    ScreamingKebab,
+}
+
+/// How a single field of the struct is serialized by Serde, as provided by its `#[serde(...)]` attributes.
+struct SerdeField {
+    /// The name that the field is serialized with, if it has been renamed.
+    rename: Option<String>,
+    /// Whether the field is never serialized.
+    skip: bool,
+    /// Whether the field is sometimes left out when serializing.
+    optional: bool,
+}
+
+impl RenameRule {
+    /// Determine the rename rule from the `#[serde(...)]` attributes of the struct.
+    ///
+    /// # Parameters
+    /// - `attrs` - The attributes of the struct
+    ///
+    /// # Returns
+    /// The rename rule, or an error if it isn't one that is supported.
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut rule = Self::None;
+
+        for meta in metas("serde", attrs)? {
+            if let Meta::NameValue(name_value) = meta {
+                if key(&name_value) == "rename_all" {
+                    rule = match string_value(&name_value)?.as_str() {
+                        "lowercase" | "snake_case" => Self::None,
+                        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => Self::Upper,
+                        "camelCase" => Self::Camel,
+                        "PascalCase" => Self::Pascal,
+                        "kebab-case" => Self::Kebab,
+                        "SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+                        _ => {
+                            return Err(Error::new_spanned(
+                                &name_value.lit,
+                                "unsupported rename rule",
+                            ))
+                        }
+                    };
+                }
+            }
+        }
+
+        Ok(rule)
+    }
+
+    /// Apply the rename rule to the name of a field.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the field in Rust, which is expected to be in `snake_case`
+    ///
+    /// # Returns
+    /// The name that the field is serialized with.
+    fn apply(&self, name: &str) -> String {
+        match self {
+            Self::None => name.to_owned(),
+            Self::Upper => name.to_ascii_uppercase(),
+            Self::Camel | Self::Pascal => {
+                let mut result = String::new();
+                for (index, word) in name.split('_').enumerate() {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        if index == 0 && matches!(self, Self::Camel) {
+                            result.push(first);
+                        } else {
+                            result.push(first.to_ascii_uppercase());
+                        }
+                        result.extend(chars);
+                    }
+                }
+                result
+            }
+            Self::Kebab => name.replace('_', "-"),
+            Self::ScreamingKebab => name.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+}
+
+impl SerdeField {
+    /// Determine how a field is serialized from its `#[serde(...)]` attributes.
+    ///
+    /// Any attributes that don't affect the shape of the serialized field are ignored.
+    ///
+    /// # Parameters
+    /// - `field` - The field
+    ///
+    /// # Returns
+    /// The details of the field, or an error if it uses a Serde feature that isn't supported.
+    fn parse(field: &Field) -> Result<Self> {
+        let mut result = Self {
+            rename: None,
+            skip: false,
+            optional: false,
+        };
+
+        for meta in metas("serde", &field.attrs)? {
+            match meta {
+                Meta::NameValue(name_value) => match key(&name_value).as_str() {
+                    "rename" => result.rename = Some(string_value(&name_value)?),
+                    "skip_serializing_if" => result.optional = true,
+                    _ => {}
+                },
+                Meta::Path(path) => {
+                    if path.is_ident("skip") || path.is_ident("skip_serializing") {
+                        result.skip = true;
+                    } else if path.is_ident("flatten") {
+                        return Err(Error::new_spanned(
+                            path,
+                            "JsonSchema cannot be derived for flattened fields",
+                        ));
+                    }
+                }
+                Meta::List(_) => {}
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Determine the format of a field from its `#[schema(...)]` attributes.
+///
+/// # Parameters
+/// - `field` - The field
+///
+/// # Returns
+/// The JSON Schema format of the field, if one was provided, or an error if the attributes were invalid.
+fn format(field: &Field) -> Result<Option<String>> {
+    let mut format = None;
+
+    for name_value in name_values("schema", &field.attrs)? {
+        match key(&name_value).as_str() {
+            "format" => format = Some(string_value(&name_value)?),
+            _ => return Err(Error::new_spanned(&name_value.path, "expected `format`")),
+        }
+    }
+
+    Ok(format)
+}
+
+/// Find the type wrapped by an `Option`.
+///
+/// # Parameters
+/// - `ty` - The type of the field
+///
+/// # Returns
+/// The type inside the `Option`, or `None` if the type isn't an `Option`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let segment = match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Generate the implementation of `#[derive(JsonSchema)]`.
+///
+/// # Parameters
+/// - `input` - The type to derive for
+///
+/// # Returns
+/// The generated code, or an error if the type couldn't be derived for.
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    no_generics(&input.generics)?;
+
+    let ident = &input.ident;
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &data.fields,
+                    "JsonSchema can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "JsonSchema can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let rename_rule = RenameRule::parse(&input.attrs)?;
+    let mut statements = vec![];
+
+    for field in fields {
+        let serde_field = SerdeField::parse(field)?;
+        if serde_field.skip {
+            continue;
+        }
+
+        let field_ident = field
+            .ident
+            .as_ref()
+            .expect("Named fields always have names");
+        // An `Option` that is skipped when it is `None` is never serialized as `null`, so is described by the schema of
+        // the type that it wraps.
+        let ty = match option_inner(&field.ty) {
+            Some(inner) if serde_field.optional => inner,
+            _ => &field.ty,
+        };
+
+        let name = serde_field.rename.unwrap_or_else(|| {
+            let name = field_ident.to_string();
+            rename_rule.apply(name.trim_start_matches("r#"))
+        });
+
+        let mut annotations = vec![];
+        if let Some(format) = format(field)? {
+            annotations.push(quote! {
+                field_schema.insert("format".to_owned(), #format.into());
+            });
+        }
+        if let Some(description) = doc_comment(&field.attrs) {
+            annotations.push(quote! {
+                field_schema.insert("description".to_owned(), #description.into());
+            });
+        }
+        let annotations = if annotations.is_empty() {
+            None
+        } else {
+            Some(quote! {
+                if let ::serde_json::Value::Object(field_schema) = &mut field_schema {
+                    #(#annotations)*
+                }
+            })
+        };
+        let required = if serde_field.optional {
+            None
+        } else {
+            Some(quote! {
+                if !<#ty as crate::http::schema::JsonSchema>::is_optional() {
+                    required.push(#name.into());
+                }
+            })
+        };
+
+        statements.push(quote! {
+            #[allow(unused_mut)]
+            let mut field_schema = <#ty as crate::http::schema::JsonSchema>::json_schema();
+            #annotations
+            properties.insert(#name.to_owned(), field_schema);
+            #required
+        });
+    }
+
+    let description = doc_comment(&input.attrs).map(|description| {
+        quote! {
+            schema.insert("description".to_owned(), #description.into());
+        }
+    });
+
+    Ok(quote! {
+        impl crate::http::schema::JsonSchema for #ident {
+            fn json_schema() -> ::serde_json::Value {
+                #[allow(unused_mut)]
+                let mut properties = ::serde_json::Map::new();
+                #[allow(unused_mut)]
+                let mut required: ::std::vec::Vec<::serde_json::Value> = ::std::vec::Vec::new();
+
+                #({ #statements })*
+
+                let mut schema = ::serde_json::Map::new();
+                schema.insert("type".to_owned(), "object".into());
+                #description
+                schema.insert("properties".to_owned(), properties.into());
+                schema.insert("required".to_owned(), required.into());
+
+                schema.into()
+            }
+        }
+    })
+}
@@ -0,0 +1,95 @@
+#![deny(clippy::all, clippy::pedantic)]
+
+mod attributes;
+mod hal_resource;
+mod json_schema;
+mod problem_type;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derive the `ProblemType`, `ProblemTypeStatus` and `Display` traits for a type, and allow it to be converted into a
+/// `Problem`.
+///
+/// The details of the problem type are provided by a `#[problem(...)]` attribute on a struct, or on every variant of
+/// an enum:
+///
+/// ```ignore
+/// #[derive(Debug, ProblemType)]
+/// pub enum UserProblem {
+///     #[problem(type = "/problems/unknown_user", title = "The user was not found", status = 404)]
+///     UnknownUser,
+///     #[problem(type = "/problems/duplicate_email", title = "The email address is already registered", status = 409)]
+///     DuplicateEmail { email: String },
+/// }
+/// ```
+///
+/// The generated code refers to the traits by their paths within the service crate, so can only be used there.
+#[proc_macro_derive(ProblemType, attributes(problem))]
+pub fn derive_problem_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    problem_type::expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive a conversion from a struct into a `HalDocument`.
+///
+/// Fields marked with `#[hal(link = "rel")]` become links and fields marked with `#[hal(embedded = "rel")]` become
+/// embedded resources, using the provided link relation. Fields of type `Option<T>` are only included when present,
+/// and fields of type `Vec<T>` are always represented as arrays. Each link relation can only be used by one embedded
+/// field. All other fields become the data of the document,
+/// serialized by Serde, including any `#[serde(...)]` attributes on the struct and those fields.
+///
+/// ```ignore
+/// #[derive(HalResource)]
+/// #[serde(rename_all = "camelCase")]
+/// pub struct UserDocument {
+///     pub display_name: String,
+///     #[hal(link = "self")]
+///     pub self_link: String,
+///     #[hal(embedded = "nl:address")]
+///     pub addresses: Vec<AddressDocument>,
+/// }
+/// ```
+///
+/// The generated code refers to `HalDocument` by its path within the service crate, so can only be used there.
+#[proc_macro_derive(HalResource, attributes(hal, serde))]
+pub fn derive_hal_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    hal_resource::expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derive the `JsonSchema` trait for a struct, describing the shape that Serde serializes it as.
+///
+/// Every field that is serialized becomes a property of the schema, using the schema of the field type and honouring
+/// `#[serde(rename_all = "...")]`, `#[serde(rename = "...")]` and `#[serde(skip)]`. Fields are required unless they
+/// are of type `Option<T>` or are marked with `#[serde(skip_serializing_if = "...")]`. Doc comments on the struct and
+/// its fields become the descriptions in the schema, and fields can be given a format with `#[schema(format = "...")]`:
+///
+/// ```ignore
+/// /// Representation of a User on the HTTP API.
+/// #[derive(Serialize, JsonSchema)]
+/// #[serde(rename_all = "camelCase")]
+/// pub struct UserModel {
+///     /// The name to display for the user.
+///     pub display_name: String,
+///     /// The email address of the user.
+///     #[schema(format = "email")]
+///     pub email: String,
+/// }
+/// ```
+///
+/// The generated code refers to the trait by its path within the service crate, so can only be used there.
+#[proc_macro_derive(JsonSchema, attributes(schema, serde))]
+pub fn derive_json_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    json_schema::expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
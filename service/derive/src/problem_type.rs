@@ -0,0 +1,150 @@
+use crate::attributes::{key, name_values, no_generics, string_value};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Lit, Result};
+
+/// The details of a single problem type, as provided by a `#[problem(...)]` attribute.
+struct ProblemDetails {
+    /// The URI Reference that identifies the problem type.
+    problem_type: String,
+    /// The title of the problem type.
+    title: String,
+    /// The HTTP Status code for the problem type.
+    status: u16,
+}
+
+impl ProblemDetails {
+    /// Parse the details of a problem type from the `#[problem(...)]` attributes of an item.
+    ///
+    /// # Parameters
+    /// - `attrs` - The attributes of the item
+    /// - `span` - The span to report missing details against
+    ///
+    /// # Returns
+    /// The details of the problem type, or an error if any were missing or invalid.
+    fn parse(attrs: &[Attribute], span: Span) -> Result<Self> {
+        let mut problem_type = None;
+        let mut title = None;
+        let mut status = None;
+
+        for name_value in name_values("problem", attrs)? {
+            match key(&name_value).as_str() {
+                "type" => problem_type = Some(string_value(&name_value)?),
+                "title" => title = Some(string_value(&name_value)?),
+                "status" => {
+                    let value = match &name_value.lit {
+                        Lit::Int(value) => value.base10_parse::<u16>()?,
+                        other => return Err(Error::new_spanned(other, "expected an integer")),
+                    };
+                    if !(100..1000).contains(&value) {
+                        return Err(Error::new_spanned(
+                            &name_value.lit,
+                            "expected an HTTP Status code",
+                        ));
+                    }
+                    status = Some(value);
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        &name_value.path,
+                        "expected one of `type`, `title` or `status`",
+                    ))
+                }
+            }
+        }
+
+        Ok(Self {
+            problem_type: problem_type
+                .ok_or_else(|| Error::new(span, "missing `type` in #[problem(...)]"))?,
+            title: title.ok_or_else(|| Error::new(span, "missing `title` in #[problem(...)]"))?,
+            status: status
+                .ok_or_else(|| Error::new(span, "missing `status` in #[problem(...)]"))?,
+        })
+    }
+}
+
+/// Generate the implementation of `#[derive(ProblemType)]`.
+///
+/// # Parameters
+/// - `input` - The type to derive for
+///
+/// # Returns
+/// The generated code, or an error if the type couldn't be derived for.
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    no_generics(&input.generics)?;
+
+    let ident = &input.ident;
+
+    // Each problem type is a pattern to match `self` against, and the details for that pattern.
+    let problems = match &input.data {
+        Data::Struct(_) => vec![(
+            quote! { _ },
+            ProblemDetails::parse(&input.attrs, ident.span())?,
+        )],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_ident = &variant.ident;
+                let details = ProblemDetails::parse(&variant.attrs, variant_ident.span())?;
+
+                Ok((quote! { Self::#variant_ident { .. } }, details))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "ProblemType cannot be derived for unions",
+            ))
+        }
+    };
+
+    let problem_types = problems.iter().map(|(pattern, details)| {
+        let problem_type = &details.problem_type;
+        quote! { #pattern => #problem_type, }
+    });
+    let statuses = problems.iter().map(|(pattern, details)| {
+        let status = details.status;
+        quote! {
+            #pattern => ::actix_http::http::StatusCode::from_u16(#status).expect("Invalid status code"),
+        }
+    });
+    let titles = problems.iter().map(|(pattern, details)| {
+        let title = &details.title;
+        quote! { #pattern => #title, }
+    });
+
+    Ok(quote! {
+        impl crate::http::problem::ProblemType for #ident {
+            fn problem_type(&self) -> &'static str {
+                match self {
+                    #(#problem_types)*
+                }
+            }
+        }
+
+        impl crate::http::problem::ProblemTypeStatus for #ident {
+            fn status_code(&self) -> ::actix_http::http::StatusCode {
+                match self {
+                    #(#statuses)*
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let title = match self {
+                    #(#titles)*
+                };
+
+                f.write_str(title)
+            }
+        }
+
+        impl ::std::convert::From<#ident> for crate::http::problem::Problem {
+            fn from(problem_type: #ident) -> Self {
+                Self::new(problem_type)
+            }
+        }
+    })
+}
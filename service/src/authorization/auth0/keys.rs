@@ -61,6 +61,28 @@ impl Keys {
         key.cloned()
     }
 
+    /// Check that the keys are available.
+    ///
+    /// If any keys are already cached then these are used, so that the check doesn't call Auth0 on every probe.
+    /// Otherwise the keys are fetched from Auth0 and cached for later requests to use.
+    ///
+    /// # Returns
+    /// Details of why the keys couldn't be fetched, if they couldn't.
+    pub async fn check_health(&self) -> Result<(), String> {
+        if !self.cache.lock().unwrap().borrow().keys.is_empty() {
+            tracing::debug!("Keys present in cache");
+            return Ok(());
+        }
+
+        match self.fetch().await {
+            Some(keys) => {
+                self.cache.lock().unwrap().borrow_mut().keys = keys.keys;
+                Ok(())
+            }
+            None => Err("Failed to fetch the JWKS from Auth0".to_owned()),
+        }
+    }
+
     /// Fetch the keys from Auth0 and store them into the cache.
     ///
     /// # Returns
@@ -253,4 +275,64 @@ mod tests {
 
         m2.assert();
     }
+
+    #[actix_rt::test]
+    async fn check_health_success() {
+        let _ = env_logger::try_init();
+
+        let m = mock("GET", "/.well-known/jwks.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"keys": []}"#)
+            .create();
+
+        let sut = Keys::new(&Domain::new(mockito::server_url()));
+
+        check!(sut.check_health().await.is_ok());
+
+        m.assert();
+    }
+
+    #[actix_rt::test]
+    async fn check_health_from_cache() {
+        let _ = env_logger::try_init();
+
+        let m = mock("GET", "/.well-known/jwks.json")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::to_string(&JWKSet {
+                    keys: vec![load_keys("myKeyId")],
+                })
+                .unwrap(),
+            )
+            .expect(1)
+            .create();
+
+        let sut = Keys::new(&Domain::new(mockito::server_url()));
+
+        check!(sut.check_health().await.is_ok());
+        check!(sut.check_health().await.is_ok());
+        let_assert!(Some(_) = sut.get("myKeyId").await);
+
+        m.assert();
+    }
+
+    #[actix_rt::test]
+    async fn check_health_failed() {
+        let _ = env_logger::try_init();
+
+        let m = mock("GET", "/.well-known/jwks.json")
+            .with_status(503)
+            .with_header("content-type", "text/plain")
+            .with_body(r#"Unavailable"#)
+            .create();
+
+        let sut = Keys::new(&Domain::new(mockito::server_url()));
+
+        let_assert!(Err(detail) = sut.check_health().await);
+        check!(detail == "Failed to fetch the JWKS from Auth0");
+
+        m.assert();
+    }
 }
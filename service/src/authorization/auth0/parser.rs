@@ -44,6 +44,14 @@ impl AccessTokenParser {
         }
     }
 
+    /// Check that the keys needed to parse tokens can be fetched.
+    ///
+    /// # Returns
+    /// Details of why the keys couldn't be fetched, if they couldn't.
+    pub async fn check_health(&self) -> Result<(), String> {
+        self.keys.check_health().await
+    }
+
     /// Attempt to parse the provided token.
     ///
     /// # Parameters
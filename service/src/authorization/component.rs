@@ -1,5 +1,6 @@
-use crate::server::RouteConfigurer;
+use crate::{health::HealthCheck, server::RouteConfigurer};
 use actix_web::web::ServiceConfig;
+use async_trait::async_trait;
 use std::sync::Arc;
 
 use super::{auth0::AccessTokenParser, auth0::Domain};
@@ -39,3 +40,10 @@ impl RouteConfigurer for Component {
         config.data(self.access_token_parser.clone());
     }
 }
+
+#[async_trait(?Send)]
+impl HealthCheck for Component {
+    async fn check_health(&self) -> Result<(), String> {
+        self.access_token_parser.check_health().await
+    }
+}
@@ -1,14 +1,14 @@
 use chrono::{DateTime, Utc};
 
 /// Enumeration of supported Principal IDs
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Principal {
     /// The authorized principal is a User.
     User(String),
 }
 
 /// Details of a Security Context for a request.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SecurityContext {
     /// The authorized principal.
     pub principal: Principal,
@@ -19,7 +19,7 @@ pub struct SecurityContext {
 }
 
 /// Details of whether the request is authorized or not.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Authorization {
     Unauthorized,
     Authorized(SecurityContext),
@@ -0,0 +1,7 @@
+pub mod component;
+mod http;
+mod problems;
+mod service;
+
+pub use problems::*;
+pub use service::*;
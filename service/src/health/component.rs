@@ -0,0 +1,74 @@
+use super::{HealthCheck, HealthUseCase};
+use crate::{http::openapi::Operation, server::RouteConfigurer};
+use actix_web::web::ServiceConfig;
+use std::{sync::Arc, time::Duration};
+
+/// The default time to allow each health check before treating the component as unhealthy.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Component for reporting on the health of the service.
+pub struct Component {
+    service: Arc<HealthUseCase>,
+}
+
+/// Builder for building the health component.
+pub struct Builder {
+    checks: Vec<(String, Arc<dyn HealthCheck>)>,
+    timeout: Duration,
+}
+
+/// Create a new instance of the health builder.
+pub fn new() -> Builder {
+    Builder {
+        checks: vec![],
+        timeout: DEFAULT_TIMEOUT,
+    }
+}
+
+impl Builder {
+    /// Register a component whose health determines whether the service is ready to serve requests.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the component, as used in the health report
+    /// - `check` - The health check for the component
+    pub fn with_check<N>(mut self, name: N, check: Arc<dyn HealthCheck>) -> Self
+    where
+        N: Into<String>,
+    {
+        self.checks.push((name.into(), check));
+
+        self
+    }
+
+    /// Specify how long each health check is allowed to take before the component is treated as unhealthy.
+    ///
+    /// # Parameters
+    /// - `timeout` - The time to allow
+    #[allow(dead_code)]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+
+        self
+    }
+
+    /// Build the actual health component.
+    pub fn build(self) -> Arc<Component> {
+        Arc::new(Component {
+            service: Arc::new(HealthUseCase {
+                checks: self.checks,
+                timeout: self.timeout,
+            }),
+        })
+    }
+}
+
+impl RouteConfigurer for Component {
+    fn configure_routes(&self, config: &mut ServiceConfig) {
+        config.data(self.service.clone());
+        super::http::configure_routes(config);
+    }
+
+    fn describe_operations(&self) -> Vec<Operation> {
+        super::http::operations()
+    }
+}
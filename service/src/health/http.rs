@@ -0,0 +1,55 @@
+use super::{SystemHealth, SERVICE_UNHEALTHY};
+use crate::http::{
+    negotiation::APPLICATION_JSON, openapi::Operation, problem::NOT_ACCEPTABLE, routes,
+    schema::JsonSchema,
+};
+use actix_http::http::{Method, StatusCode};
+use actix_web::web::ServiceConfig;
+
+mod live;
+mod ready;
+
+/// Configure the HTTP routes for the health of the service.
+///
+/// # Parameters
+/// - `config` - The HTTP Server configuration to register the routes with.
+pub fn configure_routes(config: &mut ServiceConfig) {
+    routes::new("/health/live")
+        .with_route(Method::GET, live::handle)
+        .register(config);
+    routes::new("/health/ready")
+        .with_route(Method::GET, ready::handle)
+        .register(config);
+}
+
+/// Describe the HTTP routes for the health of the service.
+///
+/// # Returns
+/// The operations of every route registered by `configure_routes`.
+pub fn operations() -> Vec<Operation> {
+    vec![
+        Operation::new(Method::GET, "/health/live", "getLiveness")
+            .with_summary(
+                "Check that the service is running, without checking any of its dependencies",
+            )
+            .with_tag("health")
+            .with_response(
+                StatusCode::OK,
+                "The service is running",
+                APPLICATION_JSON.name,
+                SystemHealth::json_schema(),
+            )
+            .with_problem(&NOT_ACCEPTABLE),
+        Operation::new(Method::GET, "/health/ready", "getReadiness")
+            .with_summary("Check that every component of the service is able to serve requests")
+            .with_tag("health")
+            .with_response(
+                StatusCode::OK,
+                "Every component is healthy",
+                APPLICATION_JSON.name,
+                SystemHealth::json_schema(),
+            )
+            .with_problem(&SERVICE_UNHEALTHY)
+            .with_problem(&NOT_ACCEPTABLE),
+    ]
+}
@@ -0,0 +1,21 @@
+use crate::{
+    health::{HealthStatus, SystemHealth},
+    http::{Response, SimpleRespondable},
+};
+use actix_http::http::header::{CacheControl, CacheDirective};
+use std::collections::BTreeMap;
+
+/// Report that the service is running
+///
+/// None of the components are checked, so that the service isn't restarted because a dependency is unavailable.
+///
+/// # Returns
+/// The HTTP Response indicating that the service is running.
+pub async fn handle() -> Response<SimpleRespondable<SystemHealth>> {
+    SimpleRespondable::new(SystemHealth {
+        status: HealthStatus::Up,
+        components: BTreeMap::new(),
+    })
+    .with_header(CacheControl(vec![CacheDirective::NoStore]))
+    .into()
+}
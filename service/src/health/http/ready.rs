@@ -0,0 +1,29 @@
+use crate::{
+    health::{HealthStatus, HealthUseCase, SystemHealth, SERVICE_UNHEALTHY},
+    http::{problem::Problem, Response, SimpleRespondable},
+};
+use actix_http::http::header::{CacheControl, CacheDirective};
+use actix_web::web::Data;
+use std::sync::Arc;
+
+/// Report whether every component of the service is able to serve requests
+///
+/// # Parameters
+/// - `health` - The use case to check the health of the components
+///
+/// # Returns
+/// The HTTP Response containing the health of every component, or else a Problem containing the same details if any
+/// of them are unhealthy.
+pub async fn handle(
+    health: Data<Arc<HealthUseCase>>,
+) -> Result<Response<SimpleRespondable<SystemHealth>>, Problem> {
+    let health = health.check_health().await;
+
+    if health.status == HealthStatus::Down {
+        return Err(Problem::from(SERVICE_UNHEALTHY).with_extra("components", health.components));
+    }
+
+    Ok(SimpleRespondable::new(health)
+        .with_header(CacheControl(vec![CacheDirective::NoStore]))
+        .into())
+}
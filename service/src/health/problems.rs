@@ -0,0 +1,24 @@
+use crate::http::problem::{ProblemDocumentation, SimpleProblemType};
+use actix_http::http::StatusCode;
+
+/// Problem to indicate that at least one component of the service is unhealthy.
+pub const SERVICE_UNHEALTHY: SimpleProblemType = SimpleProblemType {
+    problem_type: "/problems/service_unhealthy",
+    problem_title: "The service is not ready to handle requests",
+    status_code: StatusCode::SERVICE_UNAVAILABLE,
+};
+
+/// Build the documentation of the problem types that the health checks can return.
+///
+/// # Returns
+/// The documentation of the problem types.
+pub fn problem_documentation() -> Vec<ProblemDocumentation> {
+    vec![ProblemDocumentation::new(
+        &SERVICE_UNHEALTHY,
+        "At least one of the components that the service depends on failed its health check.",
+    )
+    .with_extension(
+        "components",
+        "The health of each component, keyed by name. Each entry has the `status` of the component, the `latencyMs` of its health check and a `detail` of why it is unhealthy.",
+    )]
+}
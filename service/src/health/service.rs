@@ -0,0 +1,216 @@
+use crate::http::schema::JsonSchema;
+use async_trait::async_trait;
+use futures::future::join_all;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Trait for all components that can report on whether they are able to serve requests.
+///
+/// The checks are only ever run from within a request handler, so they don't need to be `Send`.
+#[async_trait(?Send)]
+pub trait HealthCheck: Send + Sync {
+    /// Check whether the component is healthy.
+    ///
+    /// # Returns
+    /// Details of why the component is unhealthy, if it is.
+    async fn check_health(&self) -> Result<(), String>;
+}
+
+/// Whether something is healthy or not.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HealthStatus {
+    /// Healthy.
+    Up,
+    /// Unhealthy.
+    Down,
+}
+
+impl JsonSchema for HealthStatus {
+    fn json_schema() -> Value {
+        json!({"type": "string", "enum": ["UP", "DOWN"]})
+    }
+}
+
+/// The health of a single component.
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentHealth {
+    /// Whether the component is healthy.
+    pub status: HealthStatus,
+    /// How long the health check took, in milliseconds.
+    pub latency_ms: u64,
+    /// Details of why the component is unhealthy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// The health of the entire service.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SystemHealth {
+    /// Whether the service is healthy, which is only the case if every component is healthy.
+    pub status: HealthStatus,
+    /// The health of each component, keyed by the name of the component.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+/// Use Case for checking the health of every component.
+pub struct HealthUseCase {
+    pub(super) checks: Vec<(String, Arc<dyn HealthCheck>)>,
+    pub(super) timeout: Duration,
+}
+
+impl HealthUseCase {
+    /// Check the health of every component at the same time.
+    ///
+    /// Any health check that takes longer than the timeout is treated as unhealthy.
+    ///
+    /// # Returns
+    /// The health of the entire service.
+    pub async fn check_health(&self) -> SystemHealth {
+        let results = join_all(self.checks.iter().map(|(name, check)| async move {
+            let start = Instant::now();
+            let result = actix_rt::time::timeout(self.timeout, check.check_health()).await;
+            let latency = start.elapsed();
+
+            let (status, detail) = match result {
+                Ok(Ok(())) => (HealthStatus::Up, None),
+                Ok(Err(detail)) => (HealthStatus::Down, Some(detail)),
+                Err(_) => (
+                    HealthStatus::Down,
+                    Some("The health check timed out".to_owned()),
+                ),
+            };
+
+            if status == HealthStatus::Down {
+                tracing::warn!(component = ?name, detail = ?detail, latency = ?latency, "Component is unhealthy");
+            }
+
+            let health = ComponentHealth {
+                status,
+                latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+                detail,
+            };
+
+            (name.clone(), health)
+        }))
+        .await;
+
+        let components: BTreeMap<String, ComponentHealth> = results.into_iter().collect();
+        let status = if components
+            .values()
+            .all(|component| component.status == HealthStatus::Up)
+        {
+            HealthStatus::Up
+        } else {
+            HealthStatus::Down
+        };
+
+        SystemHealth { status, components }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+
+    struct Healthy;
+
+    #[async_trait(?Send)]
+    impl HealthCheck for Healthy {
+        async fn check_health(&self) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    struct Unhealthy;
+
+    #[async_trait(?Send)]
+    impl HealthCheck for Unhealthy {
+        async fn check_health(&self) -> Result<(), String> {
+            Err("Something went wrong".to_owned())
+        }
+    }
+
+    struct Slow;
+
+    #[async_trait(?Send)]
+    impl HealthCheck for Slow {
+        async fn check_health(&self) -> Result<(), String> {
+            actix_rt::time::delay_for(Duration::from_secs(5)).await;
+            Ok(())
+        }
+    }
+
+    fn use_case(checks: Vec<(&str, Arc<dyn HealthCheck>)>) -> HealthUseCase {
+        HealthUseCase {
+            checks: checks
+                .into_iter()
+                .map(|(name, check)| (name.to_owned(), check))
+                .collect(),
+            timeout: Duration::from_millis(100),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn no_checks() {
+        let health = use_case(vec![]).check_health().await;
+
+        check!(health.status == HealthStatus::Up);
+        check!(serde_json::to_value(&health).unwrap() == json!({"status": "UP"}));
+    }
+
+    #[actix_rt::test]
+    async fn all_healthy() {
+        let health = use_case(vec![
+            ("first", Arc::new(Healthy)),
+            ("second", Arc::new(Healthy)),
+        ])
+        .check_health()
+        .await;
+
+        check!(health.status == HealthStatus::Up);
+        check!(health.components.len() == 2);
+        let_assert!(Some(first) = health.components.get("first"));
+        check!(first.status == HealthStatus::Up);
+        check!(first.detail.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn one_unhealthy() {
+        let health = use_case(vec![
+            ("good", Arc::new(Healthy)),
+            ("bad", Arc::new(Unhealthy)),
+        ])
+        .check_health()
+        .await;
+
+        check!(health.status == HealthStatus::Down);
+        let_assert!(Some(good) = health.components.get("good"));
+        check!(good.status == HealthStatus::Up);
+        let_assert!(Some(bad) = health.components.get("bad"));
+        check!(bad.status == HealthStatus::Down);
+        check!(bad.detail == Some("Something went wrong".to_owned()));
+    }
+
+    #[actix_rt::test]
+    async fn timed_out() {
+        let health = use_case(vec![("slow", Arc::new(Slow))])
+            .check_health()
+            .await;
+
+        check!(health.status == HealthStatus::Down);
+        let_assert!(Some(slow) = health.components.get("slow"));
+        check!(slow.status == HealthStatus::Down);
+        check!(slow.detail == Some("The health check timed out".to_owned()));
+        check!(slow.latency_ms < 5000);
+    }
+}
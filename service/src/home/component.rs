@@ -1,17 +1,22 @@
 use super::{HomeLinksUseCase, LinkContributor};
-use crate::server::RouteConfigurer;
+use crate::{
+    http::{hal::Relations, openapi::Operation, schema::Schema},
+    server::RouteConfigurer,
+};
 use actix_web::web::ServiceConfig;
 use std::sync::Arc;
 
 /// Component for the home document.
 pub struct Component {
     service: Arc<HomeLinksUseCase>,
+    relations: Arc<Relations>,
 }
 
 /// Builder for building the home document component.
 #[derive(Default)]
 pub struct Builder {
     contributors: Vec<Arc<dyn LinkContributor>>,
+    relations: Relations,
 }
 
 /// Create a new instance of the home document builder.
@@ -31,19 +36,74 @@ impl Builder {
         self
     }
 
+    /// Register a new CURIE that custom link relations can be namespaced with.
+    ///
+    /// # Parameters
+    /// - `prefix` - The prefix of the CURIE
+    /// - `href` - The URI Template for the documentation of relations with this prefix
+    pub fn with_curie<P, H>(mut self, prefix: P, href: H) -> Self
+    where
+        P: Into<String>,
+        H: Into<String>,
+    {
+        self.relations = self.relations.with_curie(prefix, href);
+
+        self
+    }
+
+    /// Register a new custom link relation that can be used in HAL documents.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation, including the CURIE prefix if it has one
+    /// - `description` - A description of what the link relation means
+    pub fn with_relation<R, D>(mut self, rel: R, description: D) -> Self
+    where
+        R: Into<String>,
+        D: Into<String>,
+    {
+        self.relations = self.relations.with_relation(rel, description);
+
+        self
+    }
+
+    /// Register a link relation that should also be sent as a `Link` header on responses.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation, including the CURIE prefix if it has one
+    pub fn with_link_header<R>(mut self, rel: R) -> Self
+    where
+        R: Into<String>,
+    {
+        self.relations = self.relations.with_link_header(rel);
+
+        self
+    }
+
     /// Build the actual home document component.
     pub fn build(self) -> Arc<Component> {
         let service = Arc::new(HomeLinksUseCase {
             contributors: self.contributors,
         });
 
-        Arc::new(Component { service })
+        Arc::new(Component {
+            service,
+            relations: Arc::new(self.relations),
+        })
     }
 }
 
 impl RouteConfigurer for Component {
     fn configure_routes(&self, config: &mut ServiceConfig) {
         config.data(self.service.clone());
+        config.data(self.relations.clone());
         super::http::configure_routes(config);
     }
+
+    fn describe_operations(&self) -> Vec<Operation> {
+        super::http::operations()
+    }
+
+    fn describe_schemas(&self) -> Vec<Schema> {
+        super::http::schemas()
+    }
 }
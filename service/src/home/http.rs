@@ -1,11 +1,76 @@
-use actix_web::web::{get, resource, ServiceConfig};
+use crate::http::{
+    hal::APPLICATION_HAL_JSON,
+    openapi::{hal_schema, Operation, BEARER_AUTH},
+    problem::{NOT_ACCEPTABLE, NOT_FOUND, UNAUTHORIZED},
+    routes,
+    schema::{JsonSchema, Schema},
+};
+use actix_http::http::{Method, StatusCode};
+use actix_web::web::ServiceConfig;
+use serde_json::json;
 
 mod get;
+mod relation;
+
+/// The name of the schema describing the home document.
+const HOME_SCHEMA: &str = "home";
 
 /// Configure the HTTP routes for the home document.
 ///
 /// # Parameters
 /// - `config` - The HTTP Server configuration to register the routes with.
 pub fn configure_routes(config: &mut ServiceConfig) {
-    config.service(resource("/").route(get().to(get::handle)));
+    routes::new("/")
+        .with_route(Method::GET, get::handle)
+        .register(config);
+    routes::new("/rels/{rel}")
+        .with_route(Method::GET, relation::handle)
+        .register(config);
+}
+
+/// Describe the HTTP routes for the home document.
+///
+/// # Returns
+/// The operations of every route registered by `configure_routes`.
+pub fn operations() -> Vec<Operation> {
+    vec![
+        Operation::new(Method::GET, "/", "getHome")
+            .with_summary("Get the home document, linking to the rest of the API")
+            .with_tag("home")
+            .with_projection()
+            .with_response(
+                StatusCode::OK,
+                "The home document",
+                APPLICATION_HAL_JSON.name,
+                hal_schema(get::HomeDocument::json_schema()["properties"].clone()),
+            )
+            .with_problem(&UNAUTHORIZED)
+            .with_problem(&NOT_ACCEPTABLE)
+            .with_security(BEARER_AUTH, &[])
+            .with_anonymous_access(),
+        Operation::new(Method::GET, "/rels/{rel}", "getRelation")
+            .with_summary("Get the documentation of a custom link relation")
+            .with_tag("home")
+            .with_path_parameter("rel", "The link relation, including the CURIE prefix")
+            .with_projection()
+            .with_response(
+                StatusCode::OK,
+                "The documentation of the link relation",
+                APPLICATION_HAL_JSON.name,
+                hal_schema(json!({
+                    "name": {"type": "string"},
+                    "description": {"type": "string"}
+                })),
+            )
+            .with_problem(&NOT_FOUND)
+            .with_problem(&NOT_ACCEPTABLE),
+    ]
+}
+
+/// Describe the schemas of the documents returned by the routes for the home document.
+///
+/// # Returns
+/// The schemas of the documents.
+pub fn schemas() -> Vec<Schema> {
+    vec![Schema::of::<get::HomeDocument>(HOME_SCHEMA)]
 }
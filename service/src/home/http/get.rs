@@ -1,4 +1,9 @@
-use crate::http::{hal::HalDocument, Response, SimpleRespondable};
+use super::HOME_SCHEMA;
+use crate::http::{
+    hal::{HalDocument, HalRespondable},
+    schema::{schema_path, JsonSchema},
+    Response,
+};
 use crate::{authorization::Authorization, home::HomeLinksUseCase};
 use actix_http::http::{
     header::{CacheControl, CacheDirective},
@@ -9,9 +14,11 @@ use serde::Serialize;
 use std::sync::Arc;
 
 /// The actual home document contents.
-#[derive(Serialize)]
+#[derive(Serialize, JsonSchema)]
 pub struct HomeDocument {
+    /// The name of the service.
     pub name: &'static str,
+    /// The version of the service.
     pub version: &'static str,
 }
 
@@ -19,18 +26,19 @@ pub struct HomeDocument {
 pub async fn handle(
     home_links: Data<Arc<HomeLinksUseCase>>,
     authorization: Authorization,
-) -> Response<SimpleRespondable<HalDocument>> {
+) -> Response<HalRespondable> {
     let mut hal_document = HalDocument::new(HomeDocument {
         name: env!("CARGO_PKG_NAME"),
         version: env!("CARGO_PKG_VERSION"),
-    });
+    })
+    .with_link("profile", schema_path(HOME_SCHEMA));
 
     let links = home_links.generate_links(&authorization).await;
     for (name, link) in links {
         hal_document = hal_document.with_link(name, link);
     }
 
-    SimpleRespondable::from(hal_document)
+    HalRespondable::from(hal_document)
         .with_status_code(StatusCode::OK)
         .with_header(CacheControl(vec![
             CacheDirective::Public,
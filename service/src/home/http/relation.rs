@@ -0,0 +1,50 @@
+use crate::http::{
+    hal::{HalDocument, HalResource, HalRespondable, Relations},
+    problem::{Problem, NOT_FOUND},
+    Response,
+};
+use actix_http::http::header::{CacheControl, CacheDirective};
+use actix_web::web::{Data, Path};
+use std::sync::Arc;
+
+/// The documentation of a single link relation.
+#[derive(HalResource)]
+pub struct RelationDocument {
+    pub name: String,
+    pub description: String,
+    #[hal(link = "self")]
+    pub self_link: String,
+}
+
+/// Generate the documentation for a custom link relation
+///
+/// # Parameters
+/// - `path` - The parsed URL path, containing the requested link relation
+/// - `relations` - The registry of known link relations
+///
+/// # Returns
+/// The HTTP Response. Either the documentation of the link relation as a HAL document or else a Problem indicating
+/// that the link relation isn't known.
+pub async fn handle(
+    path: Path<String>,
+    relations: Data<Arc<Relations>>,
+) -> Result<Response<HalRespondable>, Problem> {
+    let name = path.0;
+    let description = relations
+        .description(&name)
+        .ok_or_else(|| Problem::from(NOT_FOUND))?
+        .to_owned();
+
+    let hal_document = HalDocument::from(RelationDocument {
+        self_link: format!("/rels/{}", name),
+        name,
+        description,
+    });
+
+    Ok(HalRespondable::from(hal_document)
+        .with_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(3600),
+        ]))
+        .into())
+}
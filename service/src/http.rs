@@ -1,12 +1,28 @@
+pub mod conditional;
+pub mod correlation;
 pub mod hal;
+pub mod i18n;
+pub mod negotiation;
+pub mod openapi;
+pub mod paging;
+pub mod patch;
+pub mod preconditions;
 pub mod problem;
+pub mod routes;
+pub mod schema;
+pub mod validation;
 
 use actix_http::{
-    http::{header::Header, HeaderMap, StatusCode},
+    http::{
+        header::{self, Header},
+        HeaderMap, StatusCode,
+    },
     Error, Response as HttpResponse,
 };
-use actix_web::Responder;
-use futures::future::{ok, Ready};
+use actix_web::{error::ErrorInternalServerError, HttpRequest, Responder};
+use futures::future::{ok, ready, Ready};
+use negotiation::{Format, MediaType, DEFAULT_MEDIA_TYPES};
+use problem::{Problem, NOT_ACCEPTABLE};
 use serde::Serialize;
 
 /// Trait that anything able to represent a response can implement.
@@ -29,6 +45,34 @@ pub trait Respondable {
         HeaderMap::new()
     }
 
+    /// The media types that the response body can be produced as, in order of preference
+    ///
+    /// # Returns
+    /// The media types to negotiate between
+    fn media_types(&self) -> &'static [MediaType] {
+        DEFAULT_MEDIA_TYPES
+    }
+
+    /// The URI of the profile that the response body conforms to, if there is one, which is included as the `profile`
+    /// parameter of the `Content-Type` header when the body is rendered as JSON.
+    ///
+    /// # Returns
+    /// The URI of the profile
+    fn profile(&self) -> Option<String> {
+        None
+    }
+
+    /// Give the response the chance to adjust itself for the request that it is responding to.
+    ///
+    /// # Parameters
+    /// - `req` - The HTTP Request that is being responded to
+    ///
+    /// # Returns
+    /// A Problem to respond with instead, if the response can't be produced for this request.
+    fn prepare(&mut self, _req: &HttpRequest) -> Result<(), Problem> {
+        Ok(())
+    }
+
     /// Retrieve the body of the response
     ///
     /// # Returns
@@ -57,6 +101,7 @@ where
 {
     status_code: StatusCode,
     headers: HeaderMap,
+    media_types: &'static [MediaType],
     body: T,
 }
 
@@ -72,10 +117,20 @@ where
         Self {
             status_code: StatusCode::OK,
             headers: HeaderMap::new(),
+            media_types: DEFAULT_MEDIA_TYPES,
             body,
         }
     }
 
+    /// Specify the media types that the body can be produced as, instead of the default ones.
+    ///
+    /// # Parameters
+    /// - `media_types` - The media types to negotiate between, in order of preference
+    pub fn with_media_types(mut self, media_types: &'static [MediaType]) -> Self {
+        self.media_types = media_types;
+        self
+    }
+
     /// Specify the status code to use.
     ///
     /// # Parameters
@@ -121,6 +176,10 @@ where
         self.headers.clone()
     }
 
+    fn media_types(&self) -> &'static [MediaType] {
+        self.media_types
+    }
+
     fn body(self) -> Self::Body {
         self.body
     }
@@ -153,15 +212,144 @@ where
     type Error = Error;
     type Future = Ready<Result<HttpResponse, Error>>;
 
-    fn respond_to(self, _req: &actix_web::HttpRequest) -> Self::Future {
-        let mut response = HttpResponse::build(self.0.status_code());
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        let mut respondable = self.0;
+        if let Err(problem) = respondable.prepare(req) {
+            return ok(problem.render(req));
+        }
+
+        let media_types = respondable.media_types();
+        let names: Vec<&str> = media_types
+            .iter()
+            .map(|media_type| media_type.name)
+            .collect();
+
+        let media_type = if let Some(index) = negotiation::negotiate(req, &names) {
+            media_types[index]
+        } else {
+            tracing::debug!(available = ?names, "No acceptable media type for response");
+            let problem = Problem::from(NOT_ACCEPTABLE).with_extra("acceptable", names);
+            return ok(problem.render(req));
+        };
+
+        let status_code = respondable.status_code();
+        let headers = respondable.headers();
+        // The profile is a JSON Schema, so only describes the body when it is rendered as JSON.
+        let content_type = match respondable.profile() {
+            Some(profile) if media_type.format == Format::Json => {
+                format!(r#"{}; profile="{}""#, media_type.name, profile)
+            }
+            _ => media_type.name.to_owned(),
+        };
+
+        if status_code.is_success() && conditional::is_not_modified(req, &headers) {
+            tracing::debug!("Client already has the current representation of the resource");
+
+            let mut response = HttpResponse::build(StatusCode::NOT_MODIFIED);
+            for (key, value) in &headers {
+                response.header(key, value.clone());
+            }
+
+            return ok(response.header(header::VARY, "Accept").finish());
+        }
+
+        let mut response = HttpResponse::build(status_code);
 
-        for (key, value) in self.0.headers().iter() {
-            response.set_header(key, value.clone());
+        for (key, value) in &headers {
+            response.header(key, value.clone());
         }
 
-        let built = response.json(self.0.body());
+        let body = match media_type.format.serialize(&respondable.body()) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!(e = ?e, media_type = ?media_type, "Failed to serialize response");
+                return ready(Err(ErrorInternalServerError(e)));
+            }
+        };
+
+        let built = response
+            .set_header(header::CONTENT_TYPE, content_type)
+            .header(header::VARY, "Accept")
+            .body(body);
 
         ok(built)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_http::body::Body;
+    use actix_http::http::header::{ETag, EntityTag};
+    use actix_web::test::TestRequest;
+    use assert2::check;
+    use serde_json::json;
+
+    fn body(response: &HttpResponse) -> Vec<u8> {
+        match response.body().as_ref() {
+            Some(Body::Bytes(bytes)) => bytes.to_vec(),
+            _ => vec![],
+        }
+    }
+
+    fn respondable() -> SimpleRespondable<serde_json::Value> {
+        SimpleRespondable::new(json!({"name": "Test"}))
+            .with_header(ETag(EntityTag::strong("abc".to_owned())))
+    }
+
+    #[actix_rt::test]
+    async fn respond_with_body() {
+        let req = TestRequest::get().to_http_request();
+
+        let response = Response(respondable()).respond_to(&req).await.unwrap();
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers().get(header::ETAG).unwrap() == r#""abc""#);
+        check!(response.headers().get(header::CONTENT_TYPE).unwrap() == "application/json");
+
+        check!(body(&response) == br#"{"name":"Test"}"#);
+    }
+
+    #[actix_rt::test]
+    async fn respond_with_other_media_types() {
+        const MEDIA_TYPES: &[MediaType] = &[negotiation::APPLICATION_CBOR];
+        let req = TestRequest::get().to_http_request();
+
+        let response = Response(respondable().with_media_types(MEDIA_TYPES))
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers().get(header::CONTENT_TYPE).unwrap() == "application/cbor");
+    }
+
+    #[actix_rt::test]
+    async fn respond_not_modified() {
+        let req = TestRequest::get()
+            .header(header::IF_NONE_MATCH, r#"W/"abc""#)
+            .to_http_request();
+
+        let response = Response(respondable()).respond_to(&req).await.unwrap();
+
+        check!(response.status() == StatusCode::NOT_MODIFIED);
+        check!(response.headers().get(header::ETAG).unwrap() == r#""abc""#);
+        check!(response.headers().get(header::CONTENT_TYPE).is_none());
+
+        check!(body(&response).is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn respond_error_ignores_conditions() {
+        let req = TestRequest::get()
+            .header(header::IF_NONE_MATCH, r#""abc""#)
+            .to_http_request();
+
+        let response = Response(respondable().with_status_code(StatusCode::CONFLICT))
+            .respond_to(&req)
+            .await
+            .unwrap();
+
+        check!(response.status() == StatusCode::CONFLICT);
+    }
+}
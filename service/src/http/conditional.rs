@@ -0,0 +1,184 @@
+use actix_http::http::{
+    header::{self, EntityTag, Header, HttpDate, IfModifiedSince, IfNoneMatch},
+    HeaderMap, Method,
+};
+use actix_web::HttpRequest;
+use std::str::FromStr;
+
+/// Determine whether the client already has an up-to-date copy of the resource, based on the conditional headers in
+/// the request and the validators in the response headers.
+///
+/// This implements the `If-None-Match` and `If-Modified-Since` evaluation from RFC 7232 for safe requests. If the
+/// request has an `If-None-Match` header then this alone decides the outcome, using the weak comparison function.
+/// Otherwise the `If-Modified-Since` header is compared to the `Last-Modified` header of the response.
+///
+/// # Parameters
+/// - `req` - The HTTP Request that is being responded to
+/// - `headers` - The headers of the response that would otherwise be sent
+///
+/// # Returns
+/// True if a `304 Not Modified` response should be sent instead. False if the full response should be sent.
+pub fn is_not_modified(req: &HttpRequest, headers: &HeaderMap) -> bool {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return false;
+    }
+
+    if req.headers().contains_key(header::IF_NONE_MATCH) {
+        let etag = parse_header::<EntityTag>(headers, &header::ETAG);
+
+        return match IfNoneMatch::parse(req) {
+            Ok(IfNoneMatch::Any) => etag.is_some(),
+            Ok(IfNoneMatch::Items(tags)) => match etag {
+                Some(etag) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+                None => false,
+            },
+            Err(e) => {
+                tracing::debug!(e = ?e, "Failed to parse If-None-Match header");
+                false
+            }
+        };
+    }
+
+    if req.headers().contains_key(header::IF_MODIFIED_SINCE) {
+        let last_modified = parse_header::<HttpDate>(headers, &header::LAST_MODIFIED);
+
+        return match IfModifiedSince::parse(req) {
+            Ok(IfModifiedSince(since)) => match last_modified {
+                Some(modified) => modified <= since,
+                None => false,
+            },
+            Err(e) => {
+                tracing::debug!(e = ?e, "Failed to parse If-Modified-Since header");
+                false
+            }
+        };
+    }
+
+    false
+}
+
+/// Parse a single header value out of a set of response headers.
+///
+/// # Parameters
+/// - `headers` - The headers to look in
+/// - `name` - The name of the header to parse
+///
+/// # Returns
+/// The parsed header value, or `None` if it was missing or couldn't be parsed.
+fn parse_header<T>(headers: &HeaderMap, name: &header::HeaderName) -> Option<T>
+where
+    T: FromStr,
+{
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_http::http::header::{HeaderValue, IntoHeaderValue};
+    use actix_web::test::TestRequest;
+    use assert2::check;
+    use test_case::test_case;
+
+    fn response_headers(etag: Option<EntityTag>, last_modified: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(etag) = etag {
+            headers.insert(header::ETAG, etag.try_into().unwrap());
+        }
+        if let Some(last_modified) = last_modified {
+            headers.insert(
+                header::LAST_MODIFIED,
+                HeaderValue::from_str(last_modified).unwrap(),
+            );
+        }
+        headers
+    }
+
+    #[test]
+    fn no_conditional_headers() {
+        let req = TestRequest::get().to_http_request();
+        let headers = response_headers(
+            Some(EntityTag::strong("abc".to_owned())),
+            Some("Sun, 07 Feb 2021 12:00:00 GMT"),
+        );
+
+        check!(!is_not_modified(&req, &headers));
+    }
+
+    #[test_case(r#""abc""#, EntityTag::strong("abc".to_owned()), true ; "strong matches strong")]
+    #[test_case(r#"W/"abc""#, EntityTag::strong("abc".to_owned()), true ; "weak matches strong")]
+    #[test_case(r#""abc""#, EntityTag::weak("abc".to_owned()), true ; "strong matches weak")]
+    #[test_case(r#"W/"abc""#, EntityTag::weak("abc".to_owned()), true ; "weak matches weak")]
+    #[test_case(r#""def", "abc""#, EntityTag::strong("abc".to_owned()), true ; "one of many")]
+    #[test_case(r#""def""#, EntityTag::strong("abc".to_owned()), false ; "different tag")]
+    #[test_case("*", EntityTag::strong("abc".to_owned()), true ; "any")]
+    #[test_case("not an etag", EntityTag::strong("abc".to_owned()), false ; "unparsable")]
+    fn if_none_match(header: &str, etag: EntityTag, expected: bool) {
+        let req = TestRequest::get()
+            .header(header::IF_NONE_MATCH, header)
+            .to_http_request();
+        let headers = response_headers(Some(etag), None);
+
+        check!(is_not_modified(&req, &headers) == expected);
+    }
+
+    #[test]
+    fn if_none_match_without_etag() {
+        let req = TestRequest::get()
+            .header(header::IF_NONE_MATCH, "*")
+            .to_http_request();
+        let headers = response_headers(None, Some("Sun, 07 Feb 2021 12:00:00 GMT"));
+
+        check!(!is_not_modified(&req, &headers));
+    }
+
+    #[test]
+    fn if_none_match_takes_precedence() {
+        let req = TestRequest::get()
+            .header(header::IF_NONE_MATCH, r#""def""#)
+            .header(header::IF_MODIFIED_SINCE, "Sun, 07 Feb 2021 12:00:00 GMT")
+            .to_http_request();
+        let headers = response_headers(
+            Some(EntityTag::strong("abc".to_owned())),
+            Some("Sun, 07 Feb 2021 12:00:00 GMT"),
+        );
+
+        check!(!is_not_modified(&req, &headers));
+    }
+
+    #[test_case("Sun, 07 Feb 2021 12:00:00 GMT", true ; "same time")]
+    #[test_case("Sun, 07 Feb 2021 13:00:00 GMT", true ; "later time")]
+    #[test_case("Sun, 07 Feb 2021 11:00:00 GMT", false ; "earlier time")]
+    #[test_case("yesterday", false ; "unparsable")]
+    fn if_modified_since(header: &str, expected: bool) {
+        let req = TestRequest::get()
+            .header(header::IF_MODIFIED_SINCE, header)
+            .to_http_request();
+        let headers = response_headers(None, Some("Sun, 07 Feb 2021 12:00:00 GMT"));
+
+        check!(is_not_modified(&req, &headers) == expected);
+    }
+
+    #[test]
+    fn if_modified_since_without_last_modified() {
+        let req = TestRequest::get()
+            .header(header::IF_MODIFIED_SINCE, "Sun, 07 Feb 2021 12:00:00 GMT")
+            .to_http_request();
+        let headers = response_headers(Some(EntityTag::strong("abc".to_owned())), None);
+
+        check!(!is_not_modified(&req, &headers));
+    }
+
+    #[test]
+    fn unsafe_method() {
+        let req = TestRequest::post()
+            .header(header::IF_NONE_MATCH, r#""abc""#)
+            .to_http_request();
+        let headers = response_headers(Some(EntityTag::strong("abc".to_owned())), None);
+
+        check!(!is_not_modified(&req, &headers));
+    }
+}
@@ -0,0 +1,116 @@
+use actix_http::http::HeaderMap;
+use opentelemetry::trace::TraceContextExt;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Header containing the ID of the request.
+pub const X_REQUEST_ID: &str = "x-request-id";
+
+/// Header containing the ID of the trace that the request was handled in.
+pub const X_TRACE_ID: &str = "x-trace-id";
+
+/// The longest request ID that will be accepted from the client.
+const MAX_REQUEST_ID_LENGTH: usize = 200;
+
+/// The details needed to correlate a response with the traces and logs produced by the request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Correlation {
+    /// The ID of the request, either provided by the client or generated if it wasn't.
+    pub request_id: String,
+    /// The OpenTelemetry Trace ID of the trace that the request is handled in, if it is being traced.
+    pub trace_id: Option<String>,
+}
+
+impl Correlation {
+    /// Build the correlation details for a request.
+    ///
+    /// # Parameters
+    /// - `headers` - The headers of the request, which might contain a request ID from the client
+    /// - `span` - The tracing span that the request is handled in
+    ///
+    /// # Returns
+    /// The correlation details
+    pub fn new(headers: &HeaderMap, span: &tracing::Span) -> Self {
+        let request_id = headers
+            .get(X_REQUEST_ID)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| is_valid_request_id(value))
+            .map_or_else(|| uuid::Uuid::new_v4().to_string(), ToOwned::to_owned);
+
+        let context = span.context();
+        let otel_span = context.span();
+        let span_context = otel_span.span_context();
+        let trace_id = if span_context.is_valid() {
+            Some(span_context.trace_id().to_hex())
+        } else {
+            None
+        };
+
+        Self {
+            request_id,
+            trace_id,
+        }
+    }
+}
+
+/// Check whether a request ID provided by the client is safe to use.
+///
+/// # Parameters
+/// - `request_id` - The request ID
+///
+/// # Returns
+/// True if the request ID can be used. False if a new one should be generated instead.
+fn is_valid_request_id(request_id: &str) -> bool {
+    !request_id.is_empty()
+        && request_id.len() <= MAX_REQUEST_ID_LENGTH
+        && request_id.chars().all(|c| c.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_http::http::{HeaderName, HeaderValue};
+    use assert2::check;
+    use test_case::test_case;
+
+    fn correlation(request_id: Option<&str>) -> Correlation {
+        let mut headers = HeaderMap::new();
+        if let Some(request_id) = request_id {
+            headers.insert(
+                HeaderName::from_static(X_REQUEST_ID),
+                HeaderValue::from_str(request_id).unwrap(),
+            );
+        }
+
+        Correlation::new(&headers, &tracing::Span::none())
+    }
+
+    #[test]
+    fn generate_request_id() {
+        let first = correlation(None);
+        let second = correlation(None);
+
+        check!(first.request_id.parse::<uuid::Uuid>().is_ok());
+        check!(first.request_id != second.request_id);
+    }
+
+    #[test_case("abc-123" ; "simple")]
+    #[test_case("  abc-123  " ; "surrounding whitespace")]
+    fn use_provided_request_id(request_id: &str) {
+        check!(correlation(Some(request_id)).request_id == "abc-123");
+    }
+
+    #[test_case("" ; "blank")]
+    #[test_case("abc 123" ; "whitespace")]
+    #[test_case(&"a".repeat(201) ; "too long")]
+    fn replace_invalid_request_id(request_id: &str) {
+        let correlation = correlation(Some(request_id));
+
+        check!(correlation.request_id.parse::<uuid::Uuid>().is_ok());
+    }
+
+    #[test]
+    fn no_trace() {
+        check!(correlation(None).trace_id.is_none());
+    }
+}
@@ -1,7 +1,20 @@
+mod collection;
 mod document;
+mod embedded;
 mod links;
+mod projection;
+mod relations;
+mod resource;
 mod response;
+mod templates;
 
+#[allow(unused_imports)]
+pub use collection::*;
 pub use document::*;
+pub use embedded::*;
 pub use links::*;
+pub use projection::*;
+pub use relations::*;
+pub use resource::*;
 pub use response::*;
+pub use templates::*;
@@ -0,0 +1,343 @@
+use super::{HalDocument, HalRespondable, Link};
+use crate::http::paging::{PagePosition, PageRequest};
+use serde::Serialize;
+use uritemplate::UriTemplate;
+
+/// The link relation used for the items in a collection.
+const ITEM_RELATION: &str = "item";
+
+/// Representation of a single page of a collection, to be returned as a HAL document.
+#[derive(Debug)]
+pub struct HalCollection {
+    /// The base URL of the collection, without any query string.
+    base: String,
+    /// The page that was requested.
+    request: PageRequest,
+    /// The items in this page.
+    items: Vec<HalDocument>,
+    /// The total number of items in the collection, if known.
+    total: Option<u64>,
+    /// The cursor for the next page, if cursor-based paging is used and there is a next page.
+    next_cursor: Option<String>,
+    /// The cursor for the previous page, if cursor-based paging is used and there is a previous page.
+    previous_cursor: Option<String>,
+}
+
+/// The paging metadata of a collection.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PageModel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub page_size: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+impl HalCollection {
+    /// Create a new collection document for a page of items.
+    ///
+    /// # Parameters
+    /// - `base` - The base URL of the collection, without any query string
+    /// - `request` - The page that was requested
+    /// - `items` - The items in this page
+    pub fn new<B, I, D>(base: B, request: PageRequest, items: I) -> Self
+    where
+        B: Into<String>,
+        I: IntoIterator<Item = D>,
+        D: Into<HalDocument>,
+    {
+        Self {
+            base: base.into(),
+            request,
+            items: items.into_iter().map(Into::into).collect(),
+            total: None,
+            next_cursor: None,
+            previous_cursor: None,
+        }
+    }
+
+    /// Specify the total number of items in the collection.
+    ///
+    /// # Parameters
+    /// - `total` - The total number of items
+    pub fn with_total(self, total: u64) -> Self {
+        Self {
+            total: Some(total),
+            ..self
+        }
+    }
+
+    /// Specify the cursor to use for the next page.
+    ///
+    /// # Parameters
+    /// - `cursor` - The cursor for the next page
+    #[allow(dead_code)]
+    pub fn with_next_cursor<S>(self, cursor: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            next_cursor: Some(cursor.into()),
+            ..self
+        }
+    }
+
+    /// Specify the cursor to use for the previous page.
+    ///
+    /// # Parameters
+    /// - `cursor` - The cursor for the previous page
+    #[allow(dead_code)]
+    pub fn with_previous_cursor<S>(self, cursor: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            previous_cursor: Some(cursor.into()),
+            ..self
+        }
+    }
+
+    /// Build a link to a page of this collection.
+    ///
+    /// # Parameters
+    /// - `position` - The position of the page, or `None` for the start of the collection
+    ///
+    /// # Returns
+    /// The link to the page
+    fn page_link(&self, position: Option<&PagePosition>) -> Link {
+        let mut template = UriTemplate::new(&format!("{}{{?offset,cursor,pageSize}}", self.base));
+
+        match position {
+            Some(PagePosition::Offset(offset)) => {
+                template.set("offset", offset.to_string());
+            }
+            Some(PagePosition::Cursor(cursor)) => {
+                template.set("cursor", cursor.as_str());
+            }
+            None => {}
+        }
+
+        template
+            .set("pageSize", self.request.page_size.to_string())
+            .build()
+            .into()
+    }
+
+    /// Generate the navigation links for the collection.
+    ///
+    /// # Returns
+    /// The link relations and links to navigate around the collection.
+    fn navigation_links(&self) -> Vec<(&'static str, Link)> {
+        let page_size = self.request.page_size;
+        let mut links = vec![("self", self.page_link(Some(&self.request.position)))];
+
+        match &self.request.position {
+            PagePosition::Offset(offset) => {
+                let offset = *offset;
+
+                links.push(("first", self.page_link(Some(&PagePosition::Offset(0)))));
+
+                if offset > 0 {
+                    let previous = PagePosition::Offset(offset.saturating_sub(page_size));
+                    links.push(("prev", self.page_link(Some(&previous))));
+                }
+
+                // An offset so large that the next page can't be represented can't have a next page either.
+                let next = offset
+                    .checked_add(page_size)
+                    .filter(|next| match self.total {
+                        Some(total) => *next < total,
+                        None => self.items.len() as u64 >= page_size,
+                    });
+                if let Some(next) = next {
+                    let next = PagePosition::Offset(next);
+                    links.push(("next", self.page_link(Some(&next))));
+                }
+
+                if let Some(total) = self.total {
+                    let last = if total == 0 {
+                        0
+                    } else {
+                        (total - 1) / page_size * page_size
+                    };
+                    links.push(("last", self.page_link(Some(&PagePosition::Offset(last)))));
+                }
+            }
+            PagePosition::Cursor(_) => {
+                links.push(("first", self.page_link(None)));
+
+                if let Some(cursor) = &self.previous_cursor {
+                    let previous = PagePosition::Cursor(cursor.clone());
+                    links.push(("prev", self.page_link(Some(&previous))));
+                }
+
+                if let Some(cursor) = &self.next_cursor {
+                    let next = PagePosition::Cursor(cursor.clone());
+                    links.push(("next", self.page_link(Some(&next))));
+                }
+            }
+        }
+
+        links
+    }
+}
+
+impl From<HalCollection> for HalDocument {
+    fn from(collection: HalCollection) -> Self {
+        let links = collection.navigation_links();
+
+        let (offset, cursor) = match collection.request.position {
+            PagePosition::Offset(offset) => (Some(offset), None),
+            PagePosition::Cursor(cursor) => (None, Some(cursor)),
+        };
+
+        let mut document = HalDocument::new(PageModel {
+            offset,
+            cursor,
+            page_size: collection.request.page_size,
+            total: collection.total,
+        });
+
+        for (rel, link) in links {
+            document = document.with_link(rel, link);
+        }
+        document.collection = true;
+
+        // The items are always an array, even if there are zero or one of them.
+        document.with_embedded_list(ITEM_RELATION, collection.items)
+    }
+}
+
+impl From<HalCollection> for HalRespondable {
+    fn from(collection: HalCollection) -> Self {
+        HalDocument::from(collection).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::hal::{Embedded, Links};
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    fn offset_request(offset: u64, page_size: u64) -> PageRequest {
+        PageRequest {
+            position: PagePosition::Offset(offset),
+            page_size,
+        }
+    }
+
+    fn items(count: usize) -> Vec<HalDocument> {
+        (0..count)
+            .map(|i| HalDocument::new(json!({ "index": i })))
+            .collect()
+    }
+
+    fn href(document: &HalDocument, rel: &str) -> Option<String> {
+        match document.links.get(rel) {
+            Some(Links::Single(link)) => Some(link.href.clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn first_page_with_total() {
+        let document: HalDocument = HalCollection::new("/items", offset_request(0, 10), items(10))
+            .with_total(25)
+            .into();
+
+        check!(href(&document, "self") == Some("/items?offset=0&pageSize=10".to_owned()));
+        check!(href(&document, "first") == Some("/items?offset=0&pageSize=10".to_owned()));
+        check!(href(&document, "prev").is_none());
+        check!(href(&document, "next") == Some("/items?offset=10&pageSize=10".to_owned()));
+        check!(href(&document, "last") == Some("/items?offset=20&pageSize=10".to_owned()));
+
+        check!(document.data == json!({"offset": 0, "pageSize": 10, "total": 25}));
+
+        let_assert!(Some(Embedded::Multiple(embedded)) = document.embedded.get("item"));
+        check!(embedded.len() == 10);
+    }
+
+    #[test]
+    fn middle_page_with_total() {
+        let document: HalDocument = HalCollection::new("/items", offset_request(15, 10), items(10))
+            .with_total(40)
+            .into();
+
+        check!(href(&document, "prev") == Some("/items?offset=5&pageSize=10".to_owned()));
+        check!(href(&document, "next") == Some("/items?offset=25&pageSize=10".to_owned()));
+        check!(href(&document, "last") == Some("/items?offset=30&pageSize=10".to_owned()));
+    }
+
+    #[test]
+    fn last_page_with_total() {
+        let document: HalDocument = HalCollection::new("/items", offset_request(20, 10), items(5))
+            .with_total(25)
+            .into();
+
+        check!(href(&document, "prev") == Some("/items?offset=10&pageSize=10".to_owned()));
+        check!(href(&document, "next").is_none());
+        check!(href(&document, "last") == Some("/items?offset=20&pageSize=10".to_owned()));
+    }
+
+    #[test]
+    fn empty_collection() {
+        let document: HalDocument = HalCollection::new("/items", offset_request(0, 10), items(0))
+            .with_total(0)
+            .into();
+
+        check!(href(&document, "next").is_none());
+        check!(href(&document, "last") == Some("/items?offset=0&pageSize=10".to_owned()));
+
+        let serialized = serde_json::to_value(document).unwrap();
+        check!(serialized["_embedded"] == json!({"item": []}));
+    }
+
+    #[test]
+    fn unknown_total() {
+        let full: HalDocument =
+            HalCollection::new("/items", offset_request(0, 10), items(10)).into();
+        check!(href(&full, "next") == Some("/items?offset=10&pageSize=10".to_owned()));
+        check!(href(&full, "last").is_none());
+        check!(full.data == json!({"offset": 0, "pageSize": 10}));
+
+        let partial: HalDocument =
+            HalCollection::new("/items", offset_request(0, 10), items(3)).into();
+        check!(href(&partial, "next").is_none());
+    }
+
+    #[test]
+    fn offset_at_limit() {
+        let document: HalDocument =
+            HalCollection::new("/items", offset_request(u64::MAX, 10), items(10)).into();
+
+        check!(href(&document, "next").is_none());
+        check!(
+            href(&document, "prev") == Some(format!("/items?offset={}&pageSize=10", u64::MAX - 10))
+        );
+    }
+
+    #[test]
+    fn cursor_page() {
+        let request = PageRequest {
+            position: PagePosition::Cursor("abc".to_owned()),
+            page_size: 10,
+        };
+        let document: HalDocument = HalCollection::new("/items", request, items(10))
+            .with_previous_cursor("aaa")
+            .with_next_cursor("def")
+            .into();
+
+        check!(href(&document, "self") == Some("/items?cursor=abc&pageSize=10".to_owned()));
+        check!(href(&document, "first") == Some("/items?pageSize=10".to_owned()));
+        check!(href(&document, "prev") == Some("/items?cursor=aaa&pageSize=10".to_owned()));
+        check!(href(&document, "next") == Some("/items?cursor=def&pageSize=10".to_owned()));
+        check!(href(&document, "last").is_none());
+
+        check!(document.data == json!({"cursor": "abc", "pageSize": 10}));
+    }
+}
@@ -1,9 +1,9 @@
-use super::{Link, Links};
-use crate::http::SimpleRespondable;
-use actix_http::http::header::ContentType;
+use super::{Embedded, Link, Links, Template};
 use serde::Serialize;
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub use newlanding_service_derive::HalResource;
 
 /// Representation of a HAL document.
 #[derive(Debug, Serialize, Clone, PartialEq)]
@@ -15,6 +15,23 @@ pub struct HalDocument {
     /// The set of linsk in the document.
     #[serde(rename = "_links", skip_serializing_if = "BTreeMap::is_empty")]
     pub links: BTreeMap<String, Links>,
+
+    /// The set of embedded resources in the document.
+    #[serde(rename = "_embedded", skip_serializing_if = "BTreeMap::is_empty")]
+    pub embedded: BTreeMap<String, Embedded>,
+
+    /// The set of HAL-FORMS templates describing the actions that can be performed on the document.
+    #[serde(rename = "_templates", skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, Template>,
+
+    /// The names of any fields that the data can have but that are absent from this particular document.
+    #[serde(skip)]
+    pub optional_fields: BTreeSet<String>,
+
+    /// Whether the document is a page of a collection, in which case projections select the fields of the embedded
+    /// items rather than of the document itself.
+    #[serde(skip)]
+    pub collection: bool,
 }
 
 impl HalDocument {
@@ -30,6 +47,10 @@ impl HalDocument {
         Self {
             data,
             links: BTreeMap::new(),
+            embedded: BTreeMap::new(),
+            templates: BTreeMap::new(),
+            optional_fields: BTreeSet::new(),
+            collection: false,
         }
     }
 
@@ -54,20 +75,118 @@ impl HalDocument {
 
         self
     }
-}
 
-impl From<HalDocument> for SimpleRespondable<HalDocument> {
-    fn from(body: HalDocument) -> Self {
-        let content_type = ContentType("application/hal+json".parse().unwrap());
+    /// Add a set of links to the document, ensuring that the link relation is always represented as an array.
+    ///
+    /// This is useful for relations where clients should always see the same shape regardless of how many links
+    /// there are. Any later calls to `with_link` for the same relation will append to this array.
+    ///
+    /// - `rel` - The link relation of the links
+    /// - `links` - The actual links
+    #[allow(dead_code)]
+    pub fn with_links<N, I, L>(mut self, rel: N, links: I) -> Self
+    where
+        N: Into<String>,
+        I: IntoIterator<Item = L>,
+        L: Into<Link>,
+    {
+        let rel = rel.into();
+
+        let mut result = match self.links.remove(&rel) {
+            None => vec![],
+            Some(Links::Single(link)) => vec![link],
+            Some(Links::Multiple(links)) => links,
+        };
+        result.extend(links.into_iter().map(Into::into));
+
+        self.links.insert(rel, Links::Multiple(result));
 
-        SimpleRespondable::new(body).with_header(content_type)
+        self
+    }
+
+    /// Add a new embedded resource to the document
+    ///
+    /// - `rel` - The link relation of the embedded resource
+    /// - `document` - The actual embedded resource
+    #[allow(dead_code)]
+    pub fn with_embedded<N, D>(mut self, rel: N, document: D) -> Self
+    where
+        N: Into<String>,
+        D: Into<HalDocument>,
+    {
+        let rel = rel.into();
+        let document = document.into();
+
+        let embedded = match self.embedded.remove(&rel) {
+            None => Embedded::from(document),
+            Some(embedded) => embedded.append(document),
+        };
+
+        self.embedded.insert(rel, embedded);
+
+        self
+    }
+
+    /// Add a list of embedded resources to the document, ensuring that the link relation is always represented as an
+    /// array even if there are zero or one resources in it. Any later calls to `with_embedded` for the same relation
+    /// will append to this array.
+    ///
+    /// - `rel` - The link relation of the embedded resources
+    /// - `documents` - The actual embedded resources
+    pub fn with_embedded_list<N, I, D>(mut self, rel: N, documents: I) -> Self
+    where
+        N: Into<String>,
+        I: IntoIterator<Item = D>,
+        D: Into<HalDocument>,
+    {
+        let rel = rel.into();
+
+        let mut result = match self.embedded.remove(&rel) {
+            None => vec![],
+            Some(Embedded::Single(document)) => vec![document],
+            Some(Embedded::Multiple(documents)) => documents,
+        };
+        result.extend(documents.into_iter().map(Into::into));
+
+        self.embedded.insert(rel, Embedded::Multiple(result));
+
+        self
+    }
+
+    /// Add a new HAL-FORMS template to the document, replacing any existing template with the same name
+    ///
+    /// - `name` - The name of the template
+    /// - `template` - The actual template
+    pub fn with_template<N>(mut self, name: N, template: Template) -> Self
+    where
+        N: Into<String>,
+    {
+        self.templates.insert(name.into(), template);
+
+        self
+    }
+
+    /// Record that the data can have a field that might be absent from this document, so that clients can still
+    /// ask for it when projecting the document.
+    ///
+    /// - `name` - The name of the field
+    pub fn with_optional_field<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.optional_fields.insert(name.into());
+
+        self
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::http::Respondable;
+    use crate::http::{
+        hal::{HalRespondable, APPLICATION_HAL_JSON},
+        Respondable,
+    };
     use assert2::{check, let_assert};
     use serde::Serialize;
     use serde_json::json;
@@ -86,6 +205,7 @@ mod tests {
 
         check!(document.data == json!({"name": "Graham"}));
         check!(document.links.is_empty());
+        check!(document.embedded.is_empty());
     }
 
     #[test]
@@ -121,18 +241,166 @@ mod tests {
         check!(links == &vec![Link::from("/foo"), Link::from("/bar")]);
     }
 
+    #[test]
+    fn with_forced_array_links() {
+        let document = HalDocument::new(json!({}))
+            .with_links("item", vec!["/foo"])
+            .with_links("empty", Vec::<Link>::new());
+
+        check!(document.links.len() == 2);
+
+        let_assert!(Some(Links::Multiple(links)) = document.links.get("item"));
+        check!(links == &vec![Link::from("/foo")]);
+
+        let_assert!(Some(Links::Multiple(links)) = document.links.get("empty"));
+        check!(links.is_empty());
+
+        check!(
+            serde_json::to_value(document).unwrap()
+                == json!({
+                    "_links": {
+                        "empty": [],
+                        "item": [{"href": "/foo"}]
+                    }
+                })
+        );
+    }
+
+    #[test]
+    fn with_link_appends_to_forced_array() {
+        let document = HalDocument::new(json!({}))
+            .with_links("item", vec!["/foo"])
+            .with_link("item", "/bar");
+
+        let_assert!(Some(Links::Multiple(links)) = document.links.get("item"));
+        check!(links == &vec![Link::from("/foo"), Link::from("/bar")]);
+    }
+
+    #[test]
+    fn with_single_embedded() {
+        let author = HalDocument::new(Body {
+            name: "Graham".to_owned(),
+        })
+        .with_link("self", "/users/abc");
+
+        let document =
+            HalDocument::new(json!({"title": "Post"})).with_embedded("author", author.clone());
+
+        check!(document.embedded.len() == 1);
+
+        let_assert!(Some(Embedded::Single(embedded)) = document.embedded.get("author"));
+        check!(embedded == &author);
+    }
+
+    #[test]
+    fn with_repeated_embedded() {
+        let first = HalDocument::new(json!({"name": "first"}));
+        let second = HalDocument::new(json!({"name": "second"}));
+
+        let document = HalDocument::new(json!({}))
+            .with_embedded("item", first.clone())
+            .with_embedded("item", second.clone());
+
+        check!(document.embedded.len() == 1);
+
+        let_assert!(Some(Embedded::Multiple(embedded)) = document.embedded.get("item"));
+        check!(embedded == &vec![first, second]);
+    }
+
+    #[test]
+    fn with_forced_array_embedded() {
+        let first = HalDocument::new(json!({"name": "first"}));
+        let second = HalDocument::new(json!({"name": "second"}));
+
+        let document = HalDocument::new(json!({}))
+            .with_embedded_list("item", vec![first.clone()])
+            .with_embedded_list("empty", Vec::<HalDocument>::new())
+            .with_embedded("item", second.clone());
+
+        let_assert!(Some(Embedded::Multiple(embedded)) = document.embedded.get("item"));
+        check!(embedded == &vec![first, second]);
+
+        let_assert!(Some(Embedded::Multiple(embedded)) = document.embedded.get("empty"));
+        check!(embedded.is_empty());
+    }
+
+    #[test]
+    fn serialize_embedded() {
+        let document = HalDocument::new(json!({"title": "Post"}))
+            .with_link("self", "/posts/1")
+            .with_embedded(
+                "author",
+                HalDocument::new(json!({"name": "Graham"})).with_link("self", "/users/abc"),
+            )
+            .with_embedded("comment", HalDocument::new(json!({"text": "First"})))
+            .with_embedded("comment", HalDocument::new(json!({"text": "Second"})));
+
+        let serialized = serde_json::to_value(document).unwrap();
+
+        check!(
+            serialized
+                == json!({
+                    "title": "Post",
+                    "_links": {
+                        "self": {"href": "/posts/1"}
+                    },
+                    "_embedded": {
+                        "author": {
+                            "name": "Graham",
+                            "_links": {
+                                "self": {"href": "/users/abc"}
+                            }
+                        },
+                        "comment": [
+                            {"text": "First"},
+                            {"text": "Second"}
+                        ]
+                    }
+                })
+        );
+    }
+
+    #[test]
+    fn serialize_templates() {
+        let document = HalDocument::new(json!({"name": "Graham"}))
+            .with_link("self", "/users/abc")
+            .with_template(
+                "edit",
+                Template::new(&actix_http::http::Method::PATCH)
+                    .with_content_type("application/merge-patch+json"),
+            );
+
+        check!(document.templates.len() == 1);
+
+        check!(
+            serde_json::to_value(document).unwrap()
+                == json!({
+                    "name": "Graham",
+                    "_links": {
+                        "self": {"href": "/users/abc"}
+                    },
+                    "_templates": {
+                        "edit": {
+                            "method": "PATCH",
+                            "contentType": "application/merge-patch+json",
+                            "properties": []
+                        }
+                    }
+                })
+        );
+    }
+
     #[test]
     fn to_respondable() {
         let document = HalDocument::new(Body {
             name: "Graham".to_owned(),
         });
-        let respondable = SimpleRespondable::from(document);
+        let respondable = HalRespondable::from(document);
 
         check!(respondable.status_code() == 200);
 
-        let headers = respondable.headers();
-        let content_type = headers.get("content-type");
-        check!(content_type.unwrap() == "application/hal+json");
+        let media_types = respondable.media_types();
+        check!(media_types[0] == APPLICATION_HAL_JSON);
 
         let body = respondable.body();
         check!(
@@ -141,4 +409,50 @@ mod tests {
             })
         );
     }
+
+    #[derive(HalResource)]
+    #[serde(rename_all = "camelCase")]
+    struct Post {
+        title: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        sub_title: Option<String>,
+        #[hal(link = "self")]
+        self_link: String,
+        #[hal(link = "related")]
+        related: Option<String>,
+        #[hal(link = "tag")]
+        tags: Vec<String>,
+        #[hal(embedded = "author")]
+        author: HalDocument,
+        #[hal(embedded = "comment")]
+        comments: Vec<HalDocument>,
+    }
+
+    #[test]
+    fn derive_hal_resource() {
+        let document = HalDocument::from(Post {
+            title: "First Post".to_owned(),
+            sub_title: None,
+            self_link: "/posts/1".to_owned(),
+            related: None,
+            tags: vec!["/tags/rust".to_owned()],
+            author: HalDocument::new(json!({"name": "Graham"})),
+            comments: vec![],
+        });
+
+        check!(
+            serde_json::to_value(document).unwrap()
+                == json!({
+                    "title": "First Post",
+                    "_links": {
+                        "self": {"href": "/posts/1"},
+                        "tag": [{"href": "/tags/rust"}]
+                    },
+                    "_embedded": {
+                        "author": {"name": "Graham"},
+                        "comment": []
+                    }
+                })
+        );
+    }
 }
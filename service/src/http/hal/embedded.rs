@@ -0,0 +1,105 @@
+use super::HalDocument;
+use serde::Serialize;
+
+/// Representation of a set of 1+ embedded resources.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Embedded {
+    /// Wrapper around a single embedded resource
+    Single(HalDocument),
+    /// Wrapper around multiple embedded resources.
+    Multiple(Vec<HalDocument>),
+}
+
+impl Embedded {
+    /// Append a new embedded resource. This will ensure that the end result is an `Embedded::Multiple` even if the
+    /// input was an `Embedded::Single`.
+    ///
+    /// # Parameters
+    /// - `document` - The embedded resource to append
+    pub fn append(self, document: HalDocument) -> Self {
+        let documents = match self {
+            Embedded::Single(previous) => {
+                vec![previous, document]
+            }
+            Embedded::Multiple(mut previous) => {
+                previous.push(document);
+                previous
+            }
+        };
+
+        Embedded::Multiple(documents)
+    }
+
+    /// Get mutable access to each of the embedded resources, however many there are.
+    ///
+    /// # Returns
+    /// An iterator over the embedded resources.
+    pub fn documents_mut(&mut self) -> std::slice::IterMut<'_, HalDocument> {
+        match self {
+            Embedded::Single(document) => std::slice::from_mut(document).iter_mut(),
+            Embedded::Multiple(documents) => documents.iter_mut(),
+        }
+    }
+}
+
+impl From<HalDocument> for Embedded {
+    fn from(document: HalDocument) -> Self {
+        Self::Single(document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    #[test]
+    fn append_to_single_document() {
+        let document_1 = HalDocument::new(json!({"name": "abc"}));
+        let document_2 = HalDocument::new(json!({"name": "def"}));
+
+        let embedded = Embedded::from(document_1.clone());
+        let result = embedded.append(document_2.clone());
+
+        let_assert!(Embedded::Multiple(list) = result);
+        check!(list == vec![document_1, document_2]);
+    }
+
+    #[test]
+    fn append_to_multiple_documents() {
+        let document_1 = HalDocument::new(json!({"name": "abc"}));
+        let document_2 = HalDocument::new(json!({"name": "def"}));
+        let document_3 = HalDocument::new(json!({"name": "ghi"}));
+
+        let embedded = Embedded::Multiple(vec![document_1.clone(), document_3.clone()]);
+        let result = embedded.append(document_2.clone());
+
+        let_assert!(Embedded::Multiple(list) = result);
+        check!(list == vec![document_1, document_3, document_2]);
+    }
+
+    #[test]
+    fn documents_of_single() {
+        let document = HalDocument::new(json!({"name": "abc"}));
+        let mut embedded = Embedded::from(document.clone());
+
+        let documents: Vec<&mut HalDocument> = embedded.documents_mut().collect();
+        check!(documents == vec![&mut document.clone()]);
+    }
+
+    #[test]
+    fn documents_of_multiple() {
+        let document_1 = HalDocument::new(json!({"name": "abc"}));
+        let document_2 = HalDocument::new(json!({"name": "def"}));
+        let mut embedded = Embedded::Multiple(vec![document_1.clone(), document_2.clone()]);
+
+        for document in embedded.documents_mut() {
+            document.data = json!({});
+        }
+
+        let_assert!(Embedded::Multiple(list) = embedded);
+        check!(list == vec![HalDocument::new(json!({})), HalDocument::new(json!({}))]);
+    }
+}
@@ -1,13 +1,31 @@
 use serde::Serialize;
 
 /// Representation of a single link in a HAL document
-#[derive(Debug, Serialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
 pub struct Link {
     /// The href of the link itself
     pub href: String,
+    /// Whether the href is a URI Template or not.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub templated: bool,
+    /// A hint as to the media type of the target resource.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    /// A URL providing information about the deprecation of the link, if it is deprecated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<String>,
     /// The name of the link, if needed to disambiguate.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// A URI that hints about the profile of the target resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// A human-readable label for the link.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The language of the target resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hreflang: Option<String>,
 }
 
 impl<S> From<S> for Link
@@ -17,11 +35,179 @@ where
     fn from(href: S) -> Self {
         Self {
             href: href.into(),
-            name: None,
+            ..Self::default()
         }
     }
 }
 
+impl Link {
+    /// Create a new templated link from the source of an RFC-6570 URI Template, as would be passed to
+    /// `UriTemplate::new`.
+    ///
+    /// `UriTemplate` accepts any string at all, so the syntax of the template is checked here instead. A template that
+    /// clients wouldn't be able to expand is logged as a warning, since it is a mistake in the code building the link.
+    ///
+    /// # Parameters
+    /// - `template` - The URI Template for the link
+    pub fn templated<S>(template: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let template = template.into();
+        if let Some(error) = template_error(&template) {
+            tracing::warn!(template = ?template, error = ?error, "Invalid URI Template for link");
+        }
+
+        Self {
+            href: template,
+            templated: true,
+            ..Self::default()
+        }
+    }
+
+    /// Specify the media type hint of the link.
+    ///
+    /// # Parameters
+    /// - `media_type` - The media type of the target resource
+    #[allow(dead_code)]
+    pub fn with_type<S>(self, media_type: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            media_type: Some(media_type.into()),
+            ..self
+        }
+    }
+
+    /// Mark the link as deprecated.
+    ///
+    /// # Parameters
+    /// - `deprecation` - A URL providing information about the deprecation
+    #[allow(dead_code)]
+    pub fn with_deprecation<S>(self, deprecation: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            deprecation: Some(deprecation.into()),
+            ..self
+        }
+    }
+
+    /// Specify the name of the link.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the link
+    #[allow(dead_code)]
+    pub fn with_name<S>(self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Specify the profile of the link.
+    ///
+    /// # Parameters
+    /// - `profile` - A URI that hints about the profile of the target resource
+    #[allow(dead_code)]
+    pub fn with_profile<S>(self, profile: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            profile: Some(profile.into()),
+            ..self
+        }
+    }
+
+    /// Specify the title of the link.
+    ///
+    /// # Parameters
+    /// - `title` - The human-readable label for the link
+    #[allow(dead_code)]
+    pub fn with_title<S>(self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Specify the language of the link.
+    ///
+    /// # Parameters
+    /// - `hreflang` - The language of the target resource
+    #[allow(dead_code)]
+    pub fn with_hreflang<S>(self, hreflang: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            hreflang: Some(hreflang.into()),
+            ..self
+        }
+    }
+}
+
+/// Check the syntax of an RFC-6570 URI Template.
+///
+/// # Parameters
+/// - `template` - The source of the template
+///
+/// # Returns
+/// Details of what is wrong with the template, if anything is.
+fn template_error(template: &str) -> Option<String> {
+    let is_brace = |c| c == '{' || c == '}';
+    let mut rest = template;
+
+    while let Some(start) = rest.find(is_brace) {
+        if rest[start..].starts_with('}') {
+            return Some("'}' without a matching '{'".to_owned());
+        }
+
+        let expression = &rest[start + 1..];
+        let end = match expression.find(is_brace) {
+            Some(end) if expression[end..].starts_with('}') => end,
+            _ => return Some("'{' without a matching '}'".to_owned()),
+        };
+
+        let variables = expression[..end]
+            .strip_prefix(|c| "+#./;?&".contains(c))
+            .unwrap_or(&expression[..end]);
+        for variable in variables.split(',') {
+            let variable = variable.strip_suffix('*').unwrap_or(variable);
+            let mut parts = variable.splitn(2, ':');
+            let name = parts.next().unwrap_or_default();
+            let valid_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "_.%".contains(c));
+            let valid_prefix = match parts.next() {
+                Some(prefix) => match prefix.parse::<u16>() {
+                    Ok(length) => (1..10_000).contains(&length),
+                    Err(_) => false,
+                },
+                None => true,
+            };
+
+            if !valid_name || !valid_prefix {
+                return Some(format!("'{}' is not a valid variable", variable));
+            }
+        }
+
+        rest = &expression[end + 1..];
+    }
+
+    None
+}
+
 /// Representation of a set of 1+ links.
 #[derive(Debug, Serialize, Clone, PartialEq)]
 #[serde(untagged)]
@@ -56,15 +242,78 @@ impl Links {
 mod tests {
     use super::*;
     use assert2::{check, let_assert};
+    use serde_json::json;
+    use test_case::test_case;
 
     #[test]
     fn convert_str_to_link() {
         let link = Link::from("/test");
 
         check!(link.href == "/test");
+        check!(!link.templated);
         check!(link.name == None);
     }
 
+    #[test]
+    fn templated_link() {
+        let link = Link::templated("/users/{id}");
+
+        check!(link.href == "/users/{id}");
+        check!(link.templated);
+    }
+
+    #[test_case("/users" ; "no expressions")]
+    #[test_case("/users/{id}" ; "simple")]
+    #[test_case("/users{?offset,pageSize}" ; "operator and several variables")]
+    #[test_case("/rels/{rel}{#section:10}" ; "several expressions and prefix")]
+    #[test_case("/search{?terms*}" ; "explode")]
+    fn valid_template(template: &str) {
+        check!(template_error(template).is_none());
+    }
+
+    #[test_case("/users/{id", "'{' without a matching '}'" ; "unclosed")]
+    #[test_case("/users/id}", "'}' without a matching '{'" ; "unopened")]
+    #[test_case("/users/{a{b}}", "'{' without a matching '}'" ; "nested")]
+    #[test_case("/users/{}", "'' is not a valid variable" ; "empty")]
+    #[test_case("/users/{user id}", "'user id' is not a valid variable" ; "invalid name")]
+    #[test_case("/users/{id:0}", "'id:0' is not a valid variable" ; "invalid prefix")]
+    fn invalid_template(template: &str, expected: &str) {
+        let_assert!(Some(error) = template_error(template));
+        check!(error == expected);
+    }
+
+    #[test]
+    fn serialize_simple_link() {
+        let link = Link::from("/test");
+
+        check!(serde_json::to_value(link).unwrap() == json!({"href": "/test"}));
+    }
+
+    #[test]
+    fn serialize_full_link() {
+        let link = Link::templated("/users?q={query}")
+            .with_type("application/hal+json")
+            .with_deprecation("/docs/deprecated")
+            .with_name("search")
+            .with_profile("/profiles/users")
+            .with_title("Search Users")
+            .with_hreflang("en");
+
+        check!(
+            serde_json::to_value(link).unwrap()
+                == json!({
+                    "href": "/users?q={query}",
+                    "templated": true,
+                    "type": "application/hal+json",
+                    "deprecation": "/docs/deprecated",
+                    "name": "search",
+                    "profile": "/profiles/users",
+                    "title": "Search Users",
+                    "hreflang": "en"
+                })
+        );
+    }
+
     #[test]
     fn append_to_single_link() {
         let link_1 = Link::from("/abc");
@@ -0,0 +1,336 @@
+use super::{Embedded, HalDocument};
+use crate::http::{
+    problem::{Problem, VALIDATION_FAILED},
+    validation::InvalidParam,
+};
+use actix_web::web::Query;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// The raw projection parameters as provided on the query string.
+#[derive(Debug, Deserialize)]
+struct RawProjection {
+    fields: Option<String>,
+    embed: Option<String>,
+}
+
+/// Details of which parts of a HAL document the client has asked for.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Projection {
+    /// The fields of the document data to include, or `None` to include them all.
+    pub fields: Option<Vec<String>>,
+    /// The link relations of the embedded documents to include, or `None` to include them all.
+    pub embed: Option<Vec<String>>,
+}
+
+impl Projection {
+    /// Parse the projection from the `fields` and `embed` parameters of the provided query string.
+    ///
+    /// Both parameters are comma-separated lists. If a parameter is absent then nothing is removed, but if it is
+    /// present and empty then everything is removed.
+    ///
+    /// # Parameters
+    /// - `query` - The query string to parse
+    ///
+    /// # Returns
+    /// The projection, or a Problem indicating why the query string couldn't be parsed.
+    pub fn parse(query: &str) -> Result<Self, Problem> {
+        let raw = Query::<RawProjection>::from_query(query)
+            .map_err(|e| {
+                tracing::warn!(e = ?e, query = ?query, "Failed to parse projection parameters");
+                Problem::from(VALIDATION_FAILED)
+                    .with_detail_message("details.projection.unparsable")
+                    .with_source(&e)
+            })?
+            .into_inner();
+
+        Ok(Self {
+            fields: raw.fields.as_deref().map(split),
+            embed: raw.embed.as_deref().map(split),
+        })
+    }
+
+    /// Apply the projection to a HAL document, removing any parts of it that the client didn't ask for.
+    ///
+    /// For a page of a collection, the fields are those of each of the items rather than the paging details of the
+    /// page itself.
+    ///
+    /// Asking for a field that the document can't have is an error, since it's most likely a mistake by the client.
+    /// For a collection, that means a field that none of the items have, so an empty page accepts any fields. Asking
+    /// to embed a link relation that isn't present isn't an error, since many embedded relations are only sometimes
+    /// present - for example, the items in an empty page of a collection.
+    ///
+    /// # Parameters
+    /// - `document` - The document to apply the projection to
+    ///
+    /// # Returns
+    /// A `422 Unprocessable Entity` Problem if any of the requested fields are unknown.
+    pub fn apply(&self, document: &mut HalDocument) -> Result<(), Problem> {
+        if let Some(fields) = &self.fields {
+            if document.collection {
+                let items: Vec<&mut HalDocument> = document
+                    .embedded
+                    .values_mut()
+                    .flat_map(Embedded::documents_mut)
+                    .collect();
+
+                // With no items there's nothing to tell which fields they would have had.
+                if !items.is_empty() {
+                    check_fields(fields, |field| {
+                        items.iter().any(|item| has_field(item, field))
+                    })?;
+                }
+                for item in items {
+                    select_fields(item, fields);
+                }
+            } else {
+                check_fields(fields, |field| has_field(document, field))?;
+                select_fields(document, fields);
+            }
+        }
+
+        if let Some(embed) = &self.embed {
+            document.embedded = std::mem::take(&mut document.embedded)
+                .into_iter()
+                .filter(|(rel, _)| embed.contains(rel))
+                .collect::<BTreeMap<_, _>>();
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that all of the requested fields are known.
+///
+/// # Parameters
+/// - `fields` - The requested fields
+/// - `is_known` - Whether a single field is known
+///
+/// # Returns
+/// A `422 Unprocessable Entity` Problem if any of the requested fields are unknown.
+fn check_fields<F>(fields: &[String], is_known: F) -> Result<(), Problem>
+where
+    F: Fn(&str) -> bool,
+{
+    let unknown: Vec<InvalidParam> = fields
+        .iter()
+        .filter(|field| !is_known(field))
+        .map(|field| InvalidParam {
+            name: "fields".to_owned(),
+            code: "unknown_field".to_owned(),
+            reason: format!("The field '{}' is not known", field),
+        })
+        .collect();
+
+    if unknown.is_empty() {
+        Ok(())
+    } else {
+        tracing::debug!(unknown = ?unknown, "Unknown fields requested");
+        Err(Problem::from(VALIDATION_FAILED).with_invalid_params(unknown))
+    }
+}
+
+/// Check whether a document can have a field, even if it is absent from this particular document.
+///
+/// # Parameters
+/// - `document` - The document
+/// - `field` - The name of the field
+///
+/// # Returns
+/// True if the document can have the field. False if not.
+fn has_field(document: &HalDocument, field: &str) -> bool {
+    document.data.get(field).is_some() || document.optional_fields.contains(field)
+}
+
+/// Remove every field from the data of a document except the requested ones.
+///
+/// The profile link is removed as well, since the schema it refers to may require fields that are no longer present.
+///
+/// # Parameters
+/// - `document` - The document
+/// - `fields` - The requested fields
+fn select_fields(document: &mut HalDocument, fields: &[String]) {
+    let data = match std::mem::take(&mut document.data) {
+        Value::Object(data) => data,
+        _ => serde_json::Map::new(),
+    };
+
+    document.data = Value::Object(
+        data.into_iter()
+            .filter(|(name, _)| fields.contains(name))
+            .collect(),
+    );
+    document.links.remove("profile");
+}
+
+/// Split a comma-separated query string parameter into its values.
+///
+/// # Parameters
+/// - `value` - The value of the parameter
+///
+/// # Returns
+/// The individual values, with any blank values removed.
+fn split(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use serde_json::json;
+    use test_case::test_case;
+
+    fn document() -> HalDocument {
+        HalDocument::new(json!({"displayName": "Graham", "email": "graham@example.com"}))
+            .with_optional_field("socialProvider")
+            .with_link("self", "/users/1")
+            .with_link("profile", "/schemas/user")
+            .with_embedded_list("item", vec![HalDocument::new(json!({"id": 1}))])
+            .with_embedded_list("related", vec![HalDocument::new(json!({"id": 2}))])
+    }
+
+    #[test_case("", None, None ; "nothing")]
+    #[test_case("fields=displayName", Some(vec!["displayName"]), None ; "fields")]
+    #[test_case("fields=displayName,%20email", Some(vec!["displayName", "email"]), None ; "multiple fields")]
+    #[test_case("fields=", Some(vec![]), None ; "empty fields")]
+    #[test_case("embed=item,,related", None, Some(vec!["item", "related"]) ; "embed")]
+    #[test_case("pageSize=10&embed=item", None, Some(vec!["item"]) ; "other parameters")]
+    fn parse(query: &str, fields: Option<Vec<&str>>, embed: Option<Vec<&str>>) {
+        let to_strings = |values: Vec<&str>| {
+            values
+                .into_iter()
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>()
+        };
+
+        let_assert!(Ok(projection) = Projection::parse(query));
+        check!(projection.fields == fields.map(to_strings));
+        check!(projection.embed == embed.map(to_strings));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let_assert!(Err(problem) = Projection::parse("fields=a&fields=b"));
+        check!(problem.status == 422);
+    }
+
+    #[test]
+    fn apply_nothing() {
+        let mut projected = document();
+
+        let_assert!(Ok(()) = Projection::default().apply(&mut projected));
+        check!(projected == document());
+    }
+
+    #[test]
+    fn apply_fields() {
+        let mut projected = document();
+        let projection = Projection {
+            fields: Some(vec!["email".to_owned(), "socialProvider".to_owned()]),
+            embed: None,
+        };
+
+        let_assert!(Ok(()) = projection.apply(&mut projected));
+        check!(projected.data == json!({"email": "graham@example.com"}));
+        check!(projected.links.keys().collect::<Vec<_>>() == vec!["self"]);
+        check!(projected.embedded == document().embedded);
+    }
+
+    #[test]
+    fn apply_unknown_fields() {
+        let mut projected = document();
+        let projection = Projection {
+            fields: Some(vec![
+                "email".to_owned(),
+                "name".to_owned(),
+                "age".to_owned(),
+            ]),
+            embed: None,
+        };
+
+        let_assert!(Err(problem) = projection.apply(&mut projected));
+        check!(problem.status == 422);
+        check!(
+            problem.extra.get("invalid-params")
+                == Some(&json!([
+                    {"name": "fields", "code": "unknown_field", "reason": "The field 'name' is not known"},
+                    {"name": "fields", "code": "unknown_field", "reason": "The field 'age' is not known"}
+                ]))
+        );
+    }
+
+    fn collection(items: Vec<HalDocument>) -> HalDocument {
+        let mut document = HalDocument::new(json!({"offset": 0, "pageSize": 10}))
+            .with_embedded_list("item", items);
+        document.collection = true;
+        document
+    }
+
+    #[test]
+    fn apply_fields_to_collection() {
+        let mut projected = collection(vec![
+            HalDocument::new(json!({"displayName": "Graham", "email": "graham@example.com"})),
+            HalDocument::new(json!({"displayName": "Bob"})).with_optional_field("email"),
+        ]);
+        let projection = Projection {
+            fields: Some(vec!["email".to_owned()]),
+            embed: None,
+        };
+
+        let_assert!(Ok(()) = projection.apply(&mut projected));
+        check!(projected.data == json!({"offset": 0, "pageSize": 10}));
+        let_assert!(Some(Embedded::Multiple(items)) = projected.embedded.get("item"));
+        check!(items[0].data == json!({"email": "graham@example.com"}));
+        check!(items[1].data == json!({}));
+    }
+
+    #[test]
+    fn apply_unknown_fields_to_collection() {
+        let mut projected = collection(vec![HalDocument::new(json!({"displayName": "Graham"}))]);
+        let projection = Projection {
+            fields: Some(vec!["displayName".to_owned(), "pageSize".to_owned()]),
+            embed: None,
+        };
+
+        let_assert!(Err(problem) = projection.apply(&mut projected));
+        check!(problem.status == 422);
+        check!(
+            problem.extra.get("invalid-params")
+                == Some(&json!([
+                    {"name": "fields", "code": "unknown_field", "reason": "The field 'pageSize' is not known"}
+                ]))
+        );
+    }
+
+    #[test]
+    fn apply_fields_to_empty_collection() {
+        let mut projected = collection(vec![]);
+        let projection = Projection {
+            fields: Some(vec!["displayName".to_owned()]),
+            embed: None,
+        };
+
+        let_assert!(Ok(()) = projection.apply(&mut projected));
+        check!(projected == collection(vec![]));
+    }
+
+    #[test]
+    fn apply_embed() {
+        let mut projected = document();
+        let projection = Projection {
+            fields: None,
+            embed: Some(vec!["item".to_owned(), "unknown".to_owned()]),
+        };
+
+        let_assert!(Ok(()) = projection.apply(&mut projected));
+        check!(projected.data == document().data);
+        check!(projected.embedded.keys().collect::<Vec<_>>() == vec!["item"]);
+    }
+}
@@ -0,0 +1,422 @@
+use super::{Embedded, HalDocument, Link, Links};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The link relations registered with IANA, which are always allowed.
+/// See <https://www.iana.org/assignments/link-relations/link-relations.xhtml>.
+const IANA_RELATIONS: &[&str] = &[
+    "about",
+    "alternate",
+    "appendix",
+    "archives",
+    "author",
+    "blocked-by",
+    "bookmark",
+    "canonical",
+    "chapter",
+    "cite-as",
+    "collection",
+    "contents",
+    "convertedFrom",
+    "copyright",
+    "create-form",
+    "current",
+    "describedby",
+    "describes",
+    "disclosure",
+    "dns-prefetch",
+    "duplicate",
+    "edit",
+    "edit-form",
+    "edit-media",
+    "enclosure",
+    "external",
+    "first",
+    "glossary",
+    "help",
+    "hosts",
+    "hub",
+    "icon",
+    "index",
+    "intervalAfter",
+    "intervalBefore",
+    "intervalContains",
+    "intervalDisjoint",
+    "intervalDuring",
+    "intervalEquals",
+    "intervalFinishedBy",
+    "intervalFinishes",
+    "intervalIn",
+    "intervalMeets",
+    "intervalMetBy",
+    "intervalOverlappedBy",
+    "intervalOverlaps",
+    "intervalStartedBy",
+    "intervalStarts",
+    "item",
+    "last",
+    "latest-version",
+    "license",
+    "lrdd",
+    "manifest",
+    "mask-icon",
+    "media-feed",
+    "memento",
+    "micropub",
+    "modulepreload",
+    "monitor",
+    "monitor-group",
+    "next",
+    "next-archive",
+    "nofollow",
+    "noopener",
+    "noreferrer",
+    "opener",
+    "openid2.local_id",
+    "openid2.provider",
+    "original",
+    "p3pv1",
+    "payment",
+    "pingback",
+    "preconnect",
+    "predecessor-version",
+    "prefetch",
+    "preload",
+    "prerender",
+    "prev",
+    "prev-archive",
+    "preview",
+    "previous",
+    "privacy-policy",
+    "profile",
+    "related",
+    "replies",
+    "search",
+    "section",
+    "self",
+    "service",
+    "service-desc",
+    "service-doc",
+    "service-meta",
+    "sibling",
+    "sitemap",
+    "start",
+    "status",
+    "stylesheet",
+    "subsection",
+    "successor-version",
+    "sunset",
+    "tag",
+    "terms-of-service",
+    "timegate",
+    "timemap",
+    "type",
+    "ugc",
+    "up",
+    "version-history",
+    "via",
+    "webmention",
+    "working-copy",
+    "working-copy-of",
+];
+
+/// The link relation used by HAL for the CURIEs themselves.
+const CURIES_RELATION: &str = "curies";
+
+/// Registry of the custom link relations that the service uses, and the CURIEs that they are namespaced with.
+#[derive(Debug, Default)]
+pub struct Relations {
+    /// The CURIEs that are known, keyed by prefix, with the URI Template for the documentation of each relation.
+    curies: BTreeMap<String, String>,
+    /// The custom link relations that are known, with a description of each one.
+    relations: BTreeMap<String, String>,
+    /// The link relations that should also be sent as RFC 8288 `Link` headers.
+    link_headers: BTreeSet<String>,
+}
+
+impl Relations {
+    /// Register a new CURIE.
+    ///
+    /// # Parameters
+    /// - `prefix` - The prefix of the CURIE. For example, "nl".
+    /// - `href` - The URI Template for the documentation of relations with this prefix. Must contain `{rel}`.
+    pub fn with_curie<P, H>(mut self, prefix: P, href: H) -> Self
+    where
+        P: Into<String>,
+        H: Into<String>,
+    {
+        self.curies.insert(prefix.into(), href.into());
+
+        self
+    }
+
+    /// Register a new custom link relation.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation, including the CURIE prefix if it has one. For example, "nl:user".
+    /// - `description` - A description of what the link relation means.
+    pub fn with_relation<R, D>(mut self, rel: R, description: D) -> Self
+    where
+        R: Into<String>,
+        D: Into<String>,
+    {
+        let rel = rel.into();
+
+        if let Some(index) = rel.find(':') {
+            let prefix = &rel[..index];
+            if !self.curies.contains_key(prefix) {
+                tracing::warn!(rel = ?rel, prefix = ?prefix, "Registered link relation with an unknown CURIE prefix");
+            }
+        }
+
+        self.relations.insert(rel, description.into());
+
+        self
+    }
+
+    /// Register a link relation that should also be sent as a `Link` header whenever it appears in a HAL document.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation, including the CURIE prefix if it has one.
+    pub fn with_link_header<R>(mut self, rel: R) -> Self
+    where
+        R: Into<String>,
+    {
+        self.link_headers.insert(rel.into());
+
+        self
+    }
+
+    /// Get the description of a custom link relation.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation, including the CURIE prefix if it has one.
+    ///
+    /// # Returns
+    /// The description of the link relation, or `None` if it's not registered.
+    pub fn description(&self, rel: &str) -> Option<&str> {
+        self.relations.get(rel).map(String::as_str)
+    }
+
+    /// Determine whether a link relation is known, either by being registered or by being a standard IANA relation.
+    ///
+    /// Relations that are absolute URIs are also allowed, since these are self-documenting extension relations.
+    ///
+    /// # Parameters
+    /// - `rel` - The link relation to check
+    ///
+    /// # Returns
+    /// True if the link relation is known. False if not.
+    pub fn is_known(&self, rel: &str) -> bool {
+        rel == CURIES_RELATION
+            || IANA_RELATIONS.contains(&rel)
+            || self.relations.contains_key(rel)
+            || rel.starts_with("http://")
+            || rel.starts_with("https://")
+    }
+
+    /// Apply the registry to the provided HAL document.
+    ///
+    /// This adds the `curies` links to the document, and flags up any link relations used in the document, or any
+    /// embedded documents, that are not known.
+    ///
+    /// # Parameters
+    /// - `document` - The document to apply the registry to
+    pub fn apply(&self, document: &mut HalDocument) {
+        self.check(document);
+
+        if !self.curies.is_empty() && !document.links.contains_key(CURIES_RELATION) {
+            let curies = self
+                .curies
+                .iter()
+                .map(|(prefix, href)| Link::templated(href).with_name(prefix))
+                .collect();
+
+            document
+                .links
+                .insert(CURIES_RELATION.to_owned(), Links::Multiple(curies));
+        }
+    }
+
+    /// Build the value of the `Link` header to send for the provided HAL document.
+    ///
+    /// Only the relations registered with `with_link_header` are included. Templated links are never included, since
+    /// RFC 8288 has no way to represent them.
+    ///
+    /// # Parameters
+    /// - `document` - The document to build the header for
+    ///
+    /// # Returns
+    /// The value of the `Link` header, or `None` if there are no links to send.
+    pub fn link_header(&self, document: &HalDocument) -> Option<String> {
+        let mut values = vec![];
+
+        for (rel, links) in &document.links {
+            if !self.link_headers.contains(rel) {
+                continue;
+            }
+
+            let links = match links {
+                Links::Single(link) => std::slice::from_ref(link),
+                Links::Multiple(links) => links.as_slice(),
+            };
+
+            for link in links.iter().filter(|link| !link.templated) {
+                let mut params = vec![format!("<{}>", link.href), format!("rel={}", quote(rel))];
+                if let Some(title) = &link.title {
+                    params.push(format!("title={}", quote(title)));
+                }
+                if let Some(media_type) = &link.media_type {
+                    params.push(format!("type={}", quote(media_type)));
+                }
+
+                values.push(params.join("; "));
+            }
+        }
+
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.join(", "))
+        }
+    }
+
+    /// Check all of the link relations in the provided HAL document and flag up any that are not known.
+    ///
+    /// # Parameters
+    /// - `document` - The document to check
+    fn check(&self, document: &HalDocument) {
+        for rel in document.links.keys().chain(document.embedded.keys()) {
+            if !self.is_known(rel) {
+                tracing::warn!(rel = ?rel, "Unknown link relation used in HAL document");
+            }
+        }
+
+        for embedded in document.embedded.values() {
+            match embedded {
+                Embedded::Single(document) => self.check(document),
+                Embedded::Multiple(documents) => {
+                    for document in documents {
+                        self.check(document);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Produce an RFC 7230 quoted string for use as a parameter value in a `Link` header.
+///
+/// # Parameters
+/// - `value` - The value to quote
+///
+/// # Returns
+/// The quoted value
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    #[test]
+    fn known_relations() {
+        let relations = Relations::default()
+            .with_curie("nl", "/rels/{rel}")
+            .with_relation("nl:user", "A user");
+
+        check!(relations.is_known("self"));
+        check!(relations.is_known("curies"));
+        check!(relations.is_known("nl:user"));
+        check!(relations.is_known("https://example.com/rels/other"));
+
+        check!(!relations.is_known("nl:unknown"));
+        check!(!relations.is_known("slef"));
+    }
+
+    #[test]
+    fn description() {
+        let relations = Relations::default()
+            .with_curie("nl", "/rels/{rel}")
+            .with_relation("nl:user", "A user");
+
+        check!(relations.description("nl:user") == Some("A user"));
+        check!(relations.description("nl:unknown").is_none());
+    }
+
+    #[test]
+    fn apply_adds_curies() {
+        let relations = Relations::default()
+            .with_curie("nl", "/rels/{rel}")
+            .with_relation("nl:user", "A user");
+
+        let mut document = HalDocument::new(json!({"name": "Test"})).with_link("self", "/");
+        relations.apply(&mut document);
+
+        let_assert!(Some(Links::Multiple(curies)) = document.links.get("curies"));
+        check!(curies == &vec![Link::templated("/rels/{rel}").with_name("nl")]);
+
+        check!(
+            serde_json::to_value(document).unwrap()
+                == json!({
+                    "name": "Test",
+                    "_links": {
+                        "curies": [
+                            {"href": "/rels/{rel}", "templated": true, "name": "nl"}
+                        ],
+                        "self": {"href": "/"}
+                    }
+                })
+        );
+    }
+
+    #[test]
+    fn apply_without_curies() {
+        let relations = Relations::default();
+
+        let mut document = HalDocument::new(json!({"name": "Test"})).with_link("self", "/");
+        relations.apply(&mut document);
+
+        check!(!document.links.contains_key("curies"));
+        check!(document.data == json!({"name": "Test"}));
+    }
+
+    #[test]
+    fn link_header() {
+        let relations = Relations::default()
+            .with_link_header("self")
+            .with_link_header("item")
+            .with_link_header("nl:user");
+
+        let document = HalDocument::new(json!({}))
+            .with_link("self", "/")
+            .with_link("other", "/other")
+            .with_link("nl:user", Link::templated("/users/{id}"))
+            .with_links(
+                "item",
+                vec![
+                    Link::from("/items/1").with_title("Item \"one\""),
+                    Link::from("/items/2").with_type("application/hal+json"),
+                ],
+            );
+
+        check!(
+            relations.link_header(&document)
+                == Some(
+                    r#"</items/1>; rel="item"; title="Item \"one\"", </items/2>; rel="item"; type="application/hal+json", </>; rel="self""#
+                        .to_owned()
+                )
+        );
+    }
+
+    #[test]
+    fn no_link_header() {
+        let relations = Relations::default().with_link_header("next");
+
+        let document = HalDocument::new(json!({})).with_link("self", "/");
+
+        check!(relations.link_header(&document).is_none());
+    }
+}
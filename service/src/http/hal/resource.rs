@@ -0,0 +1,236 @@
+use super::{HalDocument, HalRespondable, Link, Template};
+use crate::{authorization::Authorization, model::Resource};
+use actix_http::http::{
+    header::{self, CacheControl, CacheDirective, ETag, EntityTag, HttpDate, LastModified},
+    HeaderValue, StatusCode,
+};
+use serde::Serialize;
+use std::time::SystemTime;
+
+/// Trait that the data of a resource can implement so that the resource can be returned as a HAL document.
+///
+/// The identity of the resource is handled generically, so implementations only need to provide the body of the
+/// document and any links other than `self`.
+///
+/// # Types
+/// - `I` - The type of the ID of the resource
+pub trait HalResourceData<I> {
+    /// The type that is serialized as the body of the HAL document.
+    type Model: Serialize;
+
+    /// Generate any links for the resource, other than the `self` link.
+    ///
+    /// # Parameters
+    /// - `id` - The ID of the resource
+    ///
+    /// # Returns
+    /// The link relations and links to include in the document.
+    fn links(&self, _id: &I) -> Vec<(&'static str, Link)> {
+        vec![]
+    }
+
+    /// Generate the HAL-FORMS templates for the actions that the caller can perform on the resource.
+    ///
+    /// # Parameters
+    /// - `id` - The ID of the resource
+    /// - `authorization` - The authorization of the caller
+    ///
+    /// # Returns
+    /// The names of the templates and the templates to include in the document.
+    fn templates(&self, _id: &I, _authorization: &Authorization) -> Vec<(&'static str, Template)> {
+        vec![]
+    }
+
+    /// Generate the cache directives to send for the resource.
+    ///
+    /// # Returns
+    /// The directives for the `Cache-Control` header.
+    fn cache_directives(&self) -> Vec<CacheDirective> {
+        vec![CacheDirective::Public, CacheDirective::MaxAge(3600)]
+    }
+
+    /// The names of any fields of the model that are sometimes omitted from the HAL document.
+    ///
+    /// # Returns
+    /// The field names, as serialized.
+    fn optional_fields(&self) -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// The URL of the schema describing the model, if there is one, which is linked to as the `profile` of the HAL
+    /// document.
+    ///
+    /// # Returns
+    /// The URL of the schema.
+    fn profile(&self) -> Option<String> {
+        None
+    }
+
+    /// Convert the resource data into the model for the body of the HAL document.
+    ///
+    /// # Returns
+    /// The model to serialize.
+    fn into_model(self) -> Self::Model;
+}
+
+impl HalRespondable {
+    /// Build the response for a resource, as seen by the provided caller.
+    ///
+    /// # Parameters
+    /// - `resource` - The resource to respond with
+    /// - `authorization` - The authorization of the caller, used to decide which templates to include
+    pub fn from_resource<I, D>(resource: Resource<I, D>, authorization: &Authorization) -> Self
+    where
+        I: Into<Link>,
+        D: HalResourceData<I>,
+    {
+        let identity = resource.identity;
+        let links = resource.data.links(&identity.id);
+        let templates = resource.data.templates(&identity.id, authorization);
+        let cache_directives = resource.data.cache_directives();
+        let optional_fields = resource.data.optional_fields();
+        let profile = resource.data.profile();
+
+        let mut hal_document =
+            HalDocument::new(resource.data.into_model()).with_link("self", identity.id);
+        for (rel, link) in links {
+            hal_document = hal_document.with_link(rel, link);
+        }
+        for (name, template) in templates {
+            hal_document = hal_document.with_template(name, template);
+        }
+        for name in optional_fields {
+            hal_document = hal_document.with_optional_field(name);
+        }
+        if let Some(profile) = profile {
+            hal_document = hal_document.with_link("profile", profile);
+        }
+
+        let mut respondable = HalRespondable::from(hal_document)
+            .with_status_code(StatusCode::OK)
+            .with_header(CacheControl(cache_directives))
+            .with_header(ETag(EntityTag::strong(identity.version)))
+            .with_header(LastModified(HttpDate::from(SystemTime::from(
+                identity.updated,
+            ))));
+
+        // The templates depend on who is asking, so caches must not share the response between callers.
+        respondable
+            .0
+            .headers
+            .append(header::VARY, HeaderValue::from_static("Authorization"));
+
+        respondable
+    }
+}
+
+impl<I, D> From<Resource<I, D>> for HalRespondable
+where
+    I: Into<Link>,
+    D: HalResourceData<I>,
+{
+    fn from(resource: Resource<I, D>) -> Self {
+        Self::from_resource(resource, &Authorization::Unauthorized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::{Principal, SecurityContext};
+    use crate::http::{hal::Links, Respondable};
+    use crate::model::Identity;
+    use actix_http::http::Method;
+    use assert2::{check, let_assert};
+    use chrono::Utc;
+    use serde_json::json;
+
+    #[derive(Debug)]
+    struct TestData {
+        name: String,
+    }
+
+    impl HalResourceData<String> for TestData {
+        type Model = serde_json::Value;
+
+        fn links(&self, id: &String) -> Vec<(&'static str, Link)> {
+            vec![("related", format!("{}/related", id).into())]
+        }
+
+        fn templates(
+            &self,
+            _id: &String,
+            authorization: &Authorization,
+        ) -> Vec<(&'static str, Template)> {
+            match authorization {
+                Authorization::Authorized(_) => vec![("edit", Template::new(&Method::PATCH))],
+                Authorization::Unauthorized => vec![],
+            }
+        }
+
+        fn profile(&self) -> Option<String> {
+            Some("/schemas/thing".to_owned())
+        }
+
+        fn into_model(self) -> Self::Model {
+            json!({ "name": self.name })
+        }
+    }
+
+    fn resource() -> Resource<String, TestData> {
+        Resource {
+            identity: Identity {
+                id: "/things/123".to_owned(),
+                version: "abc".to_owned(),
+                created: "2021-02-01T09:00:00Z".parse().unwrap(),
+                updated: "2021-02-07T12:00:00Z".parse().unwrap(),
+            },
+            data: TestData {
+                name: "Test".to_owned(),
+            },
+        }
+    }
+
+    #[test]
+    fn resource_to_respondable() {
+        let respondable = HalRespondable::from(resource());
+
+        check!(respondable.status_code() == StatusCode::OK);
+
+        let headers = respondable.headers();
+        check!(headers.get(header::ETAG).unwrap() == r#""abc""#);
+        check!(headers.get(header::LAST_MODIFIED).unwrap() == "Sun, 07 Feb 2021 12:00:00 GMT");
+        check!(headers.get(header::CACHE_CONTROL).unwrap() == "public, max-age=3600");
+        check!(headers.get(header::VARY).unwrap() == "Authorization");
+        check!(respondable.profile() == Some("/schemas/thing".to_owned()));
+
+        let document = respondable.body();
+        check!(document.data == json!({"name": "Test"}));
+
+        let_assert!(Some(Links::Single(link)) = document.links.get("self"));
+        check!(link.href == "/things/123");
+
+        let_assert!(Some(Links::Single(link)) = document.links.get("related"));
+        check!(link.href == "/things/123/related");
+
+        let_assert!(Some(Links::Single(link)) = document.links.get("profile"));
+        check!(link.href == "/schemas/thing");
+
+        check!(document.templates.is_empty());
+    }
+
+    #[test]
+    fn resource_with_templates() {
+        let authorization = Authorization::Authorized(SecurityContext {
+            principal: Principal::User("123".to_owned()),
+            issued: Utc::now(),
+            expires: Utc::now(),
+        });
+
+        let document = HalRespondable::from_resource(resource(), &authorization).body();
+
+        check!(document.templates.len() == 1);
+        let_assert!(Some(template) = document.templates.get("edit"));
+        check!(template.method == "PATCH");
+    }
+}
@@ -1,5 +1,115 @@
-use super::HalDocument;
-use crate::http::SimpleRespondable;
+use super::{HalDocument, Links, Projection, Relations};
+use crate::http::{
+    negotiation::{
+        Format, MediaType, APPLICATION_CBOR, APPLICATION_JSON, APPLICATION_MSGPACK,
+        APPLICATION_X_MSGPACK,
+    },
+    problem::Problem,
+    Respondable, SimpleRespondable,
+};
+use actix_http::http::{
+    header::{self, Header, HeaderValue},
+    HeaderMap, StatusCode,
+};
+use actix_web::{web::Data, HttpRequest};
+use std::sync::Arc;
+
+/// Media type for HAL documents rendered as JSON.
+pub const APPLICATION_HAL_JSON: MediaType = MediaType {
+    name: "application/hal+json",
+    format: Format::Json,
+};
+
+/// Media type for HAL-FORMS documents, which are HAL documents that may also include `_templates`.
+pub const APPLICATION_HAL_FORMS_JSON: MediaType = MediaType {
+    name: "application/prs.hal-forms+json",
+    format: Format::Json,
+};
+
+/// The media types that HAL documents can be produced as, in order of preference.
+const HAL_MEDIA_TYPES: &[MediaType] = &[
+    APPLICATION_HAL_JSON,
+    APPLICATION_HAL_FORMS_JSON,
+    APPLICATION_JSON,
+    APPLICATION_CBOR,
+    APPLICATION_MSGPACK,
+    APPLICATION_X_MSGPACK,
+];
 
 /// Respondable instance for HAL documents.
-pub type HalRespondable = SimpleRespondable<HalDocument>;
+pub struct HalRespondable(pub(super) SimpleRespondable<HalDocument>);
+
+impl HalRespondable {
+    /// Specify the status code to use.
+    ///
+    /// # Parameters
+    /// - `status_code` - The status code to use
+    pub fn with_status_code(self, status_code: StatusCode) -> Self {
+        Self(self.0.with_status_code(status_code))
+    }
+
+    /// Specify a header to include in the response.
+    ///
+    /// # Parameters
+    /// - `header` - The header to add to the response.
+    pub fn with_header<H>(self, header: H) -> Self
+    where
+        H: Header,
+    {
+        Self(self.0.with_header(header))
+    }
+}
+
+impl From<HalDocument> for HalRespondable {
+    fn from(body: HalDocument) -> Self {
+        Self(SimpleRespondable::new(body))
+    }
+}
+
+impl Respondable for HalRespondable {
+    type Body = HalDocument;
+
+    fn status_code(&self) -> StatusCode {
+        self.0.status_code()
+    }
+
+    fn headers(&self) -> HeaderMap {
+        self.0.headers()
+    }
+
+    fn media_types(&self) -> &'static [MediaType] {
+        HAL_MEDIA_TYPES
+    }
+
+    fn profile(&self) -> Option<String> {
+        match self.0.body.links.get("profile") {
+            Some(Links::Single(link)) => Some(link.href.clone()),
+            _ => None,
+        }
+    }
+
+    fn prepare(&mut self, req: &HttpRequest) -> Result<(), Problem> {
+        Projection::parse(req.query_string())?.apply(&mut self.0.body)?;
+
+        if let Some(relations) = req.app_data::<Data<Arc<Relations>>>() {
+            relations.apply(&mut self.0.body);
+
+            if let Some(link_header) = relations.link_header(&self.0.body) {
+                match HeaderValue::from_str(&link_header) {
+                    Ok(value) => {
+                        self.0.headers.append(header::LINK, value);
+                    }
+                    Err(e) => {
+                        tracing::warn!(e = ?e, link_header = ?link_header, "Failed to build Link header");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn body(self) -> Self::Body {
+        self.0.body()
+    }
+}
@@ -0,0 +1,372 @@
+use actix_http::http::Method;
+use serde::Serialize;
+
+/// Representation of a single HAL-FORMS template, describing an action that can be performed on a resource.
+/// See <https://rwcbook.github.io/hal-forms/>.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Template {
+    /// A human-readable title for the action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The HTTP Method to use to perform the action.
+    pub method: String,
+    /// The media type of the request body to send.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// The URL to send the request to, if not the `self` link of the resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// The properties that make up the request body.
+    pub properties: Vec<Property>,
+}
+
+/// The types of value that a HAL-FORMS property can have, using the names of the HTML5 input types.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+#[allow(dead_code)]
+pub enum PropertyType {
+    Hidden,
+    Text,
+    Textarea,
+    Search,
+    Tel,
+    Url,
+    Email,
+    Password,
+    Date,
+    Month,
+    Week,
+    Time,
+    DatetimeLocal,
+    Number,
+    Range,
+    Color,
+}
+
+/// Representation of a single property in a HAL-FORMS template.
+#[derive(Debug, Serialize, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Property {
+    /// The name of the property.
+    pub name: String,
+    /// A human-readable prompt for the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// The type of value that the property has.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<PropertyType>,
+    /// Whether a value for the property must be provided.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub required: bool,
+    /// Whether the property can't be changed.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+    /// A regular expression that the value must match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    /// The minimum numeric value of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<i64>,
+    /// The maximum numeric value of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<i64>,
+    /// The minimum length of the value of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    /// The maximum length of the value of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    /// The current value of the property.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The set of values that the property is allowed to take.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<PropertyOptions>,
+}
+
+/// The set of values that a HAL-FORMS property is allowed to take.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyOptions {
+    /// The allowed values.
+    pub inline: Vec<String>,
+}
+
+impl Template {
+    /// Create a new template for an action performed using the given HTTP Method.
+    ///
+    /// # Parameters
+    /// - `method` - The HTTP Method to use to perform the action
+    pub fn new(method: &Method) -> Self {
+        Self {
+            title: None,
+            method: method.as_str().to_owned(),
+            content_type: None,
+            target: None,
+            properties: vec![],
+        }
+    }
+
+    /// Specify the title of the template.
+    ///
+    /// # Parameters
+    /// - `title` - The title
+    pub fn with_title<S>(self, title: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    /// Specify the media type of the request body.
+    ///
+    /// # Parameters
+    /// - `content_type` - The media type
+    pub fn with_content_type<S>(self, content_type: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            content_type: Some(content_type.into()),
+            ..self
+        }
+    }
+
+    /// Specify the URL to send the request to.
+    ///
+    /// # Parameters
+    /// - `target` - The URL
+    #[allow(dead_code)]
+    pub fn with_target<S>(self, target: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            target: Some(target.into()),
+            ..self
+        }
+    }
+
+    /// Add a property to the template.
+    ///
+    /// # Parameters
+    /// - `property` - The property
+    pub fn with_property(mut self, property: Property) -> Self {
+        self.properties.push(property);
+
+        self
+    }
+}
+
+impl Property {
+    /// Create a new property with the given name.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the property
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Specify the prompt for the property.
+    ///
+    /// # Parameters
+    /// - `prompt` - The prompt
+    pub fn with_prompt<S>(self, prompt: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            prompt: Some(prompt.into()),
+            ..self
+        }
+    }
+
+    /// Specify the type of value that the property has.
+    ///
+    /// # Parameters
+    /// - `input_type` - The type
+    pub fn with_type(self, input_type: PropertyType) -> Self {
+        Self {
+            input_type: Some(input_type),
+            ..self
+        }
+    }
+
+    /// Mark the property as required.
+    pub fn required(self) -> Self {
+        Self {
+            required: true,
+            ..self
+        }
+    }
+
+    /// Mark the property as read-only.
+    #[allow(dead_code)]
+    pub fn read_only(self) -> Self {
+        Self {
+            read_only: true,
+            ..self
+        }
+    }
+
+    /// Specify a regular expression that the value must match.
+    ///
+    /// # Parameters
+    /// - `regex` - The regular expression
+    #[allow(dead_code)]
+    pub fn with_regex<S>(self, regex: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            regex: Some(regex.into()),
+            ..self
+        }
+    }
+
+    /// Specify the range of numeric values that the property can take.
+    ///
+    /// # Parameters
+    /// - `min` - The minimum value, if there is one
+    /// - `max` - The maximum value, if there is one
+    #[allow(dead_code)]
+    pub fn with_range(self, min: Option<i64>, max: Option<i64>) -> Self {
+        Self { min, max, ..self }
+    }
+
+    /// Specify the range of lengths that the value of the property can have.
+    ///
+    /// # Parameters
+    /// - `min_length` - The minimum length, if there is one
+    /// - `max_length` - The maximum length, if there is one
+    pub fn with_length(self, min_length: Option<u64>, max_length: Option<u64>) -> Self {
+        Self {
+            min_length,
+            max_length,
+            ..self
+        }
+    }
+
+    /// Specify the current value of the property.
+    ///
+    /// # Parameters
+    /// - `value` - The value
+    pub fn with_value<S>(self, value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            value: Some(value.into()),
+            ..self
+        }
+    }
+
+    /// Specify the set of values that the property is allowed to take.
+    ///
+    /// # Parameters
+    /// - `options` - The allowed values
+    #[allow(dead_code)]
+    pub fn with_options<I, S>(self, options: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            options: Some(PropertyOptions {
+                inline: options.into_iter().map(Into::into).collect(),
+            }),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use serde_json::json;
+
+    #[test]
+    fn serialize_minimal_template() {
+        let template = Template::new(&Method::DELETE);
+
+        check!(
+            serde_json::to_value(template).unwrap()
+                == json!({
+                    "method": "DELETE",
+                    "properties": []
+                })
+        );
+    }
+
+    #[test]
+    fn serialize_full_template() {
+        let template = Template::new(&Method::PATCH)
+            .with_title("Edit")
+            .with_content_type("application/merge-patch+json")
+            .with_target("/things/1")
+            .with_property(
+                Property::new("name")
+                    .with_prompt("Name")
+                    .with_type(PropertyType::Text)
+                    .required()
+                    .with_regex("^[a-z]+$")
+                    .with_length(Some(1), Some(10))
+                    .with_value("abc"),
+            )
+            .with_property(
+                Property::new("age")
+                    .with_type(PropertyType::Number)
+                    .with_range(Some(0), None),
+            )
+            .with_property(
+                Property::new("colour")
+                    .read_only()
+                    .with_options(vec!["red", "green"]),
+            );
+
+        check!(
+            serde_json::to_value(template).unwrap()
+                == json!({
+                    "title": "Edit",
+                    "method": "PATCH",
+                    "contentType": "application/merge-patch+json",
+                    "target": "/things/1",
+                    "properties": [
+                        {
+                            "name": "name",
+                            "prompt": "Name",
+                            "type": "text",
+                            "required": true,
+                            "regex": "^[a-z]+$",
+                            "minLength": 1,
+                            "maxLength": 10,
+                            "value": "abc"
+                        },
+                        {
+                            "name": "age",
+                            "type": "number",
+                            "min": 0
+                        },
+                        {
+                            "name": "colour",
+                            "readOnly": true,
+                            "options": {
+                                "inline": ["red", "green"]
+                            }
+                        }
+                    ]
+                })
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use actix_http::http::header;
+use actix_web::HttpRequest;
+use serde_json::Value;
+use std::{cmp::Ordering, collections::HashMap};
+
+/// The language to use when the client doesn't accept any that are available.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+lazy_static::lazy_static! {
+    /// The message catalogs for every available language, with the default language first.
+    static ref CATALOGS: Vec<Catalog> = vec![
+        Catalog::parse(DEFAULT_LANGUAGE, include_str!("../../messages/en.json")),
+        Catalog::parse("fr", include_str!("../../messages/fr.json")),
+    ];
+}
+
+/// The catalog of messages for a single language.
+struct Catalog {
+    /// The language tag of the language.
+    language: &'static str,
+    /// The messages, keyed by the dotted path to them in the catalog file.
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Parse a catalog of messages from a JSON document of nested objects, as used by i18next.
+    ///
+    /// # Parameters
+    /// - `language` - The language tag of the language
+    /// - `source` - The JSON document
+    ///
+    /// # Returns
+    /// The catalog
+    fn parse(language: &'static str, source: &str) -> Self {
+        let document: Value = serde_json::from_str(source).expect("Invalid message catalog");
+
+        let mut messages = HashMap::new();
+        flatten("", &document, &mut messages);
+
+        Self { language, messages }
+    }
+}
+
+/// Flatten a JSON document of nested objects into a map keyed by the dotted path to each string.
+///
+/// # Parameters
+/// - `prefix` - The dotted path to the current value
+/// - `value` - The current value
+/// - `messages` - The map to add the strings to
+fn flatten(prefix: &str, value: &Value, messages: &mut HashMap<String, String>) {
+    match value {
+        Value::String(message) => {
+            messages.insert(prefix.to_owned(), message.clone());
+        }
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                let key = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", prefix, name)
+                };
+                flatten(&key, value, messages);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A message that can be rendered in any of the available languages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    /// The key of the message in the catalogs.
+    pub key: &'static str,
+    /// The values to interpolate into the message, by name.
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl Message {
+    /// Create a new message.
+    ///
+    /// # Parameters
+    /// - `key` - The key of the message in the catalogs
+    pub fn new(key: &'static str) -> Self {
+        Self { key, args: vec![] }
+    }
+
+    /// Specify a value to interpolate into the message, in place of `{{name}}`.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the value
+    /// - `value` - The value
+    pub fn with_arg<V>(mut self, name: &'static str, value: &V) -> Self
+    where
+        V: ToString + ?Sized,
+    {
+        self.args.push((name, value.to_string()));
+
+        self
+    }
+
+    /// Render the message in the requested language, falling back to the default language if it isn't available.
+    ///
+    /// # Parameters
+    /// - `language` - The language to render the message in
+    ///
+    /// # Returns
+    /// The language that was actually used and the rendered message, or `None` if the message isn't known.
+    pub fn render(&self, language: &str) -> Option<(&'static str, String)> {
+        translate(language, self.key).map(|(language, message)| {
+            let rendered = self
+                .args
+                .iter()
+                .fold(message.to_owned(), |message, (name, value)| {
+                    message.replace(&format!("{{{{{}}}}}", name), value)
+                });
+
+            (language, rendered)
+        })
+    }
+}
+
+impl From<&'static str> for Message {
+    fn from(key: &'static str) -> Self {
+        Self::new(key)
+    }
+}
+
+/// Look up a message in the catalog for the requested language, falling back to the default language if it isn't
+/// available.
+///
+/// # Parameters
+/// - `language` - The language to look up the message in
+/// - `key` - The key of the message
+///
+/// # Returns
+/// The language that was actually used and the message, or `None` if the message isn't known in either language.
+pub fn translate(language: &str, key: &str) -> Option<(&'static str, &'static str)> {
+    let catalogs: &'static [Catalog] = &CATALOGS;
+
+    catalogs
+        .iter()
+        .filter(|catalog| catalog.language == language)
+        .chain(catalogs.first())
+        .find_map(|catalog| {
+            catalog
+                .messages
+                .get(key)
+                .map(|message| (catalog.language, message.as_str()))
+        })
+}
+
+/// Negotiate which of the available languages is the best match for the `Accept-Language` header of the request.
+///
+/// Language ranges are matched against the primary language of each catalog, so `en-GB` will match `en`. If the
+/// request has no `Accept-Language` header, or none of the languages in it are available, then the default language
+/// is used.
+///
+/// # Parameters
+/// - `req` - The HTTP Request to negotiate for
+///
+/// # Returns
+/// The language tag of the best language.
+pub fn negotiate_language(req: &HttpRequest) -> &'static str {
+    let accept_language = match req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(value) => value,
+        None => return DEFAULT_LANGUAGE,
+    };
+
+    let mut ranges: Vec<(String, f32)> = accept_language
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim().to_ascii_lowercase();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if tag.is_empty() || quality <= 0.0 {
+                None
+            } else {
+                Some((tag, quality))
+            }
+        })
+        .collect();
+
+    // Stable sort, so that ranges with equal quality stay in the order the client listed them.
+    ranges.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    ranges
+        .iter()
+        .find_map(|(tag, _)| {
+            if tag == "*" {
+                return Some(DEFAULT_LANGUAGE);
+            }
+
+            CATALOGS
+                .iter()
+                .find(|catalog| {
+                    let primary = tag.split('-').next().unwrap_or_default();
+                    primary == catalog.language
+                })
+                .map(|catalog| catalog.language)
+        })
+        .unwrap_or(DEFAULT_LANGUAGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use assert2::check;
+    use test_case::test_case;
+
+    #[test_case(None, "en" ; "no header")]
+    #[test_case(Some("fr"), "fr" ; "exact match")]
+    #[test_case(Some("fr-CA"), "fr" ; "primary language match")]
+    #[test_case(Some("FR"), "fr" ; "case insensitive")]
+    #[test_case(Some("de, fr;q=0.5"), "fr" ; "first available")]
+    #[test_case(Some("fr;q=0.5, en;q=0.8"), "en" ; "highest quality")]
+    #[test_case(Some("en, fr"), "en" ; "equal quality keeps order")]
+    #[test_case(Some("fr;q=0"), "en" ; "explicitly not acceptable")]
+    #[test_case(Some("de"), "en" ; "nothing available")]
+    #[test_case(Some("*"), "en" ; "anything")]
+    #[test_case(Some(";;,"), "en" ; "unparsable")]
+    fn negotiate(accept_language: Option<&str>, expected: &str) {
+        let mut req = TestRequest::get();
+        if let Some(accept_language) = accept_language {
+            req = req.header(header::ACCEPT_LANGUAGE, accept_language);
+        }
+
+        check!(negotiate_language(&req.to_http_request()) == expected);
+    }
+
+    #[test_case("en", "status.404", Some(("en", "The requested resource was not found")) ; "english")]
+    #[test_case("fr", "status.404", Some(("fr", "La ressource demandée est introuvable")) ; "french")]
+    #[test_case("de", "status.404", Some(("en", "The requested resource was not found")) ; "unknown language")]
+    #[test_case("en", "status.999", None ; "unknown message")]
+    fn translate_message(language: &str, key: &str, expected: Option<(&str, &str)>) {
+        check!(translate(language, key) == expected);
+    }
+
+    #[test]
+    fn render_with_args() {
+        let message = Message::new("details.paging.pageSizeRange").with_arg("max", &100);
+
+        check!(
+            message.render("fr")
+                == Some((
+                    "fr",
+                    "La taille de page doit être comprise entre 1 et 100".to_owned()
+                ))
+        );
+    }
+
+    #[test]
+    fn catalogs_have_the_same_messages() {
+        let default = &CATALOGS[0];
+
+        for catalog in CATALOGS.iter() {
+            let mut missing: Vec<&String> = default
+                .messages
+                .keys()
+                .filter(|key| !catalog.messages.contains_key(*key))
+                .collect();
+            missing.sort();
+
+            check!((catalog.language, missing) == (catalog.language, vec![]));
+        }
+    }
+}
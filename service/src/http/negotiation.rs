@@ -0,0 +1,225 @@
+use actix_http::http::header::{self, Accept, Header};
+use actix_web::HttpRequest;
+use serde::Serialize;
+
+/// The formats that a response body can be serialized as.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Serialize the body as JSON.
+    Json,
+    /// Serialize the body as CBOR.
+    Cbor,
+    /// Serialize the body as msgpack.
+    MessagePack,
+}
+
+/// Errors that can occur when serializing a response body.
+#[derive(Debug, thiserror::Error)]
+pub enum SerializeError {
+    #[error("Failed to serialize as JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Failed to serialize as CBOR: {0}")]
+    Cbor(#[from] serde_cbor::Error),
+
+    #[error("Failed to serialize as msgpack: {0}")]
+    MessagePack(#[from] rmp_serde::encode::Error),
+}
+
+impl Format {
+    /// Serialize the provided body in this format.
+    ///
+    /// The binary formats go via a JSON value first, so that `#[serde(flatten)]` fields - which are serialized as maps
+    /// of unknown length - are supported regardless of the format.
+    ///
+    /// # Parameters
+    /// - `body` - The body to serialize
+    ///
+    /// # Returns
+    /// The serialized bytes
+    pub fn serialize<T>(self, body: &T) -> Result<Vec<u8>, SerializeError>
+    where
+        T: Serialize,
+    {
+        match self {
+            Format::Json => Ok(serde_json::to_vec(body)?),
+            Format::Cbor => {
+                let value = serde_json::to_value(body)?;
+                Ok(serde_cbor::to_vec(&value)?)
+            }
+            Format::MessagePack => {
+                let value = serde_json::to_value(body)?;
+                Ok(rmp_serde::to_vec_named(&value)?)
+            }
+        }
+    }
+}
+
+/// A media type that a response body can be produced as, and the format to use to produce it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaType {
+    /// The name of the media type, as used in the `Accept` and `Content-Type` headers.
+    pub name: &'static str,
+    /// The format to serialize the body as.
+    pub format: Format,
+}
+
+/// Media type for plain JSON responses.
+pub const APPLICATION_JSON: MediaType = MediaType {
+    name: "application/json",
+    format: Format::Json,
+};
+
+/// Media type for CBOR responses.
+pub const APPLICATION_CBOR: MediaType = MediaType {
+    name: "application/cbor",
+    format: Format::Cbor,
+};
+
+/// Media type for msgpack responses.
+pub const APPLICATION_MSGPACK: MediaType = MediaType {
+    name: "application/msgpack",
+    format: Format::MessagePack,
+};
+
+/// Alternative, unregistered, media type for msgpack responses that some clients use.
+pub const APPLICATION_X_MSGPACK: MediaType = MediaType {
+    name: "application/x-msgpack",
+    format: Format::MessagePack,
+};
+
+/// The media types that any response body can be produced as, in order of preference.
+pub const DEFAULT_MEDIA_TYPES: &[MediaType] = &[
+    APPLICATION_JSON,
+    APPLICATION_CBOR,
+    APPLICATION_MSGPACK,
+    APPLICATION_X_MSGPACK,
+];
+
+/// Negotiate which of the available media types is the best match for the `Accept` header of the request.
+///
+/// If the request has no `Accept` header, or one that can't be parsed, then the first available media type is used.
+/// Otherwise the media type with the highest quality value is used, with ties broken by the order of the available
+/// media types.
+///
+/// # Parameters
+/// - `req` - The HTTP Request to negotiate for
+/// - `available` - The names of the media types that are available, in order of preference
+///
+/// # Returns
+/// The index into `available` of the best media type, or `None` if none of them are acceptable.
+pub fn negotiate(req: &HttpRequest, available: &[&str]) -> Option<usize> {
+    if available.is_empty() {
+        return None;
+    }
+
+    if !req.headers().contains_key(header::ACCEPT) {
+        return Some(0);
+    }
+
+    let accept = match Accept::parse(req) {
+        Ok(accept) => accept,
+        Err(e) => {
+            tracing::debug!(e = ?e, "Failed to parse Accept header. Treating as */*");
+            return Some(0);
+        }
+    };
+
+    // Individual media ranges that can't be parsed are dropped, so if nothing was left then treat it the same as an
+    // unparsable header.
+    if accept.is_empty() {
+        tracing::debug!("No valid media ranges in Accept header. Treating as */*");
+        return Some(0);
+    }
+
+    let mut best = None;
+    let mut best_quality = header::q(0_u16);
+
+    for (index, media_type) in available.iter().enumerate() {
+        let mut parts = media_type.splitn(2, '/');
+        let type_ = parts.next().unwrap_or_default();
+
+        // The quality of the most specific range in the Accept header that matches this media type.
+        let quality = accept
+            .iter()
+            .filter_map(|range| {
+                let item = &range.item;
+                if item.essence_str().eq_ignore_ascii_case(media_type) {
+                    Some((2, range.quality))
+                } else if item.type_() == type_ && item.subtype() == "*" {
+                    Some((1, range.quality))
+                } else if item.type_() == "*" && item.subtype() == "*" {
+                    Some((0, range.quality))
+                } else {
+                    None
+                }
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, quality)| quality);
+
+        if let Some(quality) = quality {
+            if quality > best_quality {
+                best = Some(index);
+                best_quality = quality;
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use assert2::{check, let_assert};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    const AVAILABLE: &[&str] = &[
+        "application/hal+json",
+        "application/json",
+        "application/cbor",
+    ];
+
+    #[test]
+    fn no_accept_header() {
+        let req = TestRequest::get().to_http_request();
+
+        check!(negotiate(&req, AVAILABLE) == Some(0));
+    }
+
+    #[test_case("*/*", Some(0) ; "anything")]
+    #[test_case("application/*", Some(0) ; "any application type")]
+    #[test_case("application/json", Some(1) ; "exact match")]
+    #[test_case("application/cbor, application/json", Some(1) ; "multiple exact matches")]
+    #[test_case("application/cbor, application/json;q=0.5", Some(2) ; "quality values")]
+    #[test_case("application/json;q=0.5, */*;q=0.1", Some(1) ; "specific beats wildcard")]
+    #[test_case("application/hal+json;q=0, application/*", Some(1) ; "explicitly not acceptable")]
+    #[test_case("text/html", None ; "nothing acceptable")]
+    #[test_case("text/html, */*;q=0.1", Some(0) ; "fallback wildcard")]
+    #[test_case("this is not valid", Some(0) ; "unparsable header")]
+    fn accept_header(accept: &str, expected: Option<usize>) {
+        let req = TestRequest::get()
+            .header(header::ACCEPT, accept)
+            .to_http_request();
+
+        check!(negotiate(&req, AVAILABLE) == expected);
+    }
+
+    #[test_case(Format::Json ; "JSON")]
+    #[test_case(Format::Cbor ; "CBOR")]
+    #[test_case(Format::MessagePack ; "MessagePack")]
+    fn serialize_round_trip(format: Format) {
+        let body = json!({"name": "Graham", "age": 42, "tags": ["a", "b"]});
+
+        let_assert!(Ok(serialized) = format.serialize(&body));
+
+        let deserialized: Value = match format {
+            Format::Json => serde_json::from_slice(&serialized).unwrap(),
+            Format::Cbor => serde_cbor::from_slice(&serialized).unwrap(),
+            Format::MessagePack => rmp_serde::from_read(serialized.as_slice()).unwrap(),
+        };
+        check!(deserialized == body);
+    }
+}
@@ -0,0 +1,5 @@
+mod document;
+mod operation;
+
+pub use document::*;
+pub use operation::*;
@@ -0,0 +1,188 @@
+use super::{Operation, BEARER_AUTH, HAL_LINKS_SCHEMA, PROBLEM_SCHEMA};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// The version of the `OpenAPI` Specification that the documents conform to.
+const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Representation of an `OpenAPI` document describing the entire API.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenApi {
+    /// The version of the `OpenAPI` Specification.
+    pub openapi: &'static str,
+    /// Metadata about the API.
+    pub info: Info,
+    /// The operations of the API, keyed by path and then by lower-case HTTP Method.
+    pub paths: BTreeMap<String, BTreeMap<String, Operation>>,
+    /// The shared parts of the document that operations can refer to.
+    pub components: Components,
+}
+
+/// Metadata about the API.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Info {
+    /// The title of the API.
+    pub title: String,
+    /// The version of the API.
+    pub version: String,
+}
+
+/// The shared parts of the document that operations can refer to.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Components {
+    /// The shared schemas, keyed by name.
+    pub schemas: BTreeMap<String, Value>,
+    /// The security schemes, keyed by name.
+    pub security_schemes: BTreeMap<String, Value>,
+}
+
+impl OpenApi {
+    /// Create a new `OpenAPI` document with no operations, but with the standard shared schemas and security schemes.
+    ///
+    /// # Parameters
+    /// - `title` - The title of the API
+    /// - `version` - The version of the API
+    pub fn new<T, V>(title: T, version: V) -> Self
+    where
+        T: Into<String>,
+        V: Into<String>,
+    {
+        let mut schemas = BTreeMap::new();
+        schemas.insert(
+            PROBLEM_SCHEMA.to_owned(),
+            json!({
+                "type": "object",
+                "description": "An RFC 7807 Problem",
+                "required": ["type", "title", "status"],
+                "properties": {
+                    "type": {"type": "string", "format": "uri-reference"},
+                    "title": {"type": "string"},
+                    "status": {"type": "integer"},
+                    "detail": {"type": "string"},
+                    "instance": {"type": "string", "format": "uri-reference"},
+                    "requestId": {"type": "string"},
+                    "traceId": {"type": "string"}
+                },
+                "additionalProperties": true
+            }),
+        );
+        schemas.insert(
+            HAL_LINKS_SCHEMA.to_owned(),
+            json!({
+                "type": "object",
+                "description": "The links of a HAL document, keyed by link relation",
+                "additionalProperties": {
+                    "oneOf": [
+                        {"$ref": "#/components/schemas/HalLink"},
+                        {"type": "array", "items": {"$ref": "#/components/schemas/HalLink"}}
+                    ]
+                }
+            }),
+        );
+        schemas.insert(
+            "HalLink".to_owned(),
+            json!({
+                "type": "object",
+                "required": ["href"],
+                "properties": {
+                    "href": {"type": "string"},
+                    "templated": {"type": "boolean"},
+                    "type": {"type": "string"},
+                    "name": {"type": "string"},
+                    "title": {"type": "string"}
+                }
+            }),
+        );
+
+        let mut security_schemes = BTreeMap::new();
+        security_schemes.insert(
+            BEARER_AUTH.to_owned(),
+            json!({
+                "type": "http",
+                "scheme": "bearer",
+                "bearerFormat": "JWT",
+                "description": "An access token issued by Auth0"
+            }),
+        );
+
+        Self {
+            openapi: OPENAPI_VERSION,
+            info: Info {
+                title: title.into(),
+                version: version.into(),
+            },
+            paths: BTreeMap::new(),
+            components: Components {
+                schemas,
+                security_schemes,
+            },
+        }
+    }
+
+    /// Add a set of operations to the document. Any operation with the same path and HTTP Method as one that is
+    /// already present replaces it.
+    ///
+    /// # Parameters
+    /// - `operations` - The operations to add
+    pub fn with_operations<I>(mut self, operations: I) -> Self
+    where
+        I: IntoIterator<Item = Operation>,
+    {
+        for operation in operations {
+            let method = operation.method.as_str().to_ascii_lowercase();
+            let path = self.paths.entry(operation.path.clone()).or_default();
+
+            if path.contains_key(&method) {
+                tracing::warn!(method = ?method, path = ?operation.path, "Replacing duplicate operation");
+            }
+            path.insert(method, operation);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_http::http::Method;
+    use assert2::check;
+
+    #[test]
+    fn group_operations_by_path() {
+        let document = OpenApi::new("Test", "1.0.0").with_operations(vec![
+            Operation::new(Method::GET, "/things", "listThings"),
+            Operation::new(Method::POST, "/things", "createThing"),
+            Operation::new(Method::GET, "/things/{id}", "getThing"),
+        ]);
+
+        let serialized = serde_json::to_value(&document).unwrap();
+        check!(serialized["openapi"] == "3.0.3");
+        check!(serialized["info"] == json!({"title": "Test", "version": "1.0.0"}));
+        check!(
+            serialized["paths"]
+                == json!({
+                    "/things": {
+                        "get": {"operationId": "listThings", "responses": {}},
+                        "post": {"operationId": "createThing", "responses": {}}
+                    },
+                    "/things/{id}": {
+                        "get": {"operationId": "getThing", "responses": {}}
+                    }
+                })
+        );
+        check!(serialized["components"]["securitySchemes"]["bearerAuth"]["scheme"] == "bearer");
+    }
+
+    #[test]
+    fn replace_duplicate_operations() {
+        let document = OpenApi::new("Test", "1.0.0")
+            .with_operations(vec![Operation::new(Method::GET, "/things", "first")])
+            .with_operations(vec![Operation::new(Method::GET, "/things", "second")]);
+
+        check!(document.paths["/things"]["get"].operation_id == "second");
+    }
+}
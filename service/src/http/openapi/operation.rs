@@ -0,0 +1,462 @@
+use crate::http::problem::{SimpleProblemType, VALIDATION_FAILED};
+use actix_http::http::{Method, StatusCode};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// The name of the security scheme for requests authenticated with an Auth0 access token.
+pub const BEARER_AUTH: &str = "bearerAuth";
+
+/// The name of the schema shared by every Problem response.
+pub const PROBLEM_SCHEMA: &str = "Problem";
+
+/// The name of the schema shared by the `_links` of every HAL document.
+pub const HAL_LINKS_SCHEMA: &str = "HalLinks";
+
+/// The media type for Problem responses.
+const APPLICATION_PROBLEM_JSON: &str = "application/problem+json";
+
+/// Description of a single operation that the API supports, as a path and HTTP Method.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    /// The HTTP Method of the operation.
+    #[serde(skip)]
+    pub method: Method,
+    /// The path of the operation, in the same form that the route is registered with.
+    #[serde(skip)]
+    pub path: String,
+    /// The unique ID of the operation.
+    pub operation_id: String,
+    /// A short summary of what the operation does.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// The tags to group the operation with.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// The parameters of the operation.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<Parameter>,
+    /// The request body of the operation, if it takes one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<RequestBody>,
+    /// The possible responses of the operation, keyed by status code.
+    pub responses: BTreeMap<String, ResponseDescription>,
+    /// The alternative security requirements of the operation, any one of which is enough.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<BTreeMap<String, Vec<String>>>,
+}
+
+/// Description of a single parameter of an operation.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Parameter {
+    /// The name of the parameter.
+    pub name: String,
+    /// Where the parameter is found - `path` or `query`.
+    #[serde(rename = "in")]
+    pub location: &'static str,
+    /// A description of the parameter.
+    pub description: String,
+    /// Whether the parameter must be provided.
+    pub required: bool,
+    /// The schema of the parameter.
+    pub schema: Value,
+}
+
+/// Description of the request body of an operation.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RequestBody {
+    /// Whether the request body must be provided.
+    pub required: bool,
+    /// The schemas of the request body, keyed by media type.
+    pub content: BTreeMap<String, Content>,
+}
+
+/// Description of a single possible response of an operation.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ResponseDescription {
+    /// A description of what the response means.
+    pub description: String,
+    /// The schemas of the response body, keyed by media type.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub content: BTreeMap<String, Content>,
+    /// The problem types that can be returned with this response, if it's a Problem.
+    #[serde(rename = "x-problem-types", skip_serializing_if = "Vec::is_empty")]
+    pub problem_types: Vec<String>,
+}
+
+/// The schema of a request or response body in a single media type.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct Content {
+    /// The schema of the body.
+    pub schema: Value,
+}
+
+impl Operation {
+    /// Create a new operation description.
+    ///
+    /// # Parameters
+    /// - `method` - The HTTP Method of the operation
+    /// - `path` - The path of the operation, in the same form that the route is registered with
+    /// - `operation_id` - The unique ID of the operation
+    pub fn new<P, I>(method: Method, path: P, operation_id: I) -> Self
+    where
+        P: Into<String>,
+        I: Into<String>,
+    {
+        Self {
+            method,
+            path: path.into(),
+            operation_id: operation_id.into(),
+            summary: None,
+            tags: vec![],
+            parameters: vec![],
+            request_body: None,
+            responses: BTreeMap::new(),
+            security: vec![],
+        }
+    }
+
+    /// Specify a short summary of what the operation does.
+    ///
+    /// # Parameters
+    /// - `summary` - The summary
+    pub fn with_summary<S>(self, summary: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            summary: Some(summary.into()),
+            ..self
+        }
+    }
+
+    /// Add a tag to group the operation with.
+    ///
+    /// # Parameters
+    /// - `tag` - The tag
+    pub fn with_tag<S>(mut self, tag: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.tags.push(tag.into());
+
+        self
+    }
+
+    /// Add a parameter that is part of the path of the operation.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the parameter, as it appears in the path
+    /// - `description` - A description of the parameter
+    pub fn with_path_parameter<N, D>(self, name: N, description: D) -> Self
+    where
+        N: Into<String>,
+        D: Into<String>,
+    {
+        self.with_parameter(Parameter {
+            name: name.into(),
+            location: "path",
+            description: description.into(),
+            required: true,
+            schema: json!({"type": "string"}),
+        })
+    }
+
+    /// Add an optional parameter that is part of the query string of the operation.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the parameter
+    /// - `description` - A description of the parameter
+    /// - `schema` - The schema of the parameter
+    pub fn with_query_parameter<N, D>(self, name: N, description: D, schema: Value) -> Self
+    where
+        N: Into<String>,
+        D: Into<String>,
+    {
+        self.with_parameter(Parameter {
+            name: name.into(),
+            location: "query",
+            description: description.into(),
+            required: false,
+            schema,
+        })
+    }
+
+    /// Add the `fields` and `embed` query parameters that every HAL document supports for projecting the response,
+    /// along with the Problem returned if they are invalid.
+    pub fn with_projection(self) -> Self {
+        self.with_query_parameter(
+            "fields",
+            "Comma-separated list of the fields of the document to include, or of each item for a collection",
+            json!({"type": "string"}),
+        )
+        .with_query_parameter(
+            "embed",
+            "Comma-separated list of the link relations of the embedded resources to include",
+            json!({"type": "string"}),
+        )
+        .with_problem(&VALIDATION_FAILED)
+    }
+
+    /// Specify the request body of the operation.
+    ///
+    /// # Parameters
+    /// - `media_type` - The media type of the request body
+    /// - `schema` - The schema of the request body
+    pub fn with_request_body<M>(mut self, media_type: M, schema: Value) -> Self
+    where
+        M: Into<String>,
+    {
+        let mut request_body = self.request_body.unwrap_or(RequestBody {
+            required: true,
+            content: BTreeMap::new(),
+        });
+        request_body
+            .content
+            .insert(media_type.into(), Content { schema });
+
+        self.request_body = Some(request_body);
+
+        self
+    }
+
+    /// Add a successful response to the operation. Responses with the same status code but different media types are
+    /// combined into a single response.
+    ///
+    /// # Parameters
+    /// - `status_code` - The status code of the response
+    /// - `description` - A description of what the response means
+    /// - `media_type` - The media type of the response body
+    /// - `schema` - The schema of the response body
+    pub fn with_response<D>(
+        mut self,
+        status_code: StatusCode,
+        description: D,
+        media_type: &str,
+        schema: Value,
+    ) -> Self
+    where
+        D: Into<String>,
+    {
+        self.responses
+            .entry(status_code.as_u16().to_string())
+            .or_insert_with(|| ResponseDescription {
+                description: description.into(),
+                content: BTreeMap::new(),
+                problem_types: vec![],
+            })
+            .content
+            .insert(media_type.to_owned(), Content { schema });
+
+        self
+    }
+
+    /// Add a Problem response to the operation. Problem types with the same status code are combined into a single
+    /// response.
+    ///
+    /// # Parameters
+    /// - `problem` - The type of the Problem
+    pub fn with_problem(mut self, problem: &SimpleProblemType) -> Self {
+        let response = self
+            .responses
+            .entry(problem.status_code.as_u16().to_string())
+            .or_insert_with(|| {
+                let mut content = BTreeMap::new();
+                content.insert(
+                    APPLICATION_PROBLEM_JSON.to_owned(),
+                    Content {
+                        schema: schema_ref(PROBLEM_SCHEMA),
+                    },
+                );
+
+                ResponseDescription {
+                    description: problem.problem_title.to_owned(),
+                    content,
+                    problem_types: vec![],
+                }
+            });
+
+        if !response
+            .problem_types
+            .iter()
+            .any(|problem_type| problem_type == problem.problem_type)
+        {
+            response.problem_types.push(problem.problem_type.to_owned());
+        }
+
+        self
+    }
+
+    /// Add a security requirement that is enough on its own to allow the operation.
+    ///
+    /// # Parameters
+    /// - `scheme` - The name of the security scheme
+    /// - `scopes` - The scopes that the caller needs to have
+    pub fn with_security(mut self, scheme: &str, scopes: &[&str]) -> Self {
+        let mut requirement = BTreeMap::new();
+        requirement.insert(
+            scheme.to_owned(),
+            scopes.iter().map(|scope| (*scope).to_owned()).collect(),
+        );
+        self.security.push(requirement);
+
+        self
+    }
+
+    /// Allow the operation to be performed without any authentication, as well as with any other security
+    /// requirements that have been added.
+    pub fn with_anonymous_access(mut self) -> Self {
+        self.security.push(BTreeMap::new());
+
+        self
+    }
+
+    /// Add a parameter to the operation.
+    ///
+    /// # Parameters
+    /// - `parameter` - The parameter
+    fn with_parameter(mut self, parameter: Parameter) -> Self {
+        self.parameters.push(parameter);
+
+        self
+    }
+}
+
+/// Build a reference to one of the shared schemas of the document.
+///
+/// # Parameters
+/// - `name` - The name of the schema
+///
+/// # Returns
+/// The JSON Schema referring to the shared schema.
+pub fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+/// Build the schema for a HAL document with the provided properties, in addition to the standard `_links`.
+///
+/// # Parameters
+/// - `properties` - The JSON Schemas of the properties of the document data, keyed by name
+///
+/// # Returns
+/// The JSON Schema of the document.
+pub fn hal_schema(properties: Value) -> Value {
+    let mut properties = match properties {
+        Value::Object(properties) => properties,
+        _ => serde_json::Map::new(),
+    };
+    properties.insert("_links".to_owned(), schema_ref(HAL_LINKS_SCHEMA));
+
+    json!({
+        "type": "object",
+        "properties": properties,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        hal::APPLICATION_HAL_JSON,
+        problem::{NOT_FOUND, UNAUTHORIZED},
+    };
+    use assert2::check;
+
+    #[test]
+    fn serialize_operation() {
+        let operation = Operation::new(Method::GET, "/things/{id}", "getThing")
+            .with_summary("Get a thing")
+            .with_tag("things")
+            .with_path_parameter("id", "The ID of the thing")
+            .with_response(
+                StatusCode::OK,
+                "The thing",
+                APPLICATION_HAL_JSON.name,
+                hal_schema(json!({"name": {"type": "string"}})),
+            )
+            .with_problem(&NOT_FOUND)
+            .with_problem(&UNAUTHORIZED)
+            .with_security(BEARER_AUTH, &["read:things"])
+            .with_anonymous_access();
+
+        check!(
+            serde_json::to_value(operation).unwrap()
+                == json!({
+                    "operationId": "getThing",
+                    "summary": "Get a thing",
+                    "tags": ["things"],
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "description": "The ID of the thing",
+                            "required": true,
+                            "schema": {"type": "string"}
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "The thing",
+                            "content": {
+                                "application/hal+json": {
+                                    "schema": {
+                                        "type": "object",
+                                        "properties": {
+                                            "name": {"type": "string"},
+                                            "_links": {"$ref": "#/components/schemas/HalLinks"}
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        "401": {
+                            "description": "Unauthorized",
+                            "content": {
+                                "application/problem+json": {
+                                    "schema": {"$ref": "#/components/schemas/Problem"}
+                                }
+                            },
+                            "x-problem-types": ["about:blank"]
+                        },
+                        "404": {
+                            "description": "The requested resource was not found",
+                            "content": {
+                                "application/problem+json": {
+                                    "schema": {"$ref": "#/components/schemas/Problem"}
+                                }
+                            },
+                            "x-problem-types": ["about:blank"]
+                        }
+                    },
+                    "security": [
+                        {"bearerAuth": ["read:things"]},
+                        {}
+                    ]
+                })
+        );
+    }
+
+    #[test]
+    fn combine_problems_with_same_status() {
+        let first = SimpleProblemType {
+            problem_type: "/problems/first",
+            problem_title: "First",
+            status_code: StatusCode::CONFLICT,
+        };
+        let second = SimpleProblemType {
+            problem_type: "/problems/second",
+            problem_title: "Second",
+            status_code: StatusCode::CONFLICT,
+        };
+
+        let operation = Operation::new(Method::POST, "/things", "createThing")
+            .with_problem(&first)
+            .with_problem(&second)
+            .with_problem(&first);
+
+        let response = &operation.responses["409"];
+        check!(response.description == "First");
+        check!(response.problem_types == vec!["/problems/first", "/problems/second"]);
+    }
+}
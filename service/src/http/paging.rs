@@ -0,0 +1,234 @@
+use crate::http::{
+    i18n::Message,
+    problem::{Problem, INVALID_PAGING},
+};
+use actix_http::Payload;
+use actix_web::{web::Query, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use serde::Deserialize;
+
+/// The position in a collection that a page starts from.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PagePosition {
+    /// The page starts at a numeric offset from the start of the collection.
+    Offset(u64),
+    /// The page starts at an opaque cursor provided by a previous page.
+    Cursor(String),
+}
+
+/// Details of the page of a collection that a client has requested.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PageRequest {
+    /// Where in the collection the page starts.
+    pub position: PagePosition,
+    /// The maximum number of items in the page.
+    pub page_size: u64,
+}
+
+/// Configuration for how page requests are parsed.
+#[derive(Debug, Clone)]
+pub struct PageRequestConfig {
+    /// The page size to use if the client doesn't request one.
+    pub default_page_size: u64,
+    /// The largest page size that a client may request.
+    pub max_page_size: u64,
+}
+
+impl Default for PageRequestConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: 10,
+            max_page_size: 100,
+        }
+    }
+}
+
+/// The raw paging parameters as provided on the query string.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPageRequest {
+    offset: Option<String>,
+    cursor: Option<String>,
+    page_size: Option<String>,
+}
+
+impl PageRequest {
+    /// Parse a page request from the provided query string.
+    ///
+    /// # Parameters
+    /// - `query` - The query string to parse
+    /// - `config` - The configuration to apply to the parsing
+    ///
+    /// # Returns
+    /// The page request, or a Problem indicating why the paging parameters were invalid.
+    pub fn parse(query: &str, config: &PageRequestConfig) -> Result<Self, Problem> {
+        let raw = Query::<RawPageRequest>::from_query(query)
+            .map_err(|e| {
+                tracing::warn!(e = ?e, query = ?query, "Failed to parse paging parameters");
+                Problem::from(INVALID_PAGING).with_detail_message("details.paging.unparsable")
+            })?
+            .into_inner();
+
+        let page_size = match raw.page_size {
+            None => config.default_page_size,
+            Some(page_size) => {
+                let page_size = page_size
+                    .parse::<u64>()
+                    .map_err(|_| invalid_parameter("pageSize", "details.paging.pageSizeInvalid"))?;
+
+                if page_size == 0 || page_size > config.max_page_size {
+                    return Err(invalid_parameter(
+                        "pageSize",
+                        Message::new("details.paging.pageSizeRange")
+                            .with_arg("max", &config.max_page_size),
+                    ));
+                }
+
+                page_size
+            }
+        };
+
+        let position = match (raw.offset, raw.cursor) {
+            (Some(_), Some(_)) => {
+                return Err(invalid_parameter(
+                    "offset",
+                    "details.paging.offsetAndCursor",
+                ));
+            }
+            (Some(offset), None) => {
+                let offset = offset
+                    .parse::<u64>()
+                    .map_err(|_| invalid_parameter("offset", "details.paging.offsetInvalid"))?;
+
+                PagePosition::Offset(offset)
+            }
+            (None, Some(cursor)) => {
+                if cursor.trim().is_empty() {
+                    return Err(invalid_parameter("cursor", "details.paging.cursorBlank"));
+                }
+
+                PagePosition::Cursor(cursor)
+            }
+            (None, None) => PagePosition::Offset(0),
+        };
+
+        Ok(Self {
+            position,
+            page_size,
+        })
+    }
+
+    /// Get the offset that the page starts from, for collections that only support offset-based paging.
+    ///
+    /// # Returns
+    /// The offset, or a Problem indicating that the client asked for a cursor instead.
+    ///
+    /// # Errors
+    /// If the page request is for a cursor rather than an offset.
+    pub fn offset(&self) -> Result<u64, Problem> {
+        match &self.position {
+            PagePosition::Offset(offset) => Ok(*offset),
+            PagePosition::Cursor(_) => Err(invalid_parameter(
+                "cursor",
+                "details.paging.cursorUnsupported",
+            )),
+        }
+    }
+}
+
+/// Build a Problem indicating that a single paging parameter was invalid.
+///
+/// # Parameters
+/// - `parameter` - The name of the invalid parameter
+/// - `detail` - The message explaining why it was invalid
+///
+/// # Returns
+/// The problem
+fn invalid_parameter<D>(parameter: &str, detail: D) -> Problem
+where
+    D: Into<Message>,
+{
+    Problem::from(INVALID_PAGING)
+        .with_detail_message(detail)
+        .with_extra("parameter", parameter)
+}
+
+impl FromRequest for PageRequest {
+    type Error = Problem;
+    type Future = Ready<Result<Self, Self::Error>>;
+    type Config = PageRequestConfig;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = match req.app_data::<Self::Config>() {
+            Some(config) => Self::parse(req.query_string(), config),
+            None => Self::parse(req.query_string(), &PageRequestConfig::default()),
+        };
+
+        ready(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use test_case::test_case;
+
+    #[test_case("", &PagePosition::Offset(0), 10 ; "no parameters")]
+    #[test_case("offset=20", &PagePosition::Offset(20), 10 ; "offset only")]
+    #[test_case("pageSize=5", &PagePosition::Offset(0), 5 ; "page size only")]
+    #[test_case("offset=20&pageSize=100", &PagePosition::Offset(20), 100 ; "offset and max page size")]
+    #[test_case("cursor=abc&pageSize=5", &PagePosition::Cursor("abc".to_owned()), 5 ; "cursor and page size")]
+    #[test_case("other=value", &PagePosition::Offset(0), 10 ; "unrelated parameters")]
+    fn parse_valid(query: &str, position: &PagePosition, page_size: u64) {
+        let parsed = PageRequest::parse(query, &PageRequestConfig::default());
+
+        let_assert!(Ok(page_request) = parsed);
+        check!(&page_request.position == position);
+        check!(page_request.page_size == page_size);
+    }
+
+    #[test_case("offset=-1", "offset" ; "negative offset")]
+    #[test_case("offset=abc", "offset" ; "non-numeric offset")]
+    #[test_case("pageSize=0", "pageSize" ; "zero page size")]
+    #[test_case("pageSize=101", "pageSize" ; "page size too large")]
+    #[test_case("pageSize=abc", "pageSize" ; "non-numeric page size")]
+    #[test_case("cursor=", "cursor" ; "blank cursor")]
+    #[test_case("offset=0&cursor=abc", "offset" ; "offset and cursor")]
+    fn parse_invalid(query: &str, parameter: &str) {
+        let parsed = PageRequest::parse(query, &PageRequestConfig::default());
+
+        let_assert!(Err(problem) = parsed);
+        check!(problem.status == 400);
+        check!(problem.error.problem_type() == INVALID_PAGING.problem_type);
+        check!(problem.extra.get("parameter") == Some(&serde_json::to_value(parameter).unwrap()));
+    }
+
+    #[test]
+    fn offset() {
+        let_assert!(
+            Ok(page_request) = PageRequest::parse("offset=20", &PageRequestConfig::default())
+        );
+        let_assert!(Ok(20) = page_request.offset());
+
+        let_assert!(
+            Ok(page_request) = PageRequest::parse("cursor=abc", &PageRequestConfig::default())
+        );
+        let_assert!(Err(problem) = page_request.offset());
+        check!(problem.status == 400);
+        check!(problem.extra.get("parameter") == Some(&serde_json::to_value("cursor").unwrap()));
+    }
+
+    #[test]
+    fn parse_with_config() {
+        let config = PageRequestConfig {
+            default_page_size: 25,
+            max_page_size: 50,
+        };
+
+        let_assert!(Ok(page_request) = PageRequest::parse("", &config));
+        check!(page_request.page_size == 25);
+
+        let_assert!(Err(_) = PageRequest::parse("pageSize=51", &config));
+    }
+}
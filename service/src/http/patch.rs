@@ -0,0 +1,248 @@
+use crate::http::{
+    problem::{
+        payload_error, Problem, MALFORMED_PATCH, UNPROCESSABLE_PATCH, UNSUPPORTED_MEDIA_TYPE,
+    },
+    validation::{validate, Validate},
+};
+use actix_http::{http::header, Payload};
+use actix_web::{web::Bytes, FromRequest, HttpRequest};
+use futures::future::Future;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::pin::Pin;
+
+/// Media type for JSON Merge Patch documents, as defined in RFC 7396.
+pub const APPLICATION_MERGE_PATCH_JSON: &str = "application/merge-patch+json";
+
+/// Media type for JSON Patch documents, as defined in RFC 6902.
+pub const APPLICATION_JSON_PATCH_JSON: &str = "application/json-patch+json";
+
+/// A patch document from a request body, describing a partial update to a resource.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchDocument {
+    /// A JSON Merge Patch document.
+    Merge(Value),
+    /// A JSON Patch document.
+    Json(json_patch::Patch),
+}
+
+impl PatchDocument {
+    /// Parse a patch document from a request body.
+    ///
+    /// # Parameters
+    /// - `content_type` - The media type of the request body, without any parameters
+    /// - `body` - The request body
+    ///
+    /// # Returns
+    /// The patch document, or a Problem indicating why it couldn't be parsed.
+    pub fn parse(content_type: &str, body: &[u8]) -> Result<Self, Problem> {
+        if content_type.eq_ignore_ascii_case(APPLICATION_MERGE_PATCH_JSON) {
+            serde_json::from_slice(body)
+                .map(PatchDocument::Merge)
+                .map_err(|e| malformed(&e))
+        } else if content_type.eq_ignore_ascii_case(APPLICATION_JSON_PATCH_JSON) {
+            serde_json::from_slice(body)
+                .map(PatchDocument::Json)
+                .map_err(|e| malformed(&e))
+        } else {
+            tracing::warn!(content_type = ?content_type, "Unsupported media type for patch document");
+            Err(Problem::from(UNSUPPORTED_MEDIA_TYPE)
+                .with_detail_message("details.patch.unsupportedMediaType")
+                .with_extra(
+                    "supported",
+                    vec![APPLICATION_MERGE_PATCH_JSON, APPLICATION_JSON_PATCH_JSON],
+                ))
+        }
+    }
+
+    /// Apply the patch to the current state of a resource, producing the new state.
+    ///
+    /// The patch is applied to the JSON representation of the resource, and the result is then converted back and
+    /// validated. If the patched JSON doesn't represent a valid resource then the patch is rejected.
+    ///
+    /// # Parameters
+    /// - `current` - The current state of the resource
+    ///
+    /// # Returns
+    /// The patched resource, or a `422 Unprocessable Entity` Problem if the patch couldn't be applied or the result
+    /// isn't valid.
+    pub fn apply<T>(&self, current: &T) -> Result<T, Problem>
+    where
+        T: Serialize + DeserializeOwned + Validate,
+    {
+        let mut document = serde_json::to_value(current).unwrap();
+
+        match self {
+            PatchDocument::Merge(patch) => json_patch::merge(&mut document, patch),
+            PatchDocument::Json(patch) => {
+                json_patch::patch(&mut document, patch).map_err(|e| {
+                    tracing::warn!(e = ?e, "Failed to apply JSON Patch");
+                    Problem::from(UNPROCESSABLE_PATCH)
+                        .with_detail(e.to_string())
+                        .with_source(&e)
+                })?;
+            }
+        }
+
+        let patched = serde_json::from_value(document).map_err(|e| {
+            tracing::warn!(e = ?e, "Patched document is not a valid resource");
+            Problem::from(UNPROCESSABLE_PATCH)
+                .with_detail(e.to_string())
+                .with_source(&e)
+        })?;
+
+        validate(&patched)?;
+
+        Ok(patched)
+    }
+}
+
+/// Build a Problem indicating that a patch document couldn't be parsed.
+///
+/// # Parameters
+/// - `e` - The error from parsing the patch document
+///
+/// # Returns
+/// The problem
+fn malformed(e: &serde_json::Error) -> Problem {
+    tracing::warn!(e = ?e, "Failed to parse patch document");
+    Problem::from(MALFORMED_PATCH)
+        .with_detail(e.to_string())
+        .with_source(e)
+}
+
+impl FromRequest for PatchDocument {
+    type Error = Problem;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_owned())
+            .unwrap_or_default();
+
+        let body = Bytes::from_request(req, payload);
+
+        Box::pin(async move {
+            let body = body.await.map_err(|e| payload_error(&e))?;
+
+            PatchDocument::parse(&content_type, &body)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{problem::VALIDATION_FAILED, validation::Validator};
+    use assert2::{check, let_assert};
+    use serde::Deserialize;
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Model {
+        display_name: String,
+        email: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nickname: Option<String>,
+    }
+
+    impl Validate for Model {
+        fn validate(&self, validator: &mut Validator) {
+            validator.required("displayName", &self.display_name);
+        }
+    }
+
+    fn model() -> Model {
+        Model {
+            display_name: "Graham".to_owned(),
+            email: "graham@example.com".to_owned(),
+            nickname: Some("Gray".to_owned()),
+        }
+    }
+
+    fn merge(patch: Value) -> PatchDocument {
+        PatchDocument::Merge(patch)
+    }
+
+    fn json(patch: Value) -> PatchDocument {
+        PatchDocument::Json(json_patch::from_value(patch).unwrap())
+    }
+
+    #[test_case("application/merge-patch+json", r#"{"displayName": "Bob"}"# ; "merge patch")]
+    #[test_case("Application/Merge-Patch+JSON", r#"{"displayName": "Bob"}"# ; "merge patch case insensitive")]
+    #[test_case("application/json-patch+json", r#"[{"op": "remove", "path": "/nickname"}]"# ; "json patch")]
+    fn parse_valid(content_type: &str, body: &str) {
+        let_assert!(Ok(_) = PatchDocument::parse(content_type, body.as_bytes()));
+    }
+
+    #[test_case("application/merge-patch+json", "not json", 400 ; "malformed merge patch")]
+    #[test_case("application/json-patch+json", r#"{"displayName": "Bob"}"#, 400 ; "malformed json patch")]
+    #[test_case("application/json-patch+json", r#"[{"op": "unknown"}]"#, 400 ; "unknown json patch operation")]
+    #[test_case("application/json", r#"{"displayName": "Bob"}"#, 415 ; "plain json")]
+    #[test_case("", r#"{"displayName": "Bob"}"#, 415 ; "no content type")]
+    fn parse_invalid(content_type: &str, body: &str, status: u16) {
+        let_assert!(Err(problem) = PatchDocument::parse(content_type, body.as_bytes()));
+        check!(problem.status == status);
+    }
+
+    #[actix_rt::test]
+    async fn extract_too_large() {
+        let (req, mut payload) = actix_web::test::TestRequest::default()
+            .header(header::CONTENT_TYPE, APPLICATION_MERGE_PATCH_JSON)
+            .set_payload(vec![b' '; 300_000])
+            .to_http_parts();
+
+        let_assert!(Err(problem) = PatchDocument::from_request(&req, &mut payload).await);
+        check!(problem.status == 413);
+    }
+
+    #[test]
+    fn apply_merge_patch() {
+        let patch = merge(json!({"displayName": "Bob", "nickname": null}));
+
+        let_assert!(Ok(patched) = patch.apply(&model()));
+        check!(patched.display_name == "Bob");
+        check!(patched.email == "graham@example.com");
+        check!(patched.nickname.is_none());
+    }
+
+    #[test]
+    fn apply_json_patch() {
+        let patch = json(json!([
+            {"op": "test", "path": "/displayName", "value": "Graham"},
+            {"op": "replace", "path": "/displayName", "value": "Bob"},
+            {"op": "remove", "path": "/nickname"}
+        ]));
+
+        let_assert!(Ok(patched) = patch.apply(&model()));
+        check!(patched.display_name == "Bob");
+        check!(patched.email == "graham@example.com");
+        check!(patched.nickname.is_none());
+    }
+
+    #[test_case(&json(json!([{"op": "test", "path": "/displayName", "value": "Bob"}])) ; "failed test operation")]
+    #[test_case(&json(json!([{"op": "remove", "path": "/unknown"}])) ; "invalid pointer")]
+    #[test_case(&merge(json!({"email": null})) ; "removes required field")]
+    #[test_case(&merge(json!({"displayName": 42})) ; "wrong type")]
+    fn apply_unprocessable(patch: &PatchDocument) {
+        let_assert!(Err(problem) = patch.apply(&model()));
+        check!(problem.status == 422);
+        check!(problem.error.problem_type() == UNPROCESSABLE_PATCH.problem_type);
+    }
+
+    #[test]
+    fn apply_invalid_result() {
+        let patch = merge(json!({"displayName": ""}));
+
+        let_assert!(Err(problem) = patch.apply(&model()));
+        check!(problem.status == 422);
+        check!(problem.error.problem_type() == VALIDATION_FAILED.problem_type);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::http::problem::{Problem, PRECONDITION_FAILED, PRECONDITION_REQUIRED};
+use crate::model::Resource;
+use actix_http::{
+    http::header::{self, EntityTag, Header, IfMatch},
+    Payload,
+};
+use actix_web::{FromRequest, HttpRequest};
+use futures::future::{ok, Ready};
+
+/// The preconditions that a request has been made with, used for optimistic concurrency control when changing a
+/// resource.
+///
+/// The `If-Match` header is compared against the version of the resource, as the resource is rendered with a strong
+/// `ETag` of its version.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preconditions {
+    /// The parsed `If-Match` header, if one was provided.
+    if_match: Option<IfMatch>,
+}
+
+impl Preconditions {
+    /// Check the preconditions against the current state of the resource, allowing the request to proceed if it has
+    /// no preconditions at all.
+    ///
+    /// # Parameters
+    /// - `resource` - The current state of the resource that the request is for
+    ///
+    /// # Returns
+    /// A `412 Precondition Failed` Problem if the preconditions don't match the resource.
+    pub fn check<I, D>(&self, resource: &Resource<I, D>) -> Result<(), Problem> {
+        match &self.if_match {
+            None => Ok(()),
+            Some(if_match) => check_version(if_match, &resource.identity.version),
+        }
+    }
+
+    /// Check the preconditions against the current state of the resource, requiring that the request has some.
+    ///
+    /// # Parameters
+    /// - `resource` - The current state of the resource that the request is for
+    ///
+    /// # Returns
+    /// A `428 Precondition Required` Problem if the request has no preconditions, or a `412 Precondition Failed`
+    /// Problem if the preconditions don't match the resource.
+    pub fn require<I, D>(&self, resource: &Resource<I, D>) -> Result<(), Problem> {
+        match &self.if_match {
+            None => {
+                tracing::debug!("Request has no If-Match header");
+                Err(Problem::from(PRECONDITION_REQUIRED)
+                    .with_detail_message("details.preconditions.ifMatchRequired"))
+            }
+            Some(if_match) => check_version(if_match, &resource.identity.version),
+        }
+    }
+}
+
+/// Compare an `If-Match` header to the current version of a resource.
+///
+/// As per RFC 7232, this uses the strong comparison function, so weak entity tags never match.
+///
+/// # Parameters
+/// - `if_match` - The `If-Match` header from the request
+/// - `version` - The current version of the resource
+///
+/// # Returns
+/// A `412 Precondition Failed` Problem if the header doesn't match the version.
+fn check_version(if_match: &IfMatch, version: &str) -> Result<(), Problem> {
+    let current = EntityTag::strong(version.to_owned());
+
+    let matches = match if_match {
+        IfMatch::Any => true,
+        IfMatch::Items(tags) => tags.iter().any(|tag| tag.strong_eq(&current)),
+    };
+
+    if matches {
+        Ok(())
+    } else {
+        tracing::debug!(if_match = ?if_match, version = ?version, "If-Match header doesn't match resource version");
+        Err(Problem::from(PRECONDITION_FAILED).with_detail_message("details.preconditions.changed"))
+    }
+}
+
+impl FromRequest for Preconditions {
+    type Error = Problem;
+    type Future = Ready<Result<Self, Self::Error>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let if_match = if req.headers().contains_key(header::IF_MATCH) {
+            match IfMatch::parse(req) {
+                Ok(if_match) => Some(if_match),
+                Err(e) => {
+                    // An If-Match header that can't be parsed can never match anything.
+                    tracing::warn!(e = ?e, "Failed to parse If-Match header");
+                    Some(IfMatch::Items(vec![]))
+                }
+            }
+        } else {
+            None
+        };
+
+        ok(Self { if_match })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Identity;
+    use actix_web::test::TestRequest;
+    use assert2::{check, let_assert};
+    use test_case::test_case;
+
+    fn resource() -> Resource<String, ()> {
+        Resource {
+            identity: Identity {
+                version: "abc".to_owned(),
+                ..Identity::default()
+            },
+            data: (),
+        }
+    }
+
+    async fn preconditions(if_match: Option<&str>) -> Preconditions {
+        let mut req = TestRequest::put();
+        if let Some(if_match) = if_match {
+            req = req.header(header::IF_MATCH, if_match);
+        }
+
+        let (req, mut payload) = req.to_http_parts();
+        Preconditions::from_request(&req, &mut payload)
+            .await
+            .unwrap()
+    }
+
+    #[test_case(r#""abc""# ; "matching version")]
+    #[test_case(r#""def", "abc""# ; "one of many")]
+    #[test_case("*" ; "any")]
+    #[actix_rt::test]
+    async fn matching(if_match: &str) {
+        let preconditions = preconditions(Some(if_match)).await;
+
+        check!(preconditions.check(&resource()).is_ok());
+        check!(preconditions.require(&resource()).is_ok());
+    }
+
+    #[test_case(r#""def""# ; "different version")]
+    #[test_case(r#"W/"abc""# ; "weak tag")]
+    #[test_case("not an etag" ; "unparsable")]
+    #[actix_rt::test]
+    async fn not_matching(if_match: &str) {
+        let preconditions = preconditions(Some(if_match)).await;
+
+        let_assert!(Err(problem) = preconditions.check(&resource()));
+        check!(problem.status == 412);
+
+        let_assert!(Err(problem) = preconditions.require(&resource()));
+        check!(problem.status == 412);
+    }
+
+    #[actix_rt::test]
+    async fn missing() {
+        let preconditions = preconditions(None).await;
+
+        check!(preconditions.check(&resource()).is_ok());
+
+        let_assert!(Err(problem) = preconditions.require(&resource()));
+        check!(problem.status == 428);
+    }
+}
@@ -1,6 +1,13 @@
+mod errors;
+mod middleware;
 mod model;
+mod registry;
 mod response;
 mod types;
 
+pub use errors::*;
+pub use middleware::*;
 pub use model::*;
+pub use registry::*;
+pub use response::*;
 pub use types::*;
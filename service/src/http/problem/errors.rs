@@ -0,0 +1,295 @@
+use super::{
+    Problem, BAD_REQUEST, METHOD_NOT_ALLOWED, NOT_FOUND, PAYLOAD_TOO_LARGE, UNSUPPORTED_MEDIA_TYPE,
+};
+use crate::http::validation::malformed;
+use actix_web::{
+    error::{JsonPayloadError, PathError, PayloadError, QueryPayloadError},
+    http::{header, Method},
+    web::{route, JsonConfig, PathConfig, PayloadConfig, QueryConfig, ServiceConfig},
+    Error, HttpRequest, HttpResponse, Route,
+};
+
+/// The largest request body that any extractor will read, in bytes.
+const MAX_PAYLOAD_SIZE: usize = 262_144;
+
+/// Configure the standard extractors so that any failures to extract from the request are reported as Problems.
+///
+/// Extractors that read the raw request body, such as patch documents, use the `PayloadConfig` and report failures
+/// with `payload_error`.
+///
+/// # Parameters
+/// - `config` - The HTTP Server configuration to register the extractor configuration with.
+pub fn configure_extractors(config: &mut ServiceConfig) {
+    config.app_data(PayloadConfig::new(MAX_PAYLOAD_SIZE));
+    config.app_data(
+        JsonConfig::default()
+            .limit(MAX_PAYLOAD_SIZE)
+            .error_handler(json_error),
+    );
+    config.app_data(PathConfig::default().error_handler(path_error));
+    config.app_data(QueryConfig::default().error_handler(query_error));
+}
+
+/// Convert a failure to extract a JSON request body into a Problem.
+#[allow(clippy::needless_pass_by_value)] // Signature required by `JsonConfig`
+fn json_error(e: JsonPayloadError, _: &HttpRequest) -> Error {
+    tracing::warn!(e = ?e, "Failed to extract JSON request body");
+
+    let problem = match &e {
+        JsonPayloadError::Overflow => Problem::from(PAYLOAD_TOO_LARGE),
+        JsonPayloadError::ContentType => Problem::from(UNSUPPORTED_MEDIA_TYPE)
+            .with_detail_message("details.jsonRequired")
+            .with_extra("supported", vec!["application/json"]),
+        JsonPayloadError::Deserialize(e) => malformed(e),
+        JsonPayloadError::Payload(e) => read_error(e),
+    };
+
+    problem.with_source(&e).into()
+}
+
+/// Convert a failure to read a request body into a Problem, for extractors that read the body themselves.
+///
+/// # Parameters
+/// - `e` - The error from reading the request body
+///
+/// # Returns
+/// A `413 Payload Too Large` Problem if the body was too large, or a `400 Bad Request` Problem otherwise.
+pub fn payload_error(e: &Error) -> Problem {
+    tracing::warn!(e = ?e, "Failed to read request body");
+
+    let problem = match e.as_error::<PayloadError>() {
+        Some(e) => read_error(e),
+        None => Problem::from(BAD_REQUEST).with_detail(e.to_string()),
+    };
+
+    problem.with_source(e)
+}
+
+/// Convert an error from reading the request body into a Problem.
+fn read_error(e: &PayloadError) -> Problem {
+    match e {
+        PayloadError::Overflow => Problem::from(PAYLOAD_TOO_LARGE),
+        _ => Problem::from(BAD_REQUEST).with_detail(e.to_string()),
+    }
+}
+
+/// Convert a failure to extract the URL path into a Problem.
+///
+/// Path parameters identify the resource, so if they can't be extracted then the resource doesn't exist.
+#[allow(clippy::needless_pass_by_value)] // Signature required by `PathConfig`
+fn path_error(e: PathError, _: &HttpRequest) -> Error {
+    tracing::warn!(e = ?e, "Failed to extract URL path");
+
+    Problem::from(NOT_FOUND).with_source(&e).into()
+}
+
+/// Convert a failure to extract the query string into a Problem.
+#[allow(clippy::needless_pass_by_value)] // Signature required by `QueryConfig`
+fn query_error(e: QueryPayloadError, _: &HttpRequest) -> Error {
+    tracing::warn!(e = ?e, "Failed to extract query string");
+
+    Problem::from(BAD_REQUEST)
+        .with_detail(e.to_string())
+        .with_source(&e)
+        .into()
+}
+
+/// Default handler for any request that doesn't match a known route.
+///
+/// # Returns
+/// A `404 Not Found` Problem.
+pub async fn not_found() -> Problem {
+    Problem::from(NOT_FOUND)
+}
+
+/// Build a default service for a resource, reporting any unsupported HTTP Methods as a Problem.
+///
+/// # Parameters
+/// - `allowed` - The HTTP Methods that the resource does support, for the `Allow` header
+///
+/// # Returns
+/// The route to register as the default service of the resource.
+pub fn method_not_allowed(allowed: &[Method]) -> Route {
+    let allowed: Vec<String> = allowed
+        .iter()
+        .map(|method| method.as_str().to_owned())
+        .collect();
+
+    route().to(move |req: HttpRequest| {
+        let allowed = allowed.clone();
+
+        async move {
+            let allow = allowed.join(", ");
+
+            let mut response: HttpResponse = Problem::from(METHOD_NOT_ALLOWED)
+                .with_extra("allowed", allowed)
+                .render(&req);
+            if let Ok(allow) = header::HeaderValue::from_str(&allow) {
+                response.headers_mut().insert(header::ALLOW, allow);
+            }
+
+            Ok::<_, Error>(response)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App, FromRequest};
+    use assert2::check;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Body {
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    async fn handle(
+        _path: web::Path<u32>,
+        _query: web::Query<Body>,
+        _body: web::Json<Body>,
+    ) -> &'static str {
+        "OK"
+    }
+
+    async fn handle_raw(
+        req: HttpRequest,
+        mut payload: web::Payload,
+    ) -> Result<&'static str, Problem> {
+        web::Bytes::from_request(&req, &mut payload.0)
+            .await
+            .map_err(|e| payload_error(&e))?;
+
+        Ok("OK")
+    }
+
+    async fn call(
+        req: test::TestRequest,
+    ) -> (
+        actix_http::http::StatusCode,
+        actix_http::http::HeaderMap,
+        serde_json::Value,
+    ) {
+        let mut app = test::init_service(
+            App::new()
+                .configure(configure_extractors)
+                .default_service(route().to(not_found))
+                .route("/raw", web::post().to(handle_raw))
+                .service(
+                    web::resource("/things/{id}")
+                        .route(web::post().to(handle))
+                        .default_service(method_not_allowed(&[Method::POST])),
+                ),
+        )
+        .await;
+
+        let response = test::call_service(&mut app, req.to_request()).await;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = test::read_body(response).await;
+
+        (status, headers, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn unknown_route() {
+        let (status, headers, body) = call(test::TestRequest::get().uri("/unknown")).await;
+
+        check!(status == 404);
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+        check!(body["type"] == "about:blank");
+    }
+
+    #[actix_rt::test]
+    async fn wrong_method() {
+        let (status, headers, body) = call(test::TestRequest::get().uri("/things/1")).await;
+
+        check!(status == 405);
+        check!(headers.get(header::ALLOW).unwrap() == "POST");
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+        check!(body["allowed"] == serde_json::json!(["POST"]));
+    }
+
+    #[actix_rt::test]
+    async fn bad_path() {
+        let (status, headers, _) = call(
+            test::TestRequest::post()
+                .uri("/things/abc?name=a")
+                .set_json(&serde_json::json!({"name": "a"})),
+        )
+        .await;
+
+        check!(status == 404);
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+    }
+
+    #[actix_rt::test]
+    async fn bad_query() {
+        let (status, headers, _) = call(
+            test::TestRequest::post()
+                .uri("/things/1")
+                .set_json(&serde_json::json!({"name": "a"})),
+        )
+        .await;
+
+        check!(status == 400);
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+    }
+
+    #[actix_rt::test]
+    async fn bad_body() {
+        let (status, headers, body) = call(
+            test::TestRequest::post()
+                .uri("/things/1?name=a")
+                .set_json(&serde_json::json!({"other": "a"})),
+        )
+        .await;
+
+        check!(status == 422);
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+        check!(body["invalid-params"][0]["code"] == "malformed");
+    }
+
+    #[actix_rt::test]
+    async fn body_too_large() {
+        let (status, headers, _) = call(
+            test::TestRequest::post()
+                .uri("/things/1?name=a")
+                .header(header::CONTENT_TYPE, "application/json")
+                .set_payload(vec![b' '; MAX_PAYLOAD_SIZE + 1]),
+        )
+        .await;
+
+        check!(status == 413);
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+    }
+
+    #[actix_rt::test]
+    async fn raw_body_too_large() {
+        let (status, headers, _) = call(
+            test::TestRequest::post()
+                .uri("/raw")
+                .set_payload(vec![b' '; MAX_PAYLOAD_SIZE + 1]),
+        )
+        .await;
+
+        check!(status == 413);
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+    }
+
+    #[actix_rt::test]
+    async fn wrong_content_type() {
+        let (status, headers, _) = call(
+            test::TestRequest::post()
+                .uri("/things/1?name=a")
+                .header(header::CONTENT_TYPE, "text/plain")
+                .set_payload(r#"{"name": "a"}"#),
+        )
+        .await;
+
+        check!(status == 415);
+        check!(headers.get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+    }
+}
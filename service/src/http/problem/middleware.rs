@@ -0,0 +1,223 @@
+use super::{Problem, SimpleProblemType};
+use actix_service::{Service, Transform};
+use actix_web::{
+    dev::{ResponseBody, ServiceRequest, ServiceResponse},
+    http::{header, StatusCode},
+    Error, HttpResponse,
+};
+use futures::future::{ok, Ready};
+use futures::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Middleware for rendering any `Problem` returned as an error in the format that the client prefers.
+///
+/// Errors are converted into responses without access to the request, so this re-renders them once the request is
+/// available again. Any other error responses - for example, those produced by the framework itself - are replaced
+/// with an equivalent Problem, so that every error response is an RFC-7807 Problem.
+pub struct ProblemNegotiation;
+
+impl<S, B> Transform<S> for ProblemNegotiation
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = Middleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(Middleware { service })
+    }
+}
+
+/// Actual middleware implementation.
+pub struct Middleware<S> {
+    service: S,
+}
+
+impl<S, B> Service for Middleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let response = fut.await?;
+
+            let rendered = match response.response().error() {
+                Some(e) => match e.as_error::<Problem>() {
+                    Some(problem) => Some(problem.render(response.request())),
+                    None => Some(replace_response(&response, Some(e))),
+                },
+                None if is_unrendered_error(&response) => Some(replace_response(&response, None)),
+                None => None,
+            };
+
+            match rendered {
+                None => Ok(response),
+                Some(rendered) => {
+                    let rendered = rendered.map_body(|_, body| match body {
+                        ResponseBody::Body(body) | ResponseBody::Other(body) => {
+                            ResponseBody::Other(body)
+                        }
+                    });
+
+                    Ok(response.into_response(rendered))
+                }
+            }
+        })
+    }
+}
+
+/// Determine whether a response is an error response that hasn't been rendered as a Problem.
+///
+/// # Parameters
+/// - `response` - The response to check
+///
+/// # Returns
+/// True if the response needs to be replaced with a Problem. False if not.
+fn is_unrendered_error<B>(response: &ServiceResponse<B>) -> bool {
+    let status = response.status();
+    let is_problem = match response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(value) => value.starts_with("application/problem+"),
+        None => false,
+    };
+
+    (status.is_client_error() || status.is_server_error()) && !is_problem
+}
+
+/// Build a Problem response to replace an error response that wasn't rendered as a Problem.
+///
+/// Any headers from the original response, other than those describing the body, are kept.
+///
+/// # Parameters
+/// - `response` - The response to replace
+/// - `error` - The error that caused the response, if there was one
+///
+/// # Returns
+/// The replacement response
+fn replace_response<B>(response: &ServiceResponse<B>, error: Option<&Error>) -> HttpResponse {
+    let status = response.status();
+    tracing::debug!(status = ?status, error = ?error, "Replacing error response with a Problem");
+
+    let mut problem = Problem::from(generic_problem_type(status));
+    if let Some(e) = error {
+        // Server errors could expose internal details, so only describe client errors.
+        if status.is_client_error() {
+            problem = problem.with_detail(e.to_string());
+        }
+        problem = problem.with_source(e);
+    }
+
+    let mut rendered = problem.render(response.request());
+    for (name, value) in response.headers() {
+        if name != header::CONTENT_TYPE
+            && name != header::CONTENT_LENGTH
+            && !rendered.headers().contains_key(name)
+        {
+            rendered.headers_mut().append(name.clone(), value.clone());
+        }
+    }
+
+    rendered
+}
+
+/// Build a problem type for an error response with the given status code.
+///
+/// # Parameters
+/// - `status` - The status code of the response
+///
+/// # Returns
+/// The problem type
+fn generic_problem_type(status: StatusCode) -> SimpleProblemType {
+    SimpleProblemType {
+        problem_type: "about:blank",
+        problem_title: status.canonical_reason().unwrap_or("Unknown Error"),
+        status_code: status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App};
+    use assert2::check;
+
+    #[actix_rt::test]
+    async fn framework_error_is_replaced() {
+        let mut app = test::init_service(
+            App::new()
+                .wrap(ProblemNegotiation)
+                .service(web::resource("/").route(web::get().to(|| HttpResponse::Ok().finish()))),
+        )
+        .await;
+
+        let response =
+            test::call_service(&mut app, test::TestRequest::post().uri("/").to_request()).await;
+
+        check!(response.status() == StatusCode::METHOD_NOT_ALLOWED);
+        check!(response.headers().get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+
+        let body: serde_json::Value = test::read_body_json(response).await;
+        check!(body["title"] == "Method Not Allowed");
+        check!(body["status"] == 405);
+    }
+
+    #[actix_rt::test]
+    async fn error_is_replaced() {
+        let mut app = test::init_service(App::new().wrap(ProblemNegotiation).route(
+            "/",
+            web::get().to(|| async {
+                Err::<HttpResponse, _>(actix_web::error::ErrorConflict("Something clashed"))
+            }),
+        ))
+        .await;
+
+        let response =
+            test::call_service(&mut app, test::TestRequest::get().uri("/").to_request()).await;
+
+        check!(response.status() == StatusCode::CONFLICT);
+        check!(response.headers().get(header::CONTENT_TYPE).unwrap() == "application/problem+json");
+
+        let body: serde_json::Value = test::read_body_json(response).await;
+        check!(body["title"] == "Conflict");
+        check!(body["detail"] == "Something clashed");
+    }
+
+    #[actix_rt::test]
+    async fn success_is_untouched() {
+        let mut app = test::init_service(App::new().wrap(ProblemNegotiation).route(
+            "/",
+            web::get().to(|| HttpResponse::Ok().content_type("text/plain").body("OK")),
+        ))
+        .await;
+
+        let response =
+            test::call_service(&mut app, test::TestRequest::get().uri("/").to_request()).await;
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers().get(header::CONTENT_TYPE).unwrap() == "text/plain");
+    }
+}
@@ -1,9 +1,12 @@
+use crate::http::i18n::{Message, DEFAULT_LANGUAGE};
 use actix_http::http::StatusCode;
 use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 
+pub use newlanding_service_derive::ProblemType;
+
 /// Trait to represent the type of problem.
 pub trait ProblemType: Debug + Display {
     /// A URI Reference that identifies the problem type.
@@ -25,10 +28,14 @@ pub struct Problem {
     pub status: StatusCode,
     /// An additional detail message
     pub detail: Option<String>,
+    /// The message that the detail was rendered from, so that it can be rendered again in other languages
+    pub detail_message: Option<Message>,
     /// An additional instance subtype
     pub instance: Option<String>,
     /// Any extra details
     pub extra: HashMap<String, Value>,
+    /// The messages of the chain of errors that caused the problem, starting with the outermost
+    pub causes: Vec<String>,
 }
 
 impl Display for Problem {
@@ -54,8 +61,10 @@ impl Problem {
             error: Box::new(error),
             status,
             detail: None,
+            detail_message: None,
             instance: None,
             extra: HashMap::new(),
+            causes: vec![],
         }
     }
 
@@ -85,6 +94,29 @@ impl Problem {
     {
         Self {
             detail: Some(detail.into()),
+            detail_message: None,
+            ..self
+        }
+    }
+
+    /// Set the Detail of the Problem instance from a message in the message catalogs, so that it can be rendered in
+    /// the language that the client prefers.
+    ///
+    /// # Parameters
+    /// - `message` - The message to use
+    pub fn with_detail_message<M>(self, message: M) -> Self
+    where
+        M: Into<Message>,
+    {
+        let message = message.into();
+        let detail = match message.render(DEFAULT_LANGUAGE) {
+            Some((_, detail)) => detail,
+            None => message.key.to_owned(),
+        };
+
+        Self {
+            detail: Some(detail),
+            detail_message: Some(message),
             ..self
         }
     }
@@ -104,6 +136,27 @@ impl Problem {
         }
     }
 
+    /// Record the error that caused the Problem, along with the chain of errors that caused it in turn.
+    ///
+    /// These are only ever rendered to the client when running in development mode.
+    ///
+    /// # Parameters
+    /// - `error` - The error that caused the Problem
+    pub fn with_source<E>(self, error: &E) -> Self
+    where
+        E: std::error::Error + ?Sized,
+    {
+        let mut causes = vec![error.to_string()];
+
+        let mut source = error.source();
+        while let Some(e) = source {
+            causes.push(e.to_string());
+            source = e.source();
+        }
+
+        Self { causes, ..self }
+    }
+
     /// Set some extra data on the Problem instance
     ///
     /// # Parameters
@@ -206,4 +259,83 @@ mod tests {
             problem.extra.get(&"other_key".to_owned())
         );
     }
+
+    #[test]
+    fn test_problem_with_detail_message() {
+        let problem = Problem::new(ProblemDetails::SomeProblem)
+            .with_detail_message(Message::new("details.paging.pageSizeRange").with_arg("max", &10));
+
+        assert_eq!(
+            Some("The page size must be between 1 and 10".to_owned()),
+            problem.detail
+        );
+        assert_eq!(
+            Some("details.paging.pageSizeRange"),
+            problem.detail_message.map(|message| message.key)
+        );
+    }
+
+    #[test]
+    fn test_detail_replaces_detail_message() {
+        let problem = Problem::new(ProblemDetails::SomeProblem)
+            .with_detail_message("details.jsonRequired")
+            .with_detail("Some Detail");
+
+        assert_eq!(Some("Some Detail".to_owned()), problem.detail);
+        assert_eq!(None, problem.detail_message);
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("Outer error")]
+    struct OuterError(#[source] std::io::Error);
+
+    #[test]
+    fn test_problem_with_source() {
+        let error = OuterError(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "Inner error",
+        ));
+        let problem = Problem::new(ProblemDetails::SomeProblem).with_source(&error);
+
+        assert_eq!(
+            vec!["Outer error".to_owned(), "Inner error".to_owned()],
+            problem.causes
+        );
+    }
+
+    #[derive(Debug, ProblemType)]
+    pub enum DerivedProblem {
+        #[problem(type = "/problems/first", title = "The first problem", status = 409)]
+        First,
+        #[problem(type = "/problems/second", title = "The second problem", status = 422)]
+        Second { field: String },
+    }
+
+    #[derive(Debug, ProblemType)]
+    #[problem(type = "/problems/single", title = "The single problem", status = 503)]
+    pub struct SingleProblem;
+
+    #[test]
+    fn test_derived_enum_problem() {
+        let first = Problem::from(DerivedProblem::First);
+        assert_eq!(StatusCode::CONFLICT, first.status);
+        assert_eq!("/problems/first", first.error.problem_type());
+        assert_eq!("The first problem", first.error.to_string());
+
+        let second = Problem::from(DerivedProblem::Second {
+            field: "name".to_owned(),
+        });
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, second.status);
+        assert_eq!("/problems/second", second.error.problem_type());
+        assert_eq!("The second problem", second.error.to_string());
+    }
+
+    #[test]
+    fn test_derived_struct_problem() {
+        let problem = Problem::from(SingleProblem);
+
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, problem.status);
+        assert_eq!("/problems/single", problem.error.problem_type());
+        assert_eq!("The single problem", problem.error.to_string());
+    }
 }
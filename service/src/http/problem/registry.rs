@@ -0,0 +1,219 @@
+use super::{
+    SimpleProblemType, INVALID_PAGING, MALFORMED_PATCH, UNPROCESSABLE_PATCH, VALIDATION_FAILED,
+};
+use actix_http::http::StatusCode;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The prefix of the problem type URIs that are documented by this service.
+pub const PROBLEM_TYPE_PREFIX: &str = "/problems/";
+
+/// Documentation of a single problem type, so that clients are able to find out what it means.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProblemDocumentation {
+    /// The URI that identifies the problem type.
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// The title of the problem type.
+    pub title: String,
+    /// A description of what the problem type means.
+    pub description: String,
+    /// The HTTP Status codes that the problem type can be returned with.
+    pub status_codes: Vec<u16>,
+    /// The extension members that the problem type can include, and what they mean.
+    pub extensions: BTreeMap<String, String>,
+}
+
+impl ProblemDocumentation {
+    /// Create the documentation for a problem type.
+    ///
+    /// # Parameters
+    /// - `problem_type` - The problem type to document
+    /// - `description` - A description of what the problem type means
+    pub fn new<D>(problem_type: &SimpleProblemType, description: D) -> Self
+    where
+        D: Into<String>,
+    {
+        Self {
+            problem_type: problem_type.problem_type.to_owned(),
+            title: problem_type.problem_title.to_owned(),
+            description: description.into(),
+            status_codes: vec![problem_type.status_code.as_u16()],
+            extensions: BTreeMap::new(),
+        }
+    }
+
+    /// Record an additional HTTP Status code that the problem type can be returned with.
+    ///
+    /// # Parameters
+    /// - `status_code` - The status code
+    #[allow(dead_code)]
+    pub fn with_status_code(mut self, status_code: StatusCode) -> Self {
+        let status_code = status_code.as_u16();
+        if !self.status_codes.contains(&status_code) {
+            self.status_codes.push(status_code);
+        }
+
+        self
+    }
+
+    /// Record an extension member that the problem type can include.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the extension member
+    /// - `description` - A description of what the extension member means
+    pub fn with_extension<N, D>(mut self, name: N, description: D) -> Self
+    where
+        N: Into<String>,
+        D: Into<String>,
+    {
+        self.extensions.insert(name.into(), description.into());
+
+        self
+    }
+
+    /// Determine the name of the problem type, as used in the URL of its documentation.
+    ///
+    /// # Returns
+    /// The name of the problem type.
+    pub fn name(&self) -> &str {
+        self.problem_type
+            .strip_prefix(PROBLEM_TYPE_PREFIX)
+            .unwrap_or(&self.problem_type)
+    }
+}
+
+/// Registry of all the problem types that the service documents.
+#[derive(Debug, Default)]
+pub struct ProblemRegistry {
+    /// The documented problem types, keyed by their name.
+    problems: BTreeMap<String, ProblemDocumentation>,
+}
+
+impl ProblemRegistry {
+    /// Create a registry containing the problem types that are used throughout the service.
+    ///
+    /// Problem types of `about:blank` aren't included, since they mean nothing more than their HTTP Status code.
+    pub fn standard() -> Self {
+        Self::default()
+            .with_problem(
+                ProblemDocumentation::new(
+                    &INVALID_PAGING,
+                    "The paging parameters in the query string of a request for a collection were invalid.",
+                )
+                .with_extension("parameter", "The name of the query string parameter that was invalid."),
+            )
+            .with_problem(ProblemDocumentation::new(
+                &MALFORMED_PATCH,
+                "The request body of a PATCH request couldn't be parsed as either a JSON Merge Patch or a JSON Patch document.",
+            ))
+            .with_problem(ProblemDocumentation::new(
+                &UNPROCESSABLE_PATCH,
+                "The patch document was understood, but couldn't be applied to the current state of the resource.",
+            ))
+            .with_problem(
+                ProblemDocumentation::new(
+                    &VALIDATION_FAILED,
+                    "Some of the values in the request were missing or invalid.",
+                )
+                .with_extension(
+                    "invalid-params",
+                    "The values that were invalid. Each entry has the JSON Pointer to the value as `name`, a machine-readable `code` and a human-readable `reason`.",
+                ),
+            )
+    }
+
+    /// Register the documentation of a problem type.
+    ///
+    /// # Parameters
+    /// - `documentation` - The documentation of the problem type
+    pub fn with_problem(mut self, documentation: ProblemDocumentation) -> Self {
+        self.problems
+            .insert(documentation.name().to_owned(), documentation);
+
+        self
+    }
+
+    /// Get the documentation of a single problem type.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the problem type
+    ///
+    /// # Returns
+    /// The documentation of the problem type, if it is known.
+    pub fn get(&self, name: &str) -> Option<&ProblemDocumentation> {
+        self.problems.get(name)
+    }
+
+    /// Get the documentation of every problem type, ordered by name.
+    ///
+    /// # Returns
+    /// An iterator over the documentation of the problem types.
+    pub fn problems(&self) -> impl Iterator<Item = &ProblemDocumentation> {
+        self.problems.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    #[test]
+    fn serialize_documentation() {
+        let documentation = ProblemDocumentation::new(&VALIDATION_FAILED, "Something was invalid")
+            .with_status_code(StatusCode::BAD_REQUEST)
+            .with_status_code(StatusCode::UNPROCESSABLE_ENTITY)
+            .with_extension("invalid-params", "The invalid values");
+
+        check!(documentation.name() == "validation_failed");
+        check!(
+            serde_json::to_value(documentation).unwrap()
+                == json!({
+                    "type": "/problems/validation_failed",
+                    "title": "The request contained invalid values",
+                    "description": "Something was invalid",
+                    "statusCodes": [422, 400],
+                    "extensions": {
+                        "invalid-params": "The invalid values"
+                    }
+                })
+        );
+    }
+
+    #[test]
+    fn standard_problems_are_registered() {
+        let registry = ProblemRegistry::standard();
+
+        let_assert!(Some(documentation) = registry.get("invalid_paging"));
+        check!(documentation.problem_type == INVALID_PAGING.problem_type);
+
+        let names: Vec<&str> = registry
+            .problems()
+            .map(ProblemDocumentation::name)
+            .collect();
+        check!(
+            names
+                == vec![
+                    "invalid_paging",
+                    "malformed_patch",
+                    "unprocessable_patch",
+                    "validation_failed"
+                ]
+        );
+    }
+
+    #[test]
+    fn standard_problems_are_dereferenceable() {
+        for documentation in ProblemRegistry::standard().problems() {
+            check!(documentation.problem_type.starts_with(PROBLEM_TYPE_PREFIX));
+        }
+    }
+
+    #[test]
+    fn unknown_problem() {
+        check!(ProblemRegistry::standard().get("unknown").is_none());
+    }
+}
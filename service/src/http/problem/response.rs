@@ -1,4 +1,9 @@
-use super::Problem;
+use super::{Problem, PROBLEM_TYPE_PREFIX};
+use crate::http::{
+    correlation::Correlation,
+    i18n::{negotiate_language, translate, DEFAULT_LANGUAGE},
+    negotiation::negotiate,
+};
 use actix_web::{
     error::ResponseError,
     http::{header, StatusCode},
@@ -7,7 +12,21 @@ use actix_web::{
 use futures::future::{ready, Ready};
 use serde::Serialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+/// Media type for Problem responses rendered as JSON.
+const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Media type for Problem responses rendered as XML.
+const PROBLEM_XML: &str = "application/problem+xml";
+
+/// Configuration of how Problems are rendered, registered as application data on the HTTP Server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProblemConfig {
+    /// Whether the service is running in development mode, in which case the chain of errors that caused a Problem is
+    /// included in the response.
+    pub development_mode: bool,
+}
 
 /// HTTP representation of an RFC-7807 Problem response.
 #[derive(Serialize)]
@@ -24,24 +43,200 @@ struct ProblemModel {
     /// An additional instance subtype
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instance: Option<String>,
-    /// Any extra details
+    /// Any extra details, ordered by name so that the output is stable
     #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub extra: BTreeMap<String, Value>,
+    /// The language that the title is in
+    #[serde(skip)]
+    pub language: &'static str,
 }
 
-impl From<&Problem> for HttpResponse {
-    fn from(problem: &Problem) -> Self {
-        let body = ProblemModel {
-            r#type: problem.error.problem_type().to_owned(),
-            title: problem.error.to_string(),
+impl ProblemModel {
+    /// Build the HTTP representation of a problem, with the title and detail in the requested language if possible.
+    ///
+    /// The title is looked up in the message catalogs by the name of the problem type, or by the status code for
+    /// problem types that are `about:blank`. If it isn't in any catalog then the title of the problem type is used as
+    /// it is. The detail is only translated if it was provided as a message from the catalogs.
+    ///
+    /// # Parameters
+    /// - `problem` - The problem to represent
+    /// - `language` - The language to use
+    ///
+    /// # Returns
+    /// The HTTP representation of the problem
+    fn localized(problem: &Problem, language: &str) -> Self {
+        let problem_type = problem.error.problem_type();
+        let title_key = match problem_type.strip_prefix(PROBLEM_TYPE_PREFIX) {
+            Some(name) => format!("problems.{}", name),
+            None => format!("status.{}", problem.status.as_u16()),
+        };
+
+        let (language, title) = match translate(language, &title_key) {
+            Some((language, title)) => (language, title.to_owned()),
+            None => (DEFAULT_LANGUAGE, problem.error.to_string()),
+        };
+
+        let detail = match &problem.detail_message {
+            Some(message) => message
+                .render(language)
+                .map(|(_, detail)| detail)
+                .or_else(|| problem.detail.clone()),
+            None => problem.detail.clone(),
+        };
+
+        Self {
+            r#type: problem_type.to_owned(),
+            title,
             status: problem.status.as_u16(),
-            detail: problem.detail.clone(),
+            detail,
             instance: problem.instance.clone(),
-            extra: problem.extra.clone(),
+            extra: problem
+                .extra
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            language,
+        }
+    }
+}
+
+impl From<&Problem> for ProblemModel {
+    fn from(problem: &Problem) -> Self {
+        Self::localized(problem, DEFAULT_LANGUAGE)
+    }
+}
+
+impl Problem {
+    /// Render the problem as an HTTP Response, in whichever format and language the client prefers.
+    ///
+    /// If the client accepts neither of the supported formats then the problem is rendered as JSON anyway, since
+    /// failing to report the original problem would be less helpful.
+    ///
+    /// The request and trace IDs are included as extension members, so that the problem can be found in the traces.
+    ///
+    /// # Parameters
+    /// - `req` - The HTTP Request that the problem is in response to
+    ///
+    /// # Returns
+    /// The HTTP Response
+    pub fn render(&self, req: &HttpRequest) -> HttpResponse {
+        let mut body = ProblemModel::localized(self, negotiate_language(req));
+
+        if let Some(correlation) = req.extensions().get::<Correlation>() {
+            body.extra.insert(
+                "requestId".to_owned(),
+                Value::from(correlation.request_id.clone()),
+            );
+            if let Some(trace_id) = &correlation.trace_id {
+                body.extra
+                    .insert("traceId".to_owned(), Value::from(trace_id.clone()));
+            }
+        }
+
+        let development_mode = match req.app_data::<ProblemConfig>() {
+            Some(config) => config.development_mode,
+            None => false,
         };
+        if development_mode && !self.causes.is_empty() {
+            body.extra
+                .insert("causes".to_owned(), Value::from(self.causes.clone()));
+        }
+
+        let mut response = HttpResponse::build(self.status);
+        response
+            .header(header::CONTENT_LANGUAGE, body.language)
+            .header(header::VARY, "Accept, Accept-Language");
+
+        if negotiate(req, &[PROBLEM_JSON, PROBLEM_XML]) == Some(1) {
+            response
+                .header(header::CONTENT_TYPE, PROBLEM_XML)
+                .body(to_xml(&body))
+        } else {
+            response
+                .header(header::CONTENT_TYPE, PROBLEM_JSON)
+                .json(body)
+        }
+    }
+}
+
+/// Render a problem as an RFC-7807 XML document.
+///
+/// # Parameters
+/// - `problem` - The problem to render
+///
+/// # Returns
+/// The XML document
+fn to_xml(problem: &ProblemModel) -> String {
+    let mut result = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    result.push_str(r#"<problem xmlns="urn:ietf:rfc:7807">"#);
+
+    if let Ok(Value::Object(fields)) = serde_json::to_value(problem) {
+        for (name, value) in &fields {
+            write_xml_element(&mut result, name, value);
+        }
+    }
+
+    result.push_str("</problem>");
+
+    result
+}
+
+/// Write a single JSON value as an XML element, following the rules in RFC-7807 Appendix A.
+///
+/// # Parameters
+/// - `output` - The string to write the element to
+/// - `name` - The name of the element
+/// - `value` - The value to write
+fn write_xml_element(output: &mut String, name: &str, value: &Value) {
+    output.push('<');
+    output.push_str(name);
+    output.push('>');
+
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => output.push_str(&b.to_string()),
+        Value::Number(n) => output.push_str(&n.to_string()),
+        Value::String(s) => output.push_str(&escape_xml(s)),
+        Value::Array(values) => {
+            for value in values {
+                write_xml_element(output, "i", value);
+            }
+        }
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                write_xml_element(output, name, value);
+            }
+        }
+    }
+
+    output.push_str("</");
+    output.push_str(name);
+    output.push('>');
+}
+
+/// Escape a string so that it is safe to use as XML text content.
+///
+/// # Parameters
+/// - `value` - The string to escape
+///
+/// # Returns
+/// The escaped string
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+impl From<&Problem> for HttpResponse {
+    fn from(problem: &Problem) -> Self {
+        let body = ProblemModel::from(problem);
 
         Self::build(problem.status)
-            .header(header::CONTENT_TYPE, "application/problem+json")
+            .header(header::CONTENT_TYPE, PROBLEM_JSON)
+            .header(header::CONTENT_LANGUAGE, body.language)
             .json(body)
     }
 }
@@ -56,8 +251,8 @@ impl Responder for Problem {
     type Error = Error;
     type Future = Ready<Result<HttpResponse, Error>>;
 
-    fn respond_to(self, _req: &HttpRequest) -> Self::Future {
-        ready(Ok(self.into()))
+    fn respond_to(self, req: &HttpRequest) -> Self::Future {
+        ready(Ok(self.render(req)))
     }
 }
 
@@ -70,3 +265,162 @@ impl ResponseError for Problem {
         self.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{
+        i18n::Message,
+        problem::{SimpleProblemType, INVALID_PAGING, NOT_FOUND},
+    };
+    use actix_http::body::Body;
+    use actix_web::test::TestRequest;
+    use assert2::check;
+    use serde_json::json;
+    use test_case::test_case;
+
+    fn body(response: &HttpResponse) -> Value {
+        match response.body().as_ref() {
+            Some(Body::Bytes(bytes)) => serde_json::from_slice(bytes).unwrap(),
+            _ => Value::Null,
+        }
+    }
+
+    #[test]
+    fn render_json_by_default() {
+        let req = TestRequest::get().to_http_request();
+        let response = Problem::from(NOT_FOUND).render(&req);
+
+        check!(response.status() == StatusCode::NOT_FOUND);
+        check!(response.headers().get("content-type").unwrap() == PROBLEM_JSON);
+    }
+
+    #[test]
+    fn render_json_when_nothing_acceptable() {
+        let req = TestRequest::get()
+            .header(header::ACCEPT, "text/html")
+            .to_http_request();
+        let response = Problem::from(NOT_FOUND).render(&req);
+
+        check!(response.headers().get("content-type").unwrap() == PROBLEM_JSON);
+    }
+
+    #[test]
+    fn render_xml_when_requested() {
+        let req = TestRequest::get()
+            .header(header::ACCEPT, PROBLEM_XML)
+            .to_http_request();
+        let response = Problem::from(NOT_FOUND).render(&req);
+
+        check!(response.status() == StatusCode::NOT_FOUND);
+        check!(response.headers().get("content-type").unwrap() == PROBLEM_XML);
+    }
+
+    #[test]
+    fn render_correlation() {
+        let req = TestRequest::get().to_http_request();
+        req.extensions_mut().insert(Correlation {
+            request_id: "abc-123".to_owned(),
+            trace_id: Some("0123456789abcdef0123456789abcdef".to_owned()),
+        });
+
+        let response = Problem::from(NOT_FOUND).render(&req);
+
+        let body = body(&response);
+        check!(body["requestId"] == "abc-123");
+        check!(body["traceId"] == "0123456789abcdef0123456789abcdef");
+    }
+
+    #[test_case(false, None ; "production")]
+    #[test_case(true, Some(&["Outer", "Inner"]) ; "development")]
+    fn render_causes(development_mode: bool, expected: Option<&[&str]>) {
+        let req = TestRequest::get()
+            .app_data(ProblemConfig { development_mode })
+            .to_http_request();
+        let mut problem = Problem::from(NOT_FOUND);
+        problem.causes = vec!["Outer".to_owned(), "Inner".to_owned()];
+
+        let response = problem.render(&req);
+
+        let body = body(&response);
+        check!(body.get("causes") == expected.map(|causes| json!(causes)).as_ref());
+    }
+
+    #[test]
+    fn render_in_english_by_default() {
+        let req = TestRequest::get().to_http_request();
+        let response = Problem::from(NOT_FOUND).render(&req);
+
+        check!(response.headers().get(header::CONTENT_LANGUAGE).unwrap() == "en");
+        check!(response.headers().get(header::VARY).unwrap() == "Accept, Accept-Language");
+    }
+
+    #[test]
+    fn localize_title_and_detail() {
+        let problem = Problem::from(INVALID_PAGING)
+            .with_detail_message(Message::new("details.paging.pageSizeRange").with_arg("max", &10));
+
+        let model = ProblemModel::localized(&problem, "fr");
+
+        check!(model.language == "fr");
+        check!(model.title == "Les paramètres de pagination étaient invalides");
+        check!(
+            model.detail == Some("La taille de page doit être comprise entre 1 et 10".to_owned())
+        );
+    }
+
+    #[test]
+    fn localize_about_blank_by_status() {
+        let model = ProblemModel::localized(&Problem::from(NOT_FOUND), "fr");
+
+        check!(model.language == "fr");
+        check!(model.title == "La ressource demandée est introuvable");
+    }
+
+    #[test]
+    fn localize_unknown_language() {
+        let problem = Problem::from(INVALID_PAGING).with_detail("Something went wrong");
+
+        let model = ProblemModel::localized(&problem, "de");
+
+        check!(model.language == "en");
+        check!(model.title == "The paging parameters were invalid");
+        check!(model.detail == Some("Something went wrong".to_owned()));
+    }
+
+    #[test]
+    fn localize_unknown_title() {
+        let problem = Problem::from(SimpleProblemType {
+            problem_type: "about:blank",
+            problem_title: "I'm a teapot",
+            status_code: StatusCode::IM_A_TEAPOT,
+        });
+
+        let model = ProblemModel::localized(&problem, "fr");
+
+        check!(model.language == "en");
+        check!(model.title == "I'm a teapot");
+    }
+
+    #[test]
+    fn xml_document() {
+        let problem = Problem::from(NOT_FOUND)
+            .with_detail("The <user> wasn't found")
+            .with_extra("ids", vec!["a", "b"]);
+
+        let xml = to_xml(&ProblemModel::from(&problem));
+
+        check!(
+            xml == concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<problem xmlns="urn:ietf:rfc:7807">"#,
+                "<type>about:blank</type>",
+                "<title>The requested resource was not found</title>",
+                "<status>404</status>",
+                "<detail>The &lt;user&gt; wasn&apos;t found</detail>",
+                "<ids><i>a</i><i>b</i></ids>",
+                "</problem>"
+            )
+        );
+    }
+}
@@ -52,3 +52,94 @@ pub const UNAUTHORIZED: SimpleProblemType = SimpleProblemType {
     problem_title: "Unauthorized",
     status_code: StatusCode::UNAUTHORIZED,
 };
+
+/// Problem to indicate that the caller isn't allowed to perform the request.
+pub const FORBIDDEN: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Forbidden",
+    status_code: StatusCode::FORBIDDEN,
+};
+
+/// Problem to indicate that the paging parameters of a request were invalid.
+pub const INVALID_PAGING: SimpleProblemType = SimpleProblemType {
+    problem_type: "/problems/invalid_paging",
+    problem_title: "The paging parameters were invalid",
+    status_code: StatusCode::BAD_REQUEST,
+};
+
+/// Problem to indicate that the response can't be produced in any of the media types that the client accepts.
+pub const NOT_ACCEPTABLE: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Not Acceptable",
+    status_code: StatusCode::NOT_ACCEPTABLE,
+};
+
+/// Problem to indicate that the preconditions of a request didn't match the current state of the resource.
+pub const PRECONDITION_FAILED: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Precondition Failed",
+    status_code: StatusCode::PRECONDITION_FAILED,
+};
+
+/// Problem to indicate that a request must be made conditional on the current state of the resource.
+pub const PRECONDITION_REQUIRED: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Precondition Required",
+    status_code: StatusCode::PRECONDITION_REQUIRED,
+};
+
+/// Problem to indicate that the request body was in a media type that isn't supported.
+pub const UNSUPPORTED_MEDIA_TYPE: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Unsupported Media Type",
+    status_code: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+};
+
+/// Problem to indicate that a patch document couldn't be parsed.
+pub const MALFORMED_PATCH: SimpleProblemType = SimpleProblemType {
+    problem_type: "/problems/malformed_patch",
+    problem_title: "The patch document was malformed",
+    status_code: StatusCode::BAD_REQUEST,
+};
+
+/// Problem to indicate that a patch document couldn't be applied to the resource.
+pub const UNPROCESSABLE_PATCH: SimpleProblemType = SimpleProblemType {
+    problem_type: "/problems/unprocessable_patch",
+    problem_title: "The patch could not be applied to the resource",
+    status_code: StatusCode::UNPROCESSABLE_ENTITY,
+};
+
+/// Problem to indicate that some of the values in a request were invalid.
+pub const VALIDATION_FAILED: SimpleProblemType = SimpleProblemType {
+    problem_type: "/problems/validation_failed",
+    problem_title: "The request contained invalid values",
+    status_code: StatusCode::UNPROCESSABLE_ENTITY,
+};
+
+/// Problem to indicate that the request was malformed in some way.
+pub const BAD_REQUEST: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Bad Request",
+    status_code: StatusCode::BAD_REQUEST,
+};
+
+/// Problem to indicate that the requested resource doesn't support the HTTP Method used.
+pub const METHOD_NOT_ALLOWED: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Method Not Allowed",
+    status_code: StatusCode::METHOD_NOT_ALLOWED,
+};
+
+/// Problem to indicate that the request body was too large.
+pub const PAYLOAD_TOO_LARGE: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Payload Too Large",
+    status_code: StatusCode::PAYLOAD_TOO_LARGE,
+};
+
+/// Problem to indicate that the request couldn't be completed because of an unexpected failure.
+pub const INTERNAL_SERVER_ERROR: SimpleProblemType = SimpleProblemType {
+    problem_type: "about:blank",
+    problem_title: "Internal Server Error",
+    status_code: StatusCode::INTERNAL_SERVER_ERROR,
+};
@@ -0,0 +1,154 @@
+use crate::http::problem::method_not_allowed;
+use actix_http::http::Method;
+use actix_web::{
+    dev::Factory,
+    web::{self, ServiceConfig},
+    FromRequest, Responder,
+};
+use futures::future::Future;
+
+#[cfg(test)]
+use std::cell::RefCell;
+
+#[cfg(test)]
+thread_local! {
+    /// The routes registered on this thread while they are being recorded, or `None` if they aren't.
+    static RECORDED: RefCell<Option<Vec<(Method, String)>>> = RefCell::new(None);
+}
+
+/// Builder for the routes of a single resource, with a route for each HTTP Method that the resource supports.
+///
+/// Requests with any other HTTP Method are reported as a `405 Method Not Allowed` Problem listing the supported ones.
+pub struct Builder {
+    path: &'static str,
+    resource: actix_web::Resource,
+    methods: Vec<Method>,
+}
+
+/// Create a new builder for the routes of a resource.
+///
+/// # Parameters
+/// - `path` - The path pattern of the resource
+pub fn new(path: &'static str) -> Builder {
+    Builder {
+        path,
+        resource: web::resource(path),
+        methods: vec![],
+    }
+}
+
+impl Builder {
+    /// Add a route to handle requests with a given HTTP Method.
+    ///
+    /// # Parameters
+    /// - `method` - The HTTP Method of the requests to handle
+    /// - `handler` - The handler for the requests
+    pub fn with_route<F, T, R, U>(mut self, method: Method, handler: F) -> Self
+    where
+        F: Factory<T, R, U>,
+        T: FromRequest + 'static,
+        R: Future<Output = U> + 'static,
+        U: Responder + 'static,
+    {
+        self.resource = self.resource.route(web::method(method.clone()).to(handler));
+        self.methods.push(method);
+
+        self
+    }
+
+    /// Register the resource with the HTTP Server.
+    ///
+    /// # Parameters
+    /// - `config` - The HTTP Server configuration to register the resource with.
+    pub fn register(self, config: &mut ServiceConfig) {
+        tracing::debug!(path = self.path, methods = ?self.methods, "Registering resource");
+
+        #[cfg(test)]
+        RECORDED.with(|recorded| {
+            if let Some(recorded) = recorded.borrow_mut().as_mut() {
+                recorded.extend(
+                    self.methods
+                        .iter()
+                        .map(|method| (method.clone(), self.path.to_owned())),
+                );
+            }
+        });
+
+        config.service(
+            self.resource
+                .default_service(method_not_allowed(&self.methods)),
+        );
+    }
+}
+
+/// Record the routes of every resource registered while running a function. Only used for testing.
+///
+/// # Parameters
+/// - `register` - The function that registers the resources
+///
+/// # Returns
+/// The HTTP Method and path pattern of every route that was registered.
+#[cfg(test)]
+pub fn record<F>(register: F) -> Vec<(Method, String)>
+where
+    F: FnOnce(),
+{
+    RECORDED.with(|recorded| *recorded.borrow_mut() = Some(vec![]));
+    register();
+    RECORDED
+        .with(|recorded| recorded.borrow_mut().take())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::header, test, App, HttpResponse};
+    use assert2::check;
+
+    async fn get_thing() -> HttpResponse {
+        HttpResponse::Ok().finish()
+    }
+
+    async fn delete_thing() -> HttpResponse {
+        HttpResponse::NoContent().finish()
+    }
+
+    fn register(config: &mut ServiceConfig) {
+        new("/things/{id}")
+            .with_route(Method::GET, get_thing)
+            .with_route(Method::DELETE, delete_thing)
+            .register(config);
+    }
+
+    #[actix_rt::test]
+    async fn routes() {
+        let mut app = test::init_service(App::new().configure(register)).await;
+
+        let req = test::TestRequest::get().uri("/things/1").to_request();
+        check!(test::call_service(&mut app, req).await.status() == 200);
+
+        let req = test::TestRequest::delete().uri("/things/1").to_request();
+        check!(test::call_service(&mut app, req).await.status() == 204);
+
+        let req = test::TestRequest::post().uri("/things/1").to_request();
+        let response = test::call_service(&mut app, req).await;
+        check!(response.status() == 405);
+        check!(response.headers().get(header::ALLOW).unwrap() == "GET, DELETE");
+    }
+
+    #[test]
+    fn record_routes() {
+        let recorded = record(|| {
+            App::new().configure(register);
+        });
+
+        check!(
+            recorded
+                == vec![
+                    (Method::GET, "/things/{id}".to_owned()),
+                    (Method::DELETE, "/things/{id}".to_owned())
+                ]
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod json_schema;
+mod registry;
+
+pub use json_schema::*;
+pub use registry::*;
@@ -0,0 +1,206 @@
+use chrono::{DateTime, TimeZone};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+pub use newlanding_service_derive::JsonSchema;
+
+/// Trait that types can implement to describe the shape that they are serialized as, using JSON Schema.
+///
+/// Structs would normally derive this, in which case every field must also implement it.
+pub trait JsonSchema {
+    /// Build the JSON Schema describing values of this type.
+    ///
+    /// # Returns
+    /// The JSON Schema.
+    fn json_schema() -> Value;
+
+    /// Whether a field of this type can be left out of the object that contains it.
+    ///
+    /// # Returns
+    /// True if the field isn't required.
+    fn is_optional() -> bool {
+        false
+    }
+}
+
+/// Implement `JsonSchema` for a set of types that all have the same simple schema.
+macro_rules! simple_schema {
+    ($schema:tt => $($ty:ty),+) => {
+        $(
+            impl JsonSchema for $ty {
+                fn json_schema() -> Value {
+                    json!($schema)
+                }
+            }
+        )+
+    };
+}
+
+simple_schema!({"type": "string"} => str, String, char);
+simple_schema!({"type": "boolean"} => bool);
+simple_schema!({"type": "integer"} => i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+simple_schema!({"type": "number"} => f32, f64);
+simple_schema!({} => Value);
+
+impl<T> JsonSchema for &T
+where
+    T: JsonSchema + ?Sized,
+{
+    fn json_schema() -> Value {
+        T::json_schema()
+    }
+
+    fn is_optional() -> bool {
+        T::is_optional()
+    }
+}
+
+impl<T> JsonSchema for Option<T>
+where
+    T: JsonSchema,
+{
+    fn json_schema() -> Value {
+        // A missing value is serialized as `null` unless the field is skipped, so the schema needs to allow for that.
+        let schema = T::json_schema();
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some(schema_type) => {
+                let mut schema: Map<String, Value> =
+                    schema.as_object().cloned().unwrap_or_default();
+                schema.insert("type".to_owned(), json!([schema_type, "null"]));
+                schema.into()
+            }
+            None => json!({"anyOf": [schema, {"type": "null"}]}),
+        }
+    }
+
+    fn is_optional() -> bool {
+        true
+    }
+}
+
+impl<T> JsonSchema for Vec<T>
+where
+    T: JsonSchema,
+{
+    fn json_schema() -> Value {
+        json!({"type": "array", "items": T::json_schema()})
+    }
+}
+
+impl<T> JsonSchema for [T]
+where
+    T: JsonSchema,
+{
+    fn json_schema() -> Value {
+        json!({"type": "array", "items": T::json_schema()})
+    }
+}
+
+impl<K, V> JsonSchema for BTreeMap<K, V>
+where
+    V: JsonSchema,
+{
+    fn json_schema() -> Value {
+        json!({"type": "object", "additionalProperties": V::json_schema()})
+    }
+}
+
+impl<K, V, S> JsonSchema for HashMap<K, V, S>
+where
+    V: JsonSchema,
+{
+    fn json_schema() -> Value {
+        json!({"type": "object", "additionalProperties": V::json_schema()})
+    }
+}
+
+impl<Tz> JsonSchema for DateTime<Tz>
+where
+    Tz: TimeZone,
+{
+    fn json_schema() -> Value {
+        json!({"type": "string", "format": "date-time"})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+    use serde::Serialize;
+    use test_case::test_case;
+
+    #[test_case(&String::json_schema(), &json!({"type": "string"}) ; "string")]
+    #[test_case(&<&str>::json_schema(), &json!({"type": "string"}) ; "string slice")]
+    #[test_case(&u16::json_schema(), &json!({"type": "integer"}) ; "integer")]
+    #[test_case(&f64::json_schema(), &json!({"type": "number"}) ; "number")]
+    #[test_case(&Vec::<bool>::json_schema(), &json!({"type": "array", "items": {"type": "boolean"}}) ; "array")]
+    #[test_case(&Option::<String>::json_schema(), &json!({"type": ["string", "null"]}) ; "optional")]
+    #[test_case(&Option::<Value>::json_schema(), &json!({"anyOf": [{}, {"type": "null"}]}) ; "optional without type")]
+    #[test_case(
+        &BTreeMap::<String, i32>::json_schema(),
+        &json!({"type": "object", "additionalProperties": {"type": "integer"}})
+        ; "map"
+    )]
+    fn standard_schemas(schema: &Value, expected: &Value) {
+        check!(schema == expected);
+    }
+
+    #[test]
+    fn derive_json_schema() {
+        /// An address.
+        #[derive(Serialize, JsonSchema)]
+        struct Address {
+            city: String,
+        }
+
+        /// A person.
+        /// With a second line.
+        #[derive(Serialize, JsonSchema)]
+        #[serde(rename_all = "camelCase")]
+        #[allow(dead_code)]
+        struct Person {
+            /// The name of the person.
+            display_name: String,
+            #[schema(format = "email")]
+            email: String,
+            #[serde(rename = "years")]
+            age: u8,
+            nickname: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            title: Option<String>,
+            #[serde(skip_serializing_if = "Vec::is_empty")]
+            addresses: Vec<Address>,
+            #[serde(skip)]
+            password: String,
+        }
+
+        check!(
+            Person::json_schema()
+                == json!({
+                    "type": "object",
+                    "description": "A person. With a second line.",
+                    "properties": {
+                        "displayName": {"type": "string", "description": "The name of the person."},
+                        "email": {"type": "string", "format": "email"},
+                        "years": {"type": "integer"},
+                        "nickname": {"type": ["string", "null"]},
+                        "title": {"type": "string"},
+                        "addresses": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "description": "An address.",
+                                "properties": {
+                                    "city": {"type": "string"}
+                                },
+                                "required": ["city"]
+                            }
+                        }
+                    },
+                    "required": ["displayName", "email", "years"]
+                })
+        );
+    }
+}
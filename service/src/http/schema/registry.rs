@@ -0,0 +1,132 @@
+use super::JsonSchema;
+use crate::http::negotiation::{Format, MediaType, APPLICATION_JSON};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// The prefix of the URLs that schemas are served from.
+pub const SCHEMA_PATH_PREFIX: &str = "/schemas/";
+
+/// The version of JSON Schema that the schemas conform to.
+const JSON_SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Media type for JSON Schema documents.
+pub const APPLICATION_SCHEMA_JSON: MediaType = MediaType {
+    name: "application/schema+json",
+    format: Format::Json,
+};
+
+/// The media types that schemas can be produced as, in order of preference.
+pub const SCHEMA_MEDIA_TYPES: &[MediaType] = &[APPLICATION_SCHEMA_JSON, APPLICATION_JSON];
+
+/// Build the URL that a schema is served from, for use as a `profile` of the documents that it describes.
+///
+/// # Parameters
+/// - `name` - The name of the schema
+///
+/// # Returns
+/// The URL of the schema.
+pub fn schema_path(name: &str) -> String {
+    format!("{}{}", SCHEMA_PATH_PREFIX, name)
+}
+
+/// A named JSON Schema describing one of the document types that the service returns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    /// The name of the schema, as used in its URL.
+    pub name: String,
+    /// The schema itself, identified by its URL.
+    pub schema: Value,
+}
+
+impl Schema {
+    /// Create the schema describing the provided type.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the schema, as used in its URL
+    ///
+    /// # Types
+    /// - `T` - The type to describe
+    pub fn of<T>(name: &str) -> Self
+    where
+        T: JsonSchema,
+    {
+        let mut schema = Map::new();
+        schema.insert("$schema".to_owned(), JSON_SCHEMA_DIALECT.into());
+        schema.insert("$id".to_owned(), schema_path(name).into());
+        if let Value::Object(definition) = T::json_schema() {
+            schema.extend(definition);
+        }
+
+        Self {
+            name: name.to_owned(),
+            schema: schema.into(),
+        }
+    }
+}
+
+/// Registry of all the schemas that the service publishes.
+#[derive(Debug, Default)]
+pub struct SchemaRegistry {
+    /// The schemas, keyed by their name.
+    schemas: BTreeMap<String, Value>,
+}
+
+impl SchemaRegistry {
+    /// Register a schema.
+    ///
+    /// # Parameters
+    /// - `schema` - The schema
+    pub fn with_schema(mut self, schema: Schema) -> Self {
+        if self.schemas.contains_key(&schema.name) {
+            tracing::warn!(name = ?schema.name, "Replacing duplicate schema");
+        }
+        self.schemas.insert(schema.name, schema.schema);
+
+        self
+    }
+
+    /// Get a single schema.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the schema
+    ///
+    /// # Returns
+    /// The schema, if it is known.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.schemas.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use serde_json::json;
+
+    #[test]
+    fn schema_is_identified() {
+        let schema = Schema::of::<Vec<String>>("names");
+
+        check!(schema.name == "names");
+        check!(
+            schema.schema
+                == json!({
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "$id": "/schemas/names",
+                    "type": "array",
+                    "items": {"type": "string"}
+                })
+        );
+    }
+
+    #[test]
+    fn get_registered_schema() {
+        let registry = SchemaRegistry::default()
+            .with_schema(Schema::of::<String>("name"))
+            .with_schema(Schema::of::<bool>("flag"));
+
+        let_assert!(Some(schema) = registry.get("flag"));
+        check!(schema["type"] == "boolean");
+        check!(registry.get("unknown").is_none());
+    }
+}
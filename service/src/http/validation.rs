@@ -0,0 +1,339 @@
+use crate::http::problem::{payload_error, Problem, UNSUPPORTED_MEDIA_TYPE, VALIDATION_FAILED};
+use actix_http::{http::header, Payload};
+use actix_web::{web::Bytes, FromRequest, HttpRequest};
+use futures::future::Future;
+use serde::{de::DeserializeOwned, Serialize};
+use std::pin::Pin;
+
+/// The name of the problem extension member that lists the invalid parameters.
+const INVALID_PARAMS: &str = "invalid-params";
+
+/// Details of a single invalid parameter in a request.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct InvalidParam {
+    /// The JSON Pointer to the invalid value in the request body, or the name of the invalid parameter.
+    pub name: String,
+    /// A machine-readable code for why the value is invalid.
+    pub code: String,
+    /// A human-readable message explaining why the value is invalid.
+    pub reason: String,
+}
+
+impl Problem {
+    /// Set the list of invalid parameters on the Problem instance
+    ///
+    /// # Parameters
+    /// - `invalid_params` - The invalid parameters
+    pub fn with_invalid_params(self, invalid_params: Vec<InvalidParam>) -> Self {
+        self.with_extra(INVALID_PARAMS, invalid_params)
+    }
+}
+
+/// Trait that request models can implement to check that their values are valid.
+pub trait Validate {
+    /// Validate this value, recording any problems with the provided validator.
+    ///
+    /// # Parameters
+    /// - `validator` - The validator to record problems with
+    fn validate(&self, validator: &mut Validator);
+}
+
+/// Collector of the problems found when validating a value.
+#[derive(Debug, Default)]
+pub struct Validator {
+    /// The JSON Pointer to the value currently being validated.
+    pointer: String,
+    /// The problems found so far.
+    invalid_params: Vec<InvalidParam>,
+}
+
+impl Validator {
+    /// Record that a field of the current value is invalid.
+    ///
+    /// # Parameters
+    /// - `field` - The name of the field that is invalid
+    /// - `code` - A machine-readable code for why the field is invalid
+    /// - `reason` - A human-readable message explaining why the field is invalid
+    pub fn invalid<C, R>(&mut self, field: &str, code: C, reason: R)
+    where
+        C: Into<String>,
+        R: Into<String>,
+    {
+        self.invalid_params.push(InvalidParam {
+            name: self.field_pointer(field),
+            code: code.into(),
+            reason: reason.into(),
+        });
+    }
+
+    /// Record that a field of the current value is invalid, unless the provided condition holds.
+    ///
+    /// # Parameters
+    /// - `valid` - Whether the field is valid
+    /// - `field` - The name of the field being checked
+    /// - `code` - A machine-readable code for why the field is invalid
+    /// - `reason` - A human-readable message explaining why the field is invalid
+    pub fn check<C, R>(&mut self, valid: bool, field: &str, code: C, reason: R)
+    where
+        C: Into<String>,
+        R: Into<String>,
+    {
+        if !valid {
+            self.invalid(field, code, reason);
+        }
+    }
+
+    /// Check that a string field has a value that isn't blank.
+    ///
+    /// # Parameters
+    /// - `field` - The name of the field being checked
+    /// - `value` - The value of the field
+    pub fn required(&mut self, field: &str, value: &str) {
+        self.check(
+            !value.trim().is_empty(),
+            field,
+            "required",
+            "A value is required",
+        );
+    }
+
+    /// Validate a nested value that is a field of the current value.
+    ///
+    /// # Parameters
+    /// - `field` - The name of the field holding the nested value
+    /// - `value` - The nested value
+    pub fn nested<T>(&mut self, field: &str, value: &T)
+    where
+        T: Validate,
+    {
+        let pointer = self.field_pointer(field);
+        let parent = std::mem::replace(&mut self.pointer, pointer);
+        value.validate(self);
+        self.pointer = parent;
+    }
+
+    /// Build the JSON Pointer to a field of the current value.
+    ///
+    /// # Parameters
+    /// - `field` - The name of the field
+    ///
+    /// # Returns
+    /// The JSON Pointer, escaped as per RFC 6901
+    fn field_pointer(&self, field: &str) -> String {
+        format!(
+            "{}/{}",
+            self.pointer,
+            field.replace('~', "~0").replace('/', "~1")
+        )
+    }
+}
+
+impl From<Validator> for Result<(), Problem> {
+    fn from(validator: Validator) -> Self {
+        if validator.invalid_params.is_empty() {
+            Ok(())
+        } else {
+            tracing::debug!(invalid_params = ?validator.invalid_params, "Validation failed");
+            Err(Problem::from(VALIDATION_FAILED).with_invalid_params(validator.invalid_params))
+        }
+    }
+}
+
+/// Validate a value, producing a Problem describing everything that is wrong with it.
+///
+/// # Parameters
+/// - `value` - The value to validate
+///
+/// # Returns
+/// A `422 Unprocessable Entity` Problem listing the invalid parameters, if there were any.
+pub fn validate<T>(value: &T) -> Result<(), Problem>
+where
+    T: Validate,
+{
+    let mut validator = Validator::default();
+    value.validate(&mut validator);
+    validator.into()
+}
+
+/// Build a Problem indicating that a JSON request body couldn't be parsed into the expected shape.
+///
+/// # Parameters
+/// - `e` - The error from parsing the request body
+///
+/// # Returns
+/// The problem
+pub fn malformed(e: &serde_json::Error) -> Problem {
+    tracing::warn!(e = ?e, "Failed to parse request body");
+    Problem::from(VALIDATION_FAILED)
+        .with_invalid_params(vec![InvalidParam {
+            name: String::new(),
+            code: "malformed".to_owned(),
+            reason: e.to_string(),
+        }])
+        .with_source(e)
+}
+
+/// Extractor for a JSON request body that has been checked to be valid.
+///
+/// # Types
+/// - `T` - The type of the request body
+#[derive(Debug)]
+pub struct Validated<T>(pub T);
+
+impl<T> Validated<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    /// Parse and validate a JSON request body.
+    ///
+    /// # Parameters
+    /// - `body` - The request body
+    ///
+    /// # Returns
+    /// The valid request body, or a Problem indicating why it was invalid.
+    pub fn parse(body: &[u8]) -> Result<Self, Problem> {
+        let value: T = serde_json::from_slice(body).map_err(|e| malformed(&e))?;
+
+        validate(&value)?;
+
+        Ok(Self(value))
+    }
+}
+
+impl<T> FromRequest for Validated<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = Problem;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+    type Config = ();
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let is_json = content_type == "application/json" || content_type.ends_with("+json");
+
+        let body = Bytes::from_request(req, payload);
+
+        Box::pin(async move {
+            if !is_json {
+                return Err(Problem::from(UNSUPPORTED_MEDIA_TYPE)
+                    .with_detail_message("details.jsonRequired")
+                    .with_extra("supported", vec!["application/json"]));
+            }
+
+            let body = body.await.map_err(|e| payload_error(&e))?;
+
+            Self::parse(&body)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Address {
+        city: String,
+    }
+
+    impl Validate for Address {
+        fn validate(&self, validator: &mut Validator) {
+            validator.required("city", &self.city);
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Person {
+        display_name: String,
+        age: u32,
+        #[serde(rename = "home/address")]
+        home_address: Address,
+    }
+
+    impl Validate for Person {
+        fn validate(&self, validator: &mut Validator) {
+            validator.required("displayName", &self.display_name);
+            validator.check(self.age >= 18, "age", "too_small", "Must be at least 18");
+            validator.nested("home/address", &self.home_address);
+        }
+    }
+
+    fn invalid_params(problem: &Problem) -> Option<&serde_json::Value> {
+        problem.extra.get(INVALID_PARAMS)
+    }
+
+    #[test]
+    fn valid() {
+        let_assert!(
+            Ok(Validated(person)) = Validated::<Person>::parse(
+                br#"{"displayName": "Graham", "age": 42, "home/address": {"city": "London"}}"#
+            )
+        );
+        check!(person.display_name == "Graham");
+    }
+
+    #[test]
+    fn invalid() {
+        let_assert!(
+            Err(problem) = Validated::<Person>::parse(
+                br#"{"displayName": " ", "age": 12, "home/address": {"city": ""}}"#
+            )
+        );
+
+        check!(problem.status == 422);
+        check!(problem.error.problem_type() == VALIDATION_FAILED.problem_type);
+        check!(
+            invalid_params(&problem)
+                == Some(&json!([
+                    {"name": "/displayName", "code": "required", "reason": "A value is required"},
+                    {"name": "/age", "code": "too_small", "reason": "Must be at least 18"},
+                    {"name": "/home~1address/city", "code": "required", "reason": "A value is required"}
+                ]))
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let_assert!(Err(problem) = Validated::<Person>::parse(br#"{"displayName": "Graham"}"#));
+
+        check!(problem.status == 422);
+        let_assert!(Some(serde_json::Value::Array(params)) = invalid_params(&problem));
+        check!(params.len() == 1);
+        check!(params[0]["name"] == "");
+        check!(params[0]["code"] == "malformed");
+    }
+
+    #[actix_rt::test]
+    async fn extract_too_large() {
+        let (req, mut payload) = actix_web::test::TestRequest::default()
+            .header(header::CONTENT_TYPE, "application/json")
+            .set_payload(vec![b' '; 300_000])
+            .to_http_parts();
+
+        let_assert!(Err(problem) = Validated::<Person>::from_request(&req, &mut payload).await);
+        check!(problem.status == 413);
+    }
+
+    #[actix_rt::test]
+    async fn extract_wrong_content_type() {
+        let (req, mut payload) = actix_web::test::TestRequest::default()
+            .header(header::CONTENT_TYPE, "text/plain")
+            .set_payload(r#"{"displayName": "Graham"}"#)
+            .to_http_parts();
+
+        let_assert!(Err(problem) = Validated::<Person>::from_request(&req, &mut payload).await);
+        check!(problem.status == 415);
+    }
+}
@@ -1,2 +1,7 @@
+mod health;
 mod home;
+mod openapi;
+mod problems;
+mod schemas;
 mod service;
+mod users;
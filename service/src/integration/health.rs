@@ -0,0 +1,41 @@
+use super::service::TestService;
+use actix_web::test::TestRequest;
+use assert2::check;
+use insta::assert_json_snapshot;
+
+#[actix_rt::test]
+pub async fn test_liveness() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/health/live").to_request())
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "application/json");
+    check!(response.headers.get("cache-control").unwrap() == "no-store");
+
+    assert_json_snapshot!(response.to_json().unwrap(), @r###"
+    {
+      "status": "UP"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_unhealthy_problem_is_documented() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/problems/service_unhealthy")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 200);
+
+    let body = response.to_json().unwrap();
+    check!(body["statusCodes"] == serde_json::json!([503]));
+}
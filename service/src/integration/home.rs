@@ -13,14 +13,32 @@ pub async fn test_home_document() {
 
     check!(response.status == 200);
 
-    check!(response.headers.get("content-type").unwrap() == "application/hal+json");
+    check!(
+        response.headers.get("content-type").unwrap()
+            == r#"application/hal+json; profile="/schemas/home""#
+    );
     check!(response.headers.get("cache-control").unwrap() == "public, max-age=3600");
+    check!(response.headers.get("link").unwrap() == r#"</>; rel="self""#);
 
     assert_json_snapshot!(response.to_json().unwrap(), @r###"
     {
       "name": "newlanding_service",
       "version": "0.1.0",
       "_links": {
+        "curies": [
+          {
+            "href": "/rels/nl:{rel}",
+            "templated": true,
+            "name": "nl"
+          }
+        ],
+        "nl:user": {
+          "href": "/users/{id}",
+          "templated": true
+        },
+        "profile": {
+          "href": "/schemas/home"
+        },
         "self": {
           "href": "/"
         }
@@ -28,3 +46,260 @@ pub async fn test_home_document() {
     }
     "###);
 }
+
+#[actix_rt::test]
+pub async fn test_relation_document() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/rels/nl:user").to_request())
+        .await;
+
+    check!(response.status == 200);
+
+    check!(response.headers.get("content-type").unwrap() == "application/hal+json");
+
+    assert_json_snapshot!(response.to_json().unwrap(), @r###"
+    {
+      "name": "nl:user",
+      "description": "A single user, identified by their ID",
+      "_links": {
+        "curies": [
+          {
+            "href": "/rels/nl:{rel}",
+            "templated": true,
+            "name": "nl"
+          }
+        ],
+        "self": {
+          "href": "/rels/nl:user"
+        }
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_unknown_relation_document() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/rels/nl:unknown").to_request())
+        .await;
+
+    check!(response.status == 404);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+}
+
+#[actix_rt::test]
+pub async fn test_home_document_as_cbor() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/")
+                .header("accept", "application/cbor")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "application/cbor");
+    check!(response.headers.get("vary").unwrap() == "Accept");
+
+    let body: serde_json::Value = serde_cbor::from_slice(&response.body).unwrap();
+    check!(body["name"] == "newlanding_service");
+}
+
+#[actix_rt::test]
+pub async fn test_home_document_not_acceptable() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/")
+                .header("accept", "text/html")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 406);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+
+    assert_json_snapshot!(response.to_json().unwrap(), { ".requestId" => "[request-id]" }, @r###"
+    {
+      "type": "about:blank",
+      "title": "Not Acceptable",
+      "status": 406,
+      "acceptable": [
+        "application/hal+json",
+        "application/prs.hal-forms+json",
+        "application/json",
+        "application/cbor",
+        "application/msgpack",
+        "application/x-msgpack"
+      ],
+      "requestId": "[request-id]"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_unknown_relation_document_as_xml() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/rels/nl:unknown")
+                .header("accept", "application/problem+xml")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 404);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+xml");
+    check!(std::str::from_utf8(&response.body)
+        .unwrap()
+        .contains("<status>404</status>"));
+}
+
+#[actix_rt::test]
+pub async fn test_unknown_route() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/unknown").to_request())
+        .await;
+
+    check!(response.status == 404);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+
+    assert_json_snapshot!(response.to_json().unwrap(), { ".requestId" => "[request-id]" }, @r###"
+    {
+      "type": "about:blank",
+      "title": "The requested resource was not found",
+      "status": 404,
+      "requestId": "[request-id]"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_home_document_wrong_method() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::post().uri("/").to_request())
+        .await;
+
+    check!(response.status == 405);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+    check!(response.headers.get("allow").unwrap() == "GET");
+
+    assert_json_snapshot!(response.to_json().unwrap(), { ".requestId" => "[request-id]" }, @r###"
+    {
+      "type": "about:blank",
+      "title": "Method Not Allowed",
+      "status": 405,
+      "allowed": [
+        "GET"
+      ],
+      "requestId": "[request-id]"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_unknown_relation_document_in_french() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/rels/nl:unknown")
+                .header("accept-language", "fr-FR, en;q=0.5")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 404);
+    check!(response.headers.get("content-language").unwrap() == "fr");
+
+    assert_json_snapshot!(response.to_json().unwrap(), { ".requestId" => "[request-id]" }, @r###"
+    {
+      "type": "about:blank",
+      "title": "La ressource demandée est introuvable",
+      "status": 404,
+      "requestId": "[request-id]"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_correlation_headers() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/rels/nl:unknown")
+                .header("x-request-id", "abc-123")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 404);
+    check!(response.headers.get("x-request-id").unwrap() == "abc-123");
+
+    let body = response.to_json().unwrap();
+    check!(body["requestId"] == "abc-123");
+}
+
+#[actix_rt::test]
+pub async fn test_home_document_with_fields() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/?fields=name").to_request())
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "application/hal+json");
+
+    let body = response.to_json().unwrap();
+    check!(body["name"] == "newlanding_service");
+    check!(body.get("version").is_none());
+    check!(body["_links"]["self"]["href"] == "/");
+    check!(body["_links"].get("profile").is_none());
+}
+
+#[actix_rt::test]
+pub async fn test_home_document_with_unknown_fields() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/?fields=name,colour").to_request())
+        .await;
+
+    check!(response.status == 422);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+
+    assert_json_snapshot!(response.to_json().unwrap(), { ".requestId" => "[request-id]" }, @r###"
+    {
+      "type": "/problems/validation_failed",
+      "title": "The request contained invalid values",
+      "status": 422,
+      "invalid-params": [
+        {
+          "name": "fields",
+          "code": "unknown_field",
+          "reason": "The field 'colour' is not known"
+        }
+      ],
+      "requestId": "[request-id]"
+    }
+    "###);
+}
@@ -0,0 +1,107 @@
+use super::service::TestService;
+use actix_web::test::TestRequest;
+use assert2::check;
+use insta::assert_json_snapshot;
+
+#[actix_rt::test]
+pub async fn test_openapi_document() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/openapi.json").to_request())
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "application/json");
+
+    let body = response.to_json().unwrap();
+    check!(body["openapi"] == "3.0.3");
+    check!(body["info"]["title"] == "newlanding_service");
+
+    let operations: serde_json::Map<String, serde_json::Value> = body["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .map(|(path, methods)| {
+            let ids: serde_json::Map<String, serde_json::Value> = methods
+                .as_object()
+                .unwrap()
+                .iter()
+                .map(|(method, operation)| (method.clone(), operation["operationId"].clone()))
+                .collect();
+            (path.clone(), ids.into())
+        })
+        .collect();
+
+    assert_json_snapshot!(operations, @r###"
+    {
+      "/": {
+        "get": "getHome"
+      },
+      "/health/live": {
+        "get": "getLiveness"
+      },
+      "/health/ready": {
+        "get": "getReadiness"
+      },
+      "/openapi.json": {
+        "get": "getOpenApi"
+      },
+      "/problems": {
+        "get": "listProblems"
+      },
+      "/problems/{name}": {
+        "get": "getProblem"
+      },
+      "/rels/{rel}": {
+        "get": "getRelation"
+      },
+      "/schemas/{name}": {
+        "get": "getSchema"
+      },
+      "/users/{userId}": {
+        "get": "getUser",
+        "patch": "patchUser",
+        "put": "replaceUser"
+      }
+    }
+    "###);
+
+    let get_user = &body["paths"]["/users/{userId}"]["get"];
+    check!(get_user["parameters"][0]["name"] == "userId");
+    check!(get_user["responses"]["404"]["x-problem-types"][0] == "about:blank");
+    check!(get_user["security"] == serde_json::json!([{"bearerAuth": []}, {}]));
+    check!(
+        get_user["responses"]["200"]["content"]["application/hal+json"]["schema"]["properties"]
+            ["email"]["format"]
+            == "email"
+    );
+}
+
+#[actix_rt::test]
+pub async fn test_every_route_is_documented() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/openapi.json").to_request())
+        .await;
+    let body = response.to_json().unwrap();
+    let mut documented: Vec<(String, String)> = body["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, methods)| {
+            methods
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(move |method| (method.to_uppercase(), path.clone()))
+        })
+        .collect();
+    documented.sort();
+
+    let mut registered = test_service.registered_routes();
+    registered.sort();
+
+    check!(registered == documented);
+}
@@ -0,0 +1,168 @@
+use super::service::TestService;
+use actix_web::test::TestRequest;
+use assert2::check;
+use insta::assert_json_snapshot;
+
+#[actix_rt::test]
+pub async fn test_problem_document() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/problems/invalid_paging")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "application/hal+json");
+    check!(response.headers.get("cache-control").unwrap() == "public, max-age=3600");
+
+    assert_json_snapshot!(response.to_json().unwrap(), @r###"
+    {
+      "type": "/problems/invalid_paging",
+      "title": "The paging parameters were invalid",
+      "description": "The paging parameters in the query string of a request for a collection were invalid.",
+      "statusCodes": [
+        400
+      ],
+      "extensions": {
+        "parameter": "The name of the query string parameter that was invalid."
+      },
+      "_links": {
+        "collection": {
+          "href": "/problems"
+        },
+        "curies": [
+          {
+            "href": "/rels/nl:{rel}",
+            "templated": true,
+            "name": "nl"
+          }
+        ],
+        "self": {
+          "href": "/problems/invalid_paging"
+        }
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_problem_document_as_html() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/problems/invalid_paging")
+                .header("accept", "text/html")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "text/html; charset=utf-8");
+    check!(std::str::from_utf8(&response.body)
+        .unwrap()
+        .contains("<h1>The paging parameters were invalid</h1>"));
+}
+
+#[actix_rt::test]
+pub async fn test_unknown_problem_document() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/problems/unknown").to_request())
+        .await;
+
+    check!(response.status == 404);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+}
+
+#[actix_rt::test]
+pub async fn test_problems_document() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/problems").to_request())
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "application/hal+json");
+
+    let body = response.to_json().unwrap();
+    check!(body["total"] == 6);
+    check!(body["offset"] == 0);
+    check!(body["_links"]["self"]["href"] == "/problems?offset=0&pageSize=10");
+    check!(body["_links"]["next"].is_null());
+    check!(body["_embedded"]["item"][0]["_links"]["self"]["href"] == "/problems/invalid_paging");
+}
+
+#[actix_rt::test]
+pub async fn test_problems_document_page() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/problems?offset=2&pageSize=2")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 200);
+
+    let body = response.to_json().unwrap();
+    check!(body["total"] == 6);
+    check!(body["_embedded"]["item"].as_array().unwrap().len() == 2);
+    check!(body["_links"]["prev"]["href"] == "/problems?offset=0&pageSize=2");
+    check!(body["_links"]["next"]["href"] == "/problems?offset=4&pageSize=2");
+    check!(body["_links"]["last"]["href"] == "/problems?offset=4&pageSize=2");
+}
+
+#[actix_rt::test]
+pub async fn test_problems_document_invalid_page() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/problems?cursor=abc").to_request())
+        .await;
+
+    check!(response.status == 400);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+
+    let body = response.to_json().unwrap();
+    check!(body["type"] == "/problems/invalid_paging");
+    check!(body["parameter"] == "cursor");
+}
+
+#[actix_rt::test]
+pub async fn test_problems_document_fields() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::get()
+                .uri("/problems?pageSize=2&fields=title")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 200);
+
+    let body = response.to_json().unwrap();
+    check!(body["total"] == 6);
+    check!(
+        body["_embedded"]["item"][0]
+            == serde_json::json!({
+                "title": "The paging parameters were invalid",
+                "_links": {
+                    "self": {
+                        "href": "/problems/invalid_paging"
+                    }
+                }
+            })
+    );
+}
@@ -0,0 +1,84 @@
+use super::service::TestService;
+use actix_web::test::TestRequest;
+use assert2::check;
+use insta::assert_json_snapshot;
+
+#[actix_rt::test]
+pub async fn test_user_schema() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/schemas/user").to_request())
+        .await;
+
+    check!(response.status == 200);
+    check!(response.headers.get("content-type").unwrap() == "application/schema+json");
+    check!(response.headers.get("cache-control").unwrap() == "public, max-age=3600");
+
+    assert_json_snapshot!(response.to_json().unwrap(), @r###"
+    {
+      "$schema": "http://json-schema.org/draft-07/schema#",
+      "$id": "/schemas/user",
+      "type": "object",
+      "description": "Representation of a User on the HTTP API.",
+      "properties": {
+        "displayName": {
+          "type": "string",
+          "description": "The name to display for the user."
+        },
+        "email": {
+          "type": "string",
+          "format": "email",
+          "description": "The email address of the user."
+        },
+        "emailVerified": {
+          "type": "boolean",
+          "description": "Whether or not the email address is verified."
+        },
+        "socialProvider": {
+          "type": "string",
+          "description": "If the user is registered with a social provider then which one."
+        }
+      },
+      "required": [
+        "displayName",
+        "email",
+        "emailVerified"
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+pub async fn test_home_schema_is_the_profile() {
+    let test_service = TestService::new().await;
+
+    let home = test_service
+        .inject(TestRequest::get().uri("/").to_request())
+        .await
+        .to_json()
+        .unwrap();
+    let profile = home["_links"]["profile"]["href"].as_str().unwrap();
+
+    let response = test_service
+        .inject(TestRequest::get().uri(profile).to_request())
+        .await;
+
+    check!(response.status == 200);
+
+    let schema = response.to_json().unwrap();
+    check!(schema["$id"] == profile);
+    check!(schema["required"] == serde_json::json!(["name", "version"]));
+}
+
+#[actix_rt::test]
+pub async fn test_unknown_schema() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(TestRequest::get().uri("/schemas/unknown").to_request())
+        .await;
+
+    check!(response.status == 404);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+}
@@ -16,13 +16,39 @@ impl TestService {
             auth0_audience: "testAudience".to_owned(),
             auth0_client_id: "testAuth0ClientId".to_owned(),
             auth0_client_secret: "testAuth0ClientSecret".to_owned(),
+            development_mode: false,
+            shutdown_grace_period: 0,
+            cors_allowed_origins: "https://*.example.com".to_owned(),
+            cors_allowed_methods: "GET,HEAD,OPTIONS".to_owned(),
+            cors_allowed_headers: "Accept,Authorization,Content-Type".to_owned(),
+            cors_exposed_headers: String::new(),
+            cors_allow_credentials: false,
+            cors_max_age: None,
+            cors_permissive: false,
+            tls_certificate_path: None,
+            tls_private_key_path: None,
+            tls_min_protocol_version: "1.2".to_owned(),
+            tls_reload_interval: 0,
+            http_redirect_port: None,
+            https_public_host: None,
+            hsts_max_age: None,
+            rate_limits: "/=1000/60".to_owned(),
+            trusted_proxies: String::new(),
         };
 
-        let service = Service::new(cfg).await;
+        let service = Service::new(cfg).await.unwrap();
         Self { service }
     }
 
     pub async fn inject(&self, req: Request) -> TestResponse {
         self.service.inject(req).await
     }
+
+    pub fn registered_routes(&self) -> Vec<(String, String)> {
+        self.service
+            .registered_routes()
+            .into_iter()
+            .map(|(method, path)| (method.as_str().to_owned(), path))
+            .collect()
+    }
 }
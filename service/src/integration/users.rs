@@ -0,0 +1,55 @@
+use super::service::TestService;
+use actix_web::test::TestRequest;
+use assert2::check;
+
+#[actix_rt::test]
+pub async fn test_patch_user_unauthorized() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::patch()
+                .uri("/users/auth0%7C6044f85d48fea20070575672")
+                .header("content-type", "application/merge-patch+json")
+                .set_payload(r#"{"displayName": "New Name"}"#)
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 401);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+}
+
+#[actix_rt::test]
+pub async fn test_put_user_unauthorized() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::put()
+                .uri("/users/auth0%7C6044f85d48fea20070575672")
+                .header("if-match", "\"MjAyMS0wMy0wNyAxNjo1NDozMC44MjYgVVRD\"")
+                .set_json(&serde_json::json!({"displayName": "New Name"}))
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 401);
+    check!(response.headers.get("content-type").unwrap() == "application/problem+json");
+}
+
+#[actix_rt::test]
+pub async fn test_user_allowed_methods() {
+    let test_service = TestService::new().await;
+
+    let response = test_service
+        .inject(
+            TestRequest::delete()
+                .uri("/users/auth0%7C6044f85d48fea20070575672")
+                .to_request(),
+        )
+        .await;
+
+    check!(response.status == 405);
+    check!(response.headers.get("allow").unwrap() == "GET, PATCH, PUT");
+}
@@ -2,15 +2,22 @@
 #![allow(clippy::module_name_repetitions)]
 
 mod authorization;
+mod health;
 mod home;
 mod http;
 #[cfg(test)]
 mod integration;
 mod model;
+mod openapi;
+mod problems;
+mod schemas;
 mod server;
 mod service;
 mod settings;
 mod users;
 
+pub use server::{cors::CorsPolicyError, rate_limit::RateLimitError, tls::TlsError, ServerHandle};
 pub use service::Service;
-pub use settings::Settings;
+pub use settings::{
+    Settings, SettingsError, DEFAULT_CORS_ALLOWED_HEADERS, DEFAULT_CORS_ALLOWED_METHODS,
+};
@@ -14,7 +14,7 @@ async fn main() {
     env_logger::init();
 
     opentelemetry::global::set_text_map_propagator(opentelemetry_jaeger::Propagator::new());
-    let (tracer, _uninstall) = opentelemetry_jaeger::new_pipeline()
+    let (tracer, uninstall) = opentelemetry_jaeger::new_pipeline()
         .with_service_name(env!("CARGO_PKG_NAME"))
         .from_env()
         .install()
@@ -23,8 +23,44 @@ async fn main() {
     let subscriber = Registry::default().with(telemetry);
     tracing::subscriber::set_global_default(subscriber).unwrap();
 
-    let service = newlanding_service_lib::Service::new(load_settings()).await;
-    service.start().await;
+    let service = newlanding_service_lib::Service::new(load_settings())
+        .await
+        .expect("Invalid settings");
+    let server = service.start().expect("Failed to start HTTP server");
+
+    let stopper = server.clone();
+    actix_rt::spawn(async move {
+        shutdown_signal().await;
+        stopper.stop(true).await;
+    });
+
+    server.wait().await;
+
+    // Uninstalling the pipeline shuts down the tracer provider, which exports any spans that are still buffered.
+    tracing::debug!("Flushing traces");
+    drop(uninstall);
+}
+
+/// Wait for the process to be asked to shut down, either by SIGTERM or by SIGINT.
+#[cfg(unix)]
+async fn shutdown_signal() {
+    use actix_rt::signal::unix::{signal, SignalKind};
+    use futures::future::{select, FutureExt};
+
+    let mut terminate = signal(SignalKind::terminate()).expect("Failed to listen for SIGTERM");
+    let interrupt = actix_rt::signal::ctrl_c();
+
+    select(terminate.recv().boxed_local(), interrupt.boxed_local()).await;
+    tracing::info!("Received shutdown signal");
+}
+
+/// Wait for the process to be asked to shut down by Ctrl-C.
+#[cfg(not(unix))]
+async fn shutdown_signal() {
+    if let Err(e) = actix_rt::signal::ctrl_c().await {
+        tracing::error!(e = ?e, "Failed to listen for Ctrl-C");
+    }
+    tracing::info!("Received shutdown signal");
 }
 
 /// Load the application settings from the environment.
@@ -35,6 +71,24 @@ fn load_settings() -> newlanding_service_lib::Settings {
     let mut s = Config::new();
     s.set_default("port", 8000)
         .expect("Failed to set default value for 'port'");
+    s.set_default("shutdown_grace_period", 30)
+        .expect("Failed to set default value for 'shutdown_grace_period'");
+    s.set_default(
+        "cors_allowed_methods",
+        newlanding_service_lib::DEFAULT_CORS_ALLOWED_METHODS,
+    )
+    .expect("Failed to set default value for 'cors_allowed_methods'");
+    s.set_default(
+        "cors_allowed_headers",
+        newlanding_service_lib::DEFAULT_CORS_ALLOWED_HEADERS,
+    )
+    .expect("Failed to set default value for 'cors_allowed_headers'");
+    s.set_default("tls_min_protocol_version", "1.2")
+        .expect("Failed to set default value for 'tls_min_protocol_version'");
+    s.set_default("tls_reload_interval", 60)
+        .expect("Failed to set default value for 'tls_reload_interval'");
+    s.set_default("rate_limits", "/=120/60,/users=30/60")
+        .expect("Failed to set default value for 'rate_limits'");
 
     s.merge(Environment::default())
         .expect("Failed to load environment properties");
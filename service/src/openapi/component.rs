@@ -0,0 +1,62 @@
+use crate::{
+    http::openapi::{OpenApi, Operation},
+    server::RouteConfigurer,
+};
+use actix_web::web::ServiceConfig;
+use std::sync::Arc;
+
+/// Component for the `OpenAPI` document describing the entire service.
+pub struct Component {
+    document: Arc<OpenApi>,
+}
+
+/// Builder for building the `OpenAPI` component.
+#[derive(Default)]
+pub struct Builder {
+    routes: Vec<Arc<dyn RouteConfigurer>>,
+}
+
+/// Create a new instance of the `OpenAPI` builder.
+pub fn new() -> Builder {
+    Builder::default()
+}
+
+impl Builder {
+    /// Register a `RouteConfigurer` whose operations should be described in the `OpenAPI` document.
+    ///
+    /// # Parameters
+    /// - `routes` - The configurer for the routes to describe
+    pub fn with_routes(mut self, routes: Arc<dyn RouteConfigurer>) -> Self {
+        self.routes.push(routes);
+
+        self
+    }
+
+    /// Build the actual `OpenAPI` component, merging the operations of every registered `RouteConfigurer` along with
+    /// its own.
+    pub fn build(self) -> Arc<Component> {
+        let document = self
+            .routes
+            .iter()
+            .fold(
+                OpenApi::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+                |document, routes| document.with_operations(routes.describe_operations()),
+            )
+            .with_operations(super::http::operations());
+
+        Arc::new(Component {
+            document: Arc::new(document),
+        })
+    }
+}
+
+impl RouteConfigurer for Component {
+    fn configure_routes(&self, config: &mut ServiceConfig) {
+        config.data(self.document.clone());
+        super::http::configure_routes(config);
+    }
+
+    fn describe_operations(&self) -> Vec<Operation> {
+        super::http::operations()
+    }
+}
@@ -0,0 +1,38 @@
+use crate::http::{
+    negotiation::APPLICATION_JSON, openapi::Operation, problem::NOT_ACCEPTABLE, routes,
+};
+use actix_http::http::{Method, StatusCode};
+use actix_web::web::ServiceConfig;
+use serde_json::json;
+
+mod get;
+
+/// The path that the `OpenAPI` document is served from.
+const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Configure the HTTP routes for the `OpenAPI` document.
+///
+/// # Parameters
+/// - `config` - The HTTP Server configuration to register the routes with.
+pub fn configure_routes(config: &mut ServiceConfig) {
+    routes::new(OPENAPI_PATH)
+        .with_route(Method::GET, get::handle)
+        .register(config);
+}
+
+/// Describe the HTTP routes for the `OpenAPI` document.
+///
+/// # Returns
+/// The operations of every route registered by `configure_routes`.
+pub fn operations() -> Vec<Operation> {
+    vec![Operation::new(Method::GET, OPENAPI_PATH, "getOpenApi")
+        .with_summary("Get the OpenAPI document describing the entire API")
+        .with_tag("openapi")
+        .with_response(
+            StatusCode::OK,
+            "The OpenAPI document",
+            APPLICATION_JSON.name,
+            json!({"type": "object"}),
+        )
+        .with_problem(&NOT_ACCEPTABLE)]
+}
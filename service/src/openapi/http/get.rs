@@ -0,0 +1,20 @@
+use crate::http::{openapi::OpenApi, Response, SimpleRespondable};
+use actix_http::http::header::{CacheControl, CacheDirective};
+use actix_web::web::Data;
+use std::sync::Arc;
+
+/// Generate the `OpenAPI` document describing the entire service
+///
+/// # Parameters
+/// - `document` - The `OpenAPI` document
+///
+/// # Returns
+/// The HTTP Response containing the `OpenAPI` document.
+pub async fn handle(document: Data<Arc<OpenApi>>) -> Response<SimpleRespondable<OpenApi>> {
+    SimpleRespondable::new(document.get_ref().as_ref().clone())
+        .with_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(3600),
+        ]))
+        .into()
+}
@@ -0,0 +1,56 @@
+use crate::{
+    http::{
+        openapi::Operation,
+        problem::{ProblemDocumentation, ProblemRegistry},
+    },
+    server::RouteConfigurer,
+};
+use actix_web::web::ServiceConfig;
+use std::sync::Arc;
+
+/// Component for the documentation of problem types.
+pub struct Component {
+    registry: Arc<ProblemRegistry>,
+}
+
+/// Builder for building the problem documentation component.
+pub struct Builder {
+    registry: ProblemRegistry,
+}
+
+/// Create a new instance of the problem documentation builder, already containing the standard problem types.
+pub fn new() -> Builder {
+    Builder {
+        registry: ProblemRegistry::standard(),
+    }
+}
+
+impl Builder {
+    /// Register the documentation of a problem type.
+    ///
+    /// # Parameters
+    /// - `documentation` - The documentation of the problem type
+    pub fn with_problem(mut self, documentation: ProblemDocumentation) -> Self {
+        self.registry = self.registry.with_problem(documentation);
+
+        self
+    }
+
+    /// Build the actual problem documentation component.
+    pub fn build(self) -> Arc<Component> {
+        Arc::new(Component {
+            registry: Arc::new(self.registry),
+        })
+    }
+}
+
+impl RouteConfigurer for Component {
+    fn configure_routes(&self, config: &mut ServiceConfig) {
+        config.data(self.registry.clone());
+        super::http::configure_routes(config);
+    }
+
+    fn describe_operations(&self) -> Vec<Operation> {
+        super::http::operations()
+    }
+}
@@ -0,0 +1,106 @@
+use crate::http::{
+    hal::APPLICATION_HAL_JSON,
+    openapi::{hal_schema, Operation},
+    problem::{INVALID_PAGING, NOT_ACCEPTABLE, NOT_FOUND},
+    routes,
+};
+use actix_http::http::{Method, StatusCode};
+use actix_web::web::ServiceConfig;
+use serde_json::{json, Value};
+
+mod get;
+mod html;
+mod list;
+
+/// Configure the HTTP routes for the documentation of problem types.
+///
+/// # Parameters
+/// - `config` - The HTTP Server configuration to register the routes with.
+pub fn configure_routes(config: &mut ServiceConfig) {
+    routes::new("/problems")
+        .with_route(Method::GET, list::handle)
+        .register(config);
+    routes::new("/problems/{name}")
+        .with_route(Method::GET, get::handle)
+        .register(config);
+}
+
+/// Describe the HTTP routes for the documentation of problem types.
+///
+/// # Returns
+/// The operations of every route registered by `configure_routes`.
+pub fn operations() -> Vec<Operation> {
+    vec![
+        Operation::new(Method::GET, "/problems", "listProblems")
+            .with_summary("Get the documentation of every problem type")
+            .with_tag("problems")
+            .with_query_parameter(
+                "offset",
+                "The offset of the first problem type in the page",
+                json!({"type": "integer", "minimum": 0}),
+            )
+            .with_query_parameter(
+                "pageSize",
+                "The maximum number of problem types in the page",
+                json!({"type": "integer", "minimum": 1}),
+            )
+            .with_projection()
+            .with_response(
+                StatusCode::OK,
+                "A page of the documentation of every problem type",
+                APPLICATION_HAL_JSON.name,
+                hal_schema(json!({
+                    "offset": {"type": "integer"},
+                    "pageSize": {"type": "integer"},
+                    "total": {"type": "integer"},
+                    "_embedded": {
+                        "type": "object",
+                        "properties": {
+                            "item": {"type": "array", "items": problem_schema()}
+                        }
+                    }
+                })),
+            )
+            .with_response(
+                StatusCode::OK,
+                "The documentation of every problem type",
+                html::TEXT_HTML,
+                json!({"type": "string"}),
+            )
+            .with_problem(&INVALID_PAGING)
+            .with_problem(&NOT_ACCEPTABLE),
+        Operation::new(Method::GET, "/problems/{name}", "getProblem")
+            .with_summary("Get the documentation of a single problem type")
+            .with_tag("problems")
+            .with_path_parameter("name", "The name of the problem type")
+            .with_projection()
+            .with_response(
+                StatusCode::OK,
+                "The documentation of the problem type",
+                APPLICATION_HAL_JSON.name,
+                problem_schema(),
+            )
+            .with_response(
+                StatusCode::OK,
+                "The documentation of the problem type",
+                html::TEXT_HTML,
+                json!({"type": "string"}),
+            )
+            .with_problem(&NOT_FOUND)
+            .with_problem(&NOT_ACCEPTABLE),
+    ]
+}
+
+/// Build the schema of the documentation of a single problem type.
+///
+/// # Returns
+/// The JSON Schema of the HAL document.
+fn problem_schema() -> Value {
+    hal_schema(json!({
+        "type": {"type": "string", "format": "uri-reference"},
+        "title": {"type": "string"},
+        "description": {"type": "string"},
+        "statusCodes": {"type": "array", "items": {"type": "integer"}},
+        "extensions": {"type": "object", "additionalProperties": {"type": "string"}}
+    }))
+}
@@ -0,0 +1,49 @@
+use super::html;
+use crate::http::{
+    hal::{HalDocument, HalRespondable},
+    problem::{Problem, ProblemRegistry, NOT_FOUND},
+    Response,
+};
+use actix_http::http::header::{CacheControl, CacheDirective};
+use actix_web::{
+    web::{Data, Path},
+    Either, HttpRequest, HttpResponse,
+};
+use std::sync::Arc;
+
+/// Generate the documentation for a single problem type
+///
+/// # Parameters
+/// - `req` - The HTTP Request, to determine whether the client wants HTML
+/// - `path` - The parsed URL path, containing the name of the problem type
+/// - `registry` - The registry of known problem types
+///
+/// # Returns
+/// The HTTP Response. Either the documentation of the problem type as an HTML page or a HAL document, or else a
+/// Problem indicating that the problem type isn't known.
+pub async fn handle(
+    req: HttpRequest,
+    path: Path<String>,
+    registry: Data<Arc<ProblemRegistry>>,
+) -> Result<Either<HttpResponse, Response<HalRespondable>>, Problem> {
+    let documentation = registry
+        .get(&path.0)
+        .ok_or_else(|| Problem::from(NOT_FOUND))?;
+
+    if html::is_preferred(&req) {
+        return Ok(Either::A(html::render_problem(documentation)));
+    }
+
+    let hal_document = HalDocument::new(documentation)
+        .with_link("self", documentation.problem_type.clone())
+        .with_link("collection", "/problems");
+
+    Ok(Either::B(
+        HalRespondable::from(hal_document)
+            .with_header(CacheControl(vec![
+                CacheDirective::Public,
+                CacheDirective::MaxAge(3600),
+            ]))
+            .into(),
+    ))
+}
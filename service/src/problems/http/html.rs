@@ -0,0 +1,198 @@
+use crate::http::{
+    hal::APPLICATION_HAL_JSON, negotiation::negotiate, problem::ProblemDocumentation,
+};
+use actix_http::http::{
+    header::{self, CacheControl, CacheDirective},
+    StatusCode,
+};
+use actix_web::{HttpRequest, HttpResponse};
+use std::fmt::Write;
+
+/// Media type for HTML pages.
+pub const TEXT_HTML: &str = "text/html";
+
+/// Determine whether the client would rather have the documentation as an HTML page than as a HAL document.
+///
+/// # Parameters
+/// - `req` - The HTTP Request
+///
+/// # Returns
+/// True if an HTML page should be returned. False if a HAL document should be returned instead.
+pub fn is_preferred(req: &HttpRequest) -> bool {
+    negotiate(req, &[APPLICATION_HAL_JSON.name, TEXT_HTML]) == Some(1)
+}
+
+/// Render the documentation of a single problem type as an HTML page.
+///
+/// # Parameters
+/// - `documentation` - The documentation to render
+///
+/// # Returns
+/// The HTTP Response
+pub fn render_problem(documentation: &ProblemDocumentation) -> HttpResponse {
+    let mut body = String::new();
+
+    writeln!(body, "<h1>{}</h1>", escape(&documentation.title)).unwrap();
+    writeln!(
+        body,
+        "<p><code>{}</code></p>",
+        escape(&documentation.problem_type)
+    )
+    .unwrap();
+    writeln!(body, "<p>{}</p>", escape(&documentation.description)).unwrap();
+
+    writeln!(body, "<h2>Status Codes</h2>").unwrap();
+    writeln!(body, "<ul>").unwrap();
+    for status_code in &documentation.status_codes {
+        let reason = StatusCode::from_u16(*status_code)
+            .ok()
+            .and_then(|status_code| status_code.canonical_reason())
+            .unwrap_or_default();
+        writeln!(body, "<li>{} {}</li>", status_code, escape(reason)).unwrap();
+    }
+    writeln!(body, "</ul>").unwrap();
+
+    if !documentation.extensions.is_empty() {
+        writeln!(body, "<h2>Extension Members</h2>").unwrap();
+        writeln!(body, "<dl>").unwrap();
+        for (name, description) in &documentation.extensions {
+            writeln!(body, "<dt><code>{}</code></dt>", escape(name)).unwrap();
+            writeln!(body, "<dd>{}</dd>", escape(description)).unwrap();
+        }
+        writeln!(body, "</dl>").unwrap();
+    }
+
+    writeln!(body, r#"<p><a href="/problems">All problem types</a></p>"#).unwrap();
+
+    page(&documentation.title, &body)
+}
+
+/// Render the documentation of every problem type as an HTML page.
+///
+/// # Parameters
+/// - `problems` - The documentation of the problem types to render
+///
+/// # Returns
+/// The HTTP Response
+pub fn render_problems<'a, I>(problems: I) -> HttpResponse
+where
+    I: IntoIterator<Item = &'a ProblemDocumentation>,
+{
+    let mut body = String::new();
+
+    writeln!(body, "<h1>Problem Types</h1>").unwrap();
+    writeln!(body, "<dl>").unwrap();
+    for documentation in problems {
+        writeln!(
+            body,
+            r#"<dt><a href="{}">{}</a></dt>"#,
+            escape(&documentation.problem_type),
+            escape(&documentation.title)
+        )
+        .unwrap();
+        writeln!(body, "<dd>{}</dd>", escape(&documentation.description)).unwrap();
+    }
+    writeln!(body, "</dl>").unwrap();
+
+    page("Problem Types", &body)
+}
+
+/// Wrap the body of an HTML page in the standard page structure, and build a response for it.
+///
+/// # Parameters
+/// - `title` - The title of the page
+/// - `body` - The HTML contents of the page body
+///
+/// # Returns
+/// The HTTP Response
+fn page(title: &str, body: &str) -> HttpResponse {
+    let html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        body
+    );
+
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .header(header::VARY, "Accept")
+        .set(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(3600),
+        ]))
+        .body(html)
+}
+
+/// Escape a string so that it is safe to use as HTML text or attribute content.
+///
+/// # Parameters
+/// - `value` - The string to escape
+///
+/// # Returns
+/// The escaped string
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::problem::{ProblemRegistry, VALIDATION_FAILED};
+    use actix_http::body::Body;
+    use actix_web::test::TestRequest;
+    use assert2::check;
+    use test_case::test_case;
+
+    fn body(response: &HttpResponse) -> String {
+        match response.body().as_ref() {
+            Some(Body::Bytes(bytes)) => String::from_utf8(bytes.to_vec()).unwrap(),
+            _ => String::new(),
+        }
+    }
+
+    #[test_case(None, false ; "no accept header")]
+    #[test_case(Some("*/*"), false ; "anything")]
+    #[test_case(Some("application/json"), false ; "json")]
+    #[test_case(Some("text/html"), true ; "html")]
+    #[test_case(Some("text/html,application/xhtml+xml,*/*;q=0.8"), true ; "browser")]
+    fn preferred(accept: Option<&str>, expected: bool) {
+        let mut req = TestRequest::get();
+        if let Some(accept) = accept {
+            req = req.header(header::ACCEPT, accept);
+        }
+
+        check!(is_preferred(&req.to_http_request()) == expected);
+    }
+
+    #[test]
+    fn render_single_problem() {
+        let documentation = ProblemDocumentation::new(&VALIDATION_FAILED, "Values <were> invalid")
+            .with_extension("invalid-params", "The invalid values");
+
+        let response = render_problem(&documentation);
+
+        check!(response.status() == StatusCode::OK);
+        check!(response.headers().get(header::CONTENT_TYPE).unwrap() == "text/html; charset=utf-8");
+
+        let body = body(&response);
+        check!(body.contains("<title>The request contained invalid values</title>"));
+        check!(body.contains("<p>Values &lt;were&gt; invalid</p>"));
+        check!(body.contains("<li>422 Unprocessable Entity</li>"));
+        check!(body.contains("<dt><code>invalid-params</code></dt>"));
+    }
+
+    #[test]
+    fn render_all_problems() {
+        let registry = ProblemRegistry::standard();
+
+        let response = render_problems(registry.problems());
+
+        let body = body(&response);
+        check!(body.contains(r#"<a href="/problems/validation_failed">"#));
+        check!(body.contains(r#"<a href="/problems/invalid_paging">"#));
+    }
+}
@@ -0,0 +1,54 @@
+use super::html;
+use crate::http::{
+    hal::{HalCollection, HalDocument, HalRespondable},
+    paging::PageRequest,
+    problem::{Problem, ProblemRegistry},
+    Response,
+};
+use actix_http::http::header::{CacheControl, CacheDirective};
+use actix_web::{web::Data, Either, HttpRequest, HttpResponse};
+use std::{convert::TryFrom, sync::Arc};
+
+/// Generate the documentation for every known problem type
+///
+/// # Parameters
+/// - `req` - The HTTP Request, to determine whether the client wants HTML
+/// - `registry` - The registry of known problem types
+/// - `page` - The page of problem types that was requested, which only applies to the HAL document
+///
+/// # Returns
+/// The HTTP Response, containing the documentation of every problem type as either an HTML page or a page of a HAL
+/// collection, or else a Problem indicating that the paging parameters were invalid.
+pub async fn handle(
+    req: HttpRequest,
+    registry: Data<Arc<ProblemRegistry>>,
+    page: PageRequest,
+) -> Result<Either<HttpResponse, Response<HalRespondable>>, Problem> {
+    if html::is_preferred(&req) {
+        return Ok(Either::A(html::render_problems(registry.problems())));
+    }
+
+    let offset = usize::try_from(page.offset()?).unwrap_or(usize::MAX);
+    let page_size = usize::try_from(page.page_size).unwrap_or(usize::MAX);
+    let total = registry.problems().count() as u64;
+
+    let items: Vec<HalDocument> = registry
+        .problems()
+        .skip(offset)
+        .take(page_size)
+        .map(|documentation| {
+            HalDocument::new(documentation).with_link("self", documentation.problem_type.clone())
+        })
+        .collect();
+
+    let collection = HalCollection::new("/problems", page, items).with_total(total);
+
+    Ok(Either::B(
+        HalRespondable::from(collection)
+            .with_header(CacheControl(vec![
+                CacheDirective::Public,
+                CacheDirective::MaxAge(3600),
+            ]))
+            .into(),
+    ))
+}
@@ -0,0 +1,58 @@
+use crate::{
+    http::{openapi::Operation, schema::SchemaRegistry},
+    server::RouteConfigurer,
+};
+use actix_web::web::ServiceConfig;
+use std::sync::Arc;
+
+/// Component for publishing the schemas of the documents that the service returns.
+pub struct Component {
+    registry: Arc<SchemaRegistry>,
+}
+
+/// Builder for building the schemas component.
+#[derive(Default)]
+pub struct Builder {
+    routes: Vec<Arc<dyn RouteConfigurer>>,
+}
+
+/// Create a new instance of the schemas builder.
+pub fn new() -> Builder {
+    Builder::default()
+}
+
+impl Builder {
+    /// Register a `RouteConfigurer` whose schemas should be published.
+    ///
+    /// # Parameters
+    /// - `routes` - The configurer for the routes to publish the schemas of
+    pub fn with_routes(mut self, routes: Arc<dyn RouteConfigurer>) -> Self {
+        self.routes.push(routes);
+
+        self
+    }
+
+    /// Build the actual schemas component, collecting the schemas of every registered `RouteConfigurer`.
+    pub fn build(self) -> Arc<Component> {
+        let registry = self
+            .routes
+            .iter()
+            .flat_map(|routes| routes.describe_schemas())
+            .fold(SchemaRegistry::default(), SchemaRegistry::with_schema);
+
+        Arc::new(Component {
+            registry: Arc::new(registry),
+        })
+    }
+}
+
+impl RouteConfigurer for Component {
+    fn configure_routes(&self, config: &mut ServiceConfig) {
+        config.data(self.registry.clone());
+        super::http::configure_routes(config);
+    }
+
+    fn describe_operations(&self) -> Vec<Operation> {
+        super::http::operations()
+    }
+}
@@ -0,0 +1,42 @@
+use crate::http::{
+    openapi::Operation,
+    problem::{NOT_ACCEPTABLE, NOT_FOUND},
+    routes,
+    schema::APPLICATION_SCHEMA_JSON,
+};
+use actix_http::http::{Method, StatusCode};
+use actix_web::web::ServiceConfig;
+use serde_json::json;
+
+mod get;
+
+/// Configure the HTTP routes for the published schemas.
+///
+/// # Parameters
+/// - `config` - The HTTP Server configuration to register the routes with.
+pub fn configure_routes(config: &mut ServiceConfig) {
+    routes::new("/schemas/{name}")
+        .with_route(Method::GET, get::handle)
+        .register(config);
+}
+
+/// Describe the HTTP routes for the published schemas.
+///
+/// # Returns
+/// The operations of every route registered by `configure_routes`.
+pub fn operations() -> Vec<Operation> {
+    vec![Operation::new(Method::GET, "/schemas/{name}", "getSchema")
+        .with_summary(
+            "Get the JSON Schema of a document type, as linked to by the profile of the documents",
+        )
+        .with_tag("schemas")
+        .with_path_parameter("name", "The name of the schema")
+        .with_response(
+            StatusCode::OK,
+            "The JSON Schema",
+            APPLICATION_SCHEMA_JSON.name,
+            json!({"type": "object"}),
+        )
+        .with_problem(&NOT_FOUND)
+        .with_problem(&NOT_ACCEPTABLE)]
+}
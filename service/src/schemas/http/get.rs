@@ -0,0 +1,34 @@
+use crate::http::{
+    problem::{Problem, NOT_FOUND},
+    schema::{SchemaRegistry, SCHEMA_MEDIA_TYPES},
+    Response, SimpleRespondable,
+};
+use actix_http::http::header::{CacheControl, CacheDirective};
+use actix_web::web::{Data, Path};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Generate a single schema
+///
+/// # Parameters
+/// - `path` - The parsed URL path, containing the name of the schema
+/// - `registry` - The registry of published schemas
+///
+/// # Returns
+/// The HTTP Response. Either the schema, or else a Problem indicating that the schema isn't known.
+pub async fn handle(
+    path: Path<String>,
+    registry: Data<Arc<SchemaRegistry>>,
+) -> Result<Response<SimpleRespondable<Value>>, Problem> {
+    let schema = registry
+        .get(&path.0)
+        .ok_or_else(|| Problem::from(NOT_FOUND))?;
+
+    Ok(SimpleRespondable::new(schema.clone())
+        .with_media_types(SCHEMA_MEDIA_TYPES)
+        .with_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(3600),
+        ]))
+        .into())
+}
@@ -1,18 +1,42 @@
 pub mod component;
-mod span;
+pub mod cors;
+mod handle;
+mod https;
+pub mod rate_limit;
+pub mod span;
+pub mod tls;
 
-use std::sync::Arc;
+pub use handle::ServerHandle;
+use std::{net::TcpListener, sync::Arc, time::Duration};
 
-use actix_cors::Cors;
-use actix_http::http::header;
-use actix_web::{middleware::Logger, web::ServiceConfig, App, HttpServer};
+use actix_web::{
+    middleware::{Condition, Logger},
+    web,
+    web::ServiceConfig,
+    App, HttpServer,
+};
 use actix_web_prom::PrometheusMetrics;
+use futures::future::{select, FutureExt};
+
+use crate::http::{
+    openapi::Operation,
+    problem::{self, ProblemConfig, ProblemNegotiation},
+    schema::Schema,
+};
 
 /// The HTTP Server running the application.
 pub struct Server {
     port: u16,
+    shutdown_grace_period: Duration,
+    cors: cors::CorsPolicy,
+    tls: Option<tls::TlsConfig>,
+    redirect_port: Option<u16>,
+    public_host: String,
+    hsts_max_age: Option<Duration>,
+    rate_limiter: Arc<rate_limit::RateLimiter>,
     prometheus: prometheus::Registry,
     pub(super) routes: Vec<Arc<dyn RouteConfigurer>>,
+    pub(super) problem_config: ProblemConfig,
 }
 
 /// Trait that can be implemented by other components to configure routes into the HTTP Server.
@@ -22,35 +46,85 @@ pub trait RouteConfigurer: Send + Sync {
     /// # Parameters
     /// - `config` - The HTTP Server configuration to wire the routes onto
     fn configure_routes(&self, config: &mut ServiceConfig);
+
+    /// Describe the operations of the routes that are configured, so that they can be included in the `OpenAPI`
+    /// document for the service.
+    ///
+    /// # Returns
+    /// The operations of every route that `configure_routes` registers.
+    fn describe_operations(&self) -> Vec<Operation> {
+        vec![]
+    }
+
+    /// Describe the schemas of the documents that the routes return, so that they can be published for clients.
+    ///
+    /// # Returns
+    /// The schemas of the documents returned by the routes that `configure_routes` registers.
+    fn describe_schemas(&self) -> Vec<Schema> {
+        vec![]
+    }
 }
 
 impl Server {
     /// Start the server listening on the configured port.
-    pub async fn start(self) {
+    ///
+    /// If TLS is configured then the server serves HTTPS, including HTTP/2, on the configured port. It can optionally
+    /// also listen for plain HTTP on another port, redirecting every request made there to HTTPS.
+    ///
+    /// The server is stopped using the returned handle, rather than by the process receiving a signal, so that the
+    /// caller can decide how to shut down.
+    ///
+    /// # Returns
+    /// The handle to the running server.
+    ///
+    /// # Errors
+    /// Any error from binding the server to the port, or from loading the TLS certificate.
+    pub fn start(self) -> std::io::Result<ServerHandle> {
         let address = format!("0.0.0.0:{}", self.port);
 
         tracing::debug!(address = ?address, "Starting HTTP server");
 
+        // Bind up front, so that redirects know which port HTTPS is actually served on.
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        let redirect_listener = match (&self.tls, self.redirect_port) {
+            (Some(_), Some(port)) => Some(TcpListener::bind(format!("0.0.0.0:{}", port))?),
+            _ => None,
+        };
+        let redirect_address = match &redirect_listener {
+            Some(listener) => Some(listener.local_addr()?),
+            None => None,
+        };
+
         let prometheus =
             PrometheusMetrics::new_with_registry(self.prometheus, "actix", Some("/metrics"), None)
                 .unwrap();
         let routes = self.routes.clone();
+        let problem_config = self.problem_config;
+        let cors = self.cors;
+        let rate_limiter = self.rate_limiter;
+        let https_redirect = https::HttpsRedirect {
+            public_host: self.public_host,
+            https_port: address.port(),
+            hsts_max_age: self.hsts_max_age,
+        };
+        let secure = self.tls.is_some();
 
-        HttpServer::new(move || {
+        let server = HttpServer::new(move || {
             let prometheus = prometheus.clone();
             let routes = routes.clone();
 
             let mut app = App::new()
+                .wrap(ProblemNegotiation)
+                .wrap(rate_limit::RateLimit(rate_limiter.clone()))
                 .wrap(prometheus)
                 .wrap(Logger::default())
-                .wrap(
-                    Cors::default()
-                        .allow_any_origin()
-                        .allow_any_method()
-                        .allow_any_header()
-                        .expose_headers(vec![header::ETAG, header::LOCATION, header::LINK]),
-                )
-                .wrap(span::Span);
+                .wrap(cors.build_middleware())
+                .wrap(Condition::new(secure, https_redirect.clone()))
+                .wrap(span::Span)
+                .app_data(problem_config)
+                .configure(problem::configure_extractors)
+                .default_service(web::route().to(problem::not_found));
 
             for c in &routes {
                 app = app.configure(move |server_config| {
@@ -62,10 +136,40 @@ impl Server {
 
             app
         })
-        .bind(address)
-        .unwrap()
-        .run()
-        .await
-        .unwrap();
+        .shutdown_timeout(self.shutdown_grace_period.as_secs())
+        .disable_signals();
+
+        let (server, reloader) = match &self.tls {
+            Some(tls) => {
+                let (acceptor, reloader) = tls
+                    .acceptor()
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                let server = server.listen_openssl(listener, acceptor)?;
+                let server = match redirect_listener {
+                    Some(redirect_listener) => server.listen(redirect_listener)?,
+                    None => server,
+                };
+                (server, reloader)
+            }
+            None => (server.listen(listener)?, None),
+        };
+
+        tracing::info!(
+            address = ?address,
+            redirect_address = ?redirect_address,
+            tls = secure,
+            "Started HTTP server"
+        );
+
+        let handle = ServerHandle::new(server.run(), address, redirect_address);
+
+        if let Some(reloader) = reloader {
+            let stopped = handle.clone();
+            actix_rt::spawn(async move {
+                select(reloader.run().boxed_local(), stopped.wait().boxed_local()).await;
+            });
+        }
+
+        Ok(handle)
     }
 }
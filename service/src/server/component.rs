@@ -1,5 +1,6 @@
-use super::{RouteConfigurer, Server};
-use std::sync::Arc;
+use super::{cors::CorsPolicy, rate_limit::RateLimiter, tls::TlsConfig, RouteConfigurer, Server};
+use crate::http::problem::ProblemConfig;
+use std::{sync::Arc, time::Duration};
 
 /// Component representing the HTTP Server.
 pub struct Component {
@@ -10,11 +11,25 @@ pub struct Component {
 #[derive(Default)]
 pub struct Builder {
     routes: Vec<Arc<dyn RouteConfigurer>>,
+    development_mode: bool,
+    shutdown_grace_period: Duration,
+    cors: CorsPolicy,
+    tls: Option<TlsConfig>,
+    redirect_port: Option<u16>,
+    public_host: String,
+    hsts_max_age: Option<Duration>,
+    rate_limiter: RateLimiter,
 }
 
+/// The default time to wait for in-flight requests to finish when stopping, which matches the Actix default.
+const DEFAULT_SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
 /// Create a new builder to build the component with.
 pub fn new() -> Builder {
-    Builder::default()
+    Builder {
+        shutdown_grace_period: DEFAULT_SHUTDOWN_GRACE_PERIOD,
+        ..Builder::default()
+    }
 }
 
 impl Builder {
@@ -28,6 +43,89 @@ impl Builder {
         self
     }
 
+    /// Specify whether the HTTP Server is running in development mode, in which case responses include more details
+    /// to help with debugging.
+    ///
+    /// # Parameters
+    /// - `development_mode` - Whether to run in development mode
+    pub fn with_development_mode(mut self, development_mode: bool) -> Self {
+        self.development_mode = development_mode;
+
+        self
+    }
+
+    /// Specify how long the HTTP Server waits for in-flight requests to finish when it is stopped gracefully.
+    ///
+    /// # Parameters
+    /// - `shutdown_grace_period` - The time to wait. Actix only supports whole seconds, so this is rounded down.
+    pub fn with_shutdown_grace_period(mut self, shutdown_grace_period: Duration) -> Self {
+        self.shutdown_grace_period = shutdown_grace_period;
+
+        self
+    }
+
+    /// Specify the policy for which cross-origin requests the HTTP Server allows. If not specified then no
+    /// cross-origin requests are allowed.
+    ///
+    /// # Parameters
+    /// - `cors` - The CORS policy
+    pub fn with_cors_policy(mut self, cors: CorsPolicy) -> Self {
+        self.cors = cors;
+
+        self
+    }
+
+    /// Specify that the HTTP Server should serve HTTPS using the provided TLS configuration. If not specified then
+    /// plain HTTP is served instead.
+    ///
+    /// # Parameters
+    /// - `tls` - The TLS configuration
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+
+        self
+    }
+
+    /// Specify a port to listen for plain HTTP requests on, redirecting them all to HTTPS. This is only used if TLS is
+    /// configured.
+    ///
+    /// The redirects always go to the configured public host, never to the host that the client asked for, so that
+    /// they can't be used to send clients elsewhere.
+    ///
+    /// # Parameters
+    /// - `redirect_port` - The port to listen on
+    /// - `public_host` - The host name that clients use to reach the HTTPS server
+    pub fn with_https_redirect<S>(mut self, redirect_port: u16, public_host: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.redirect_port = Some(redirect_port);
+        self.public_host = public_host.into();
+
+        self
+    }
+
+    /// Specify that HTTPS responses should tell clients to only use HTTPS in future. This is only used if TLS is
+    /// configured.
+    ///
+    /// # Parameters
+    /// - `max_age` - How long clients should remember to only use HTTPS for
+    pub fn with_hsts(mut self, max_age: Duration) -> Self {
+        self.hsts_max_age = Some(max_age);
+
+        self
+    }
+
+    /// Specify the rate limits to apply to clients of the HTTP Server. If not specified then requests aren't limited.
+    ///
+    /// # Parameters
+    /// - `rate_limiter` - The rate limiter
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+
+        self
+    }
+
     /// Actually build the HTTP Server component
     ///
     /// # Parameters
@@ -38,8 +136,18 @@ impl Builder {
         Component {
             server: Server {
                 port,
+                shutdown_grace_period: self.shutdown_grace_period,
+                cors: self.cors,
+                tls: self.tls,
+                redirect_port: self.redirect_port,
+                public_host: self.public_host,
+                hsts_max_age: self.hsts_max_age,
+                rate_limiter: Arc::new(self.rate_limiter),
                 prometheus,
                 routes: self.routes,
+                problem_config: ProblemConfig {
+                    development_mode: self.development_mode,
+                },
             },
         }
     }
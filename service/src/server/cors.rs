@@ -0,0 +1,503 @@
+use actix_cors::Cors;
+use actix_http::http::{header, HeaderName, Method};
+use std::{convert::TryFrom, str::FromStr, time::Duration};
+
+use crate::http::correlation::{X_REQUEST_ID, X_TRACE_ID};
+use crate::server::rate_limit::{RATELIMIT_LIMIT, RATELIMIT_REMAINING, RATELIMIT_RESET};
+
+/// Errors that can occur when building a CORS policy.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum CorsPolicyError {
+    #[error("The allowed origin '{0}' is not a valid origin or wildcard subdomain pattern")]
+    InvalidOrigin(String),
+
+    #[error("The allowed method '{0}' is not a valid HTTP method")]
+    InvalidMethod(String),
+
+    #[error("The header '{0}' is not a valid HTTP header name")]
+    InvalidHeader(String),
+
+    #[error("The permissive CORS policy can only be used in development mode")]
+    PermissiveOutsideDevelopment,
+}
+
+/// A single origin that is allowed to make cross-origin requests.
+#[derive(Debug, Clone, PartialEq)]
+enum AllowedOrigin {
+    /// Exactly one origin, such as `https://app.example.com`.
+    Exact(String),
+    /// Any subdomain of a host, such as `https://*.example.com`. This doesn't match the host itself.
+    Subdomain {
+        /// The scheme of the origin, including the `://`.
+        scheme: String,
+        /// The host and port after the wildcard, including the leading `.`.
+        suffix: String,
+    },
+}
+
+impl FromStr for AllowedOrigin {
+    type Err = CorsPolicyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CorsPolicyError::InvalidOrigin(s.to_owned());
+
+        let origin = s.trim().to_ascii_lowercase();
+        let scheme = ["https://", "http://"]
+            .iter()
+            .find(|scheme| origin.starts_with(*scheme))
+            .ok_or_else(invalid)?;
+        let authority = &origin[scheme.len()..];
+
+        let host = match authority.rfind(':') {
+            Some(index) => {
+                authority[index + 1..]
+                    .parse::<u16>()
+                    .map_err(|_| invalid())?;
+                &authority[..index]
+            }
+            None => authority,
+        };
+
+        if let Some(base) = host.strip_prefix("*.") {
+            // Require at least two labels after the wildcard, so that a whole top-level domain can't be allowed.
+            if !is_valid_host(base) || !base.contains('.') {
+                return Err(invalid());
+            }
+
+            Ok(AllowedOrigin::Subdomain {
+                scheme: (*scheme).to_owned(),
+                suffix: authority[1..].to_owned(),
+            })
+        } else if is_valid_host(host) {
+            Ok(AllowedOrigin::Exact(origin))
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl AllowedOrigin {
+    /// Check whether this allows the provided origin.
+    ///
+    /// # Parameters
+    /// - `origin` - The origin to check, in lowercase
+    ///
+    /// # Returns
+    /// True if the origin is allowed. False if not.
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            AllowedOrigin::Exact(allowed) => allowed == origin,
+            AllowedOrigin::Subdomain { scheme, suffix } => {
+                match origin
+                    .strip_prefix(scheme.as_str())
+                    .and_then(|rest| rest.strip_suffix(suffix.as_str()))
+                {
+                    Some(subdomain) => is_valid_host(subdomain),
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+/// Check whether a string is a valid host name, made up of non-empty labels of letters, digits and hyphens.
+///
+/// # Parameters
+/// - `host` - The host name to check
+///
+/// # Returns
+/// True if the host name is valid. False if not.
+fn is_valid_host(host: &str) -> bool {
+    !host.is_empty()
+        && host.split('.').all(|label| {
+            !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+/// The policy for which cross-origin requests the HTTP Server allows.
+///
+/// The default policy doesn't allow any cross-origin requests at all.
+#[derive(Debug, Clone, Default)]
+pub struct CorsPolicy {
+    /// Whether to allow everything. Only for use in development.
+    permissive: bool,
+    /// The origins that may make requests.
+    origins: Vec<AllowedOrigin>,
+    /// The methods that may be used.
+    methods: Vec<Method>,
+    /// The request headers that may be sent.
+    headers: Vec<HeaderName>,
+    /// The response headers that clients may read, in addition to those that the service always exposes.
+    exposed_headers: Vec<HeaderName>,
+    /// Whether requests may include credentials.
+    credentials: bool,
+    /// How long, in seconds, clients may cache the result of a preflight request for.
+    max_age: Option<usize>,
+}
+
+/// Builder to help build a `CorsPolicy`.
+#[derive(Default)]
+pub struct Builder {
+    origins: Vec<String>,
+    methods: Vec<String>,
+    headers: Vec<String>,
+    exposed_headers: Vec<String>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+/// Create a new builder to build a CORS policy with.
+pub fn new() -> Builder {
+    Builder::default()
+}
+
+impl Builder {
+    /// Allow some origins to make cross-origin requests.
+    ///
+    /// # Parameters
+    /// - `origins` - The origins to allow. Each is either an exact origin, such as `https://app.example.com`, or a
+    ///   wildcard subdomain pattern, such as `https://*.example.com`.
+    pub fn with_allowed_origins<I, S>(mut self, origins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.origins.extend(origins.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Allow some HTTP methods to be used in cross-origin requests.
+    ///
+    /// # Parameters
+    /// - `methods` - The methods to allow
+    pub fn with_allowed_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.methods.extend(methods.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Allow some request headers to be sent in cross-origin requests.
+    ///
+    /// # Parameters
+    /// - `headers` - The names of the headers to allow
+    pub fn with_allowed_headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.headers.extend(headers.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Expose some response headers to cross-origin clients, in addition to those that the service always exposes.
+    ///
+    /// # Parameters
+    /// - `headers` - The names of the headers to expose
+    pub fn with_exposed_headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exposed_headers
+            .extend(headers.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Specify whether cross-origin requests may include credentials, such as cookies.
+    ///
+    /// # Parameters
+    /// - `credentials` - Whether to allow credentials
+    pub fn with_credentials(mut self, credentials: bool) -> Self {
+        self.credentials = credentials;
+
+        self
+    }
+
+    /// Specify how long clients may cache the result of a preflight request for.
+    ///
+    /// # Parameters
+    /// - `max_age` - The time to cache for. This is sent in whole seconds, so it is rounded down.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+
+        self
+    }
+
+    /// Actually build the CORS policy, checking that everything in it is valid.
+    ///
+    /// # Returns
+    /// The CORS policy.
+    ///
+    /// # Errors
+    /// If any of the origins, methods or headers are invalid.
+    pub fn build(self) -> Result<CorsPolicy, CorsPolicyError> {
+        let origins = self
+            .origins
+            .iter()
+            .map(|origin| origin.parse())
+            .collect::<Result<_, _>>()?;
+        let methods = self
+            .methods
+            .iter()
+            .map(|method| {
+                Method::from_str(method.trim())
+                    .map_err(|_| CorsPolicyError::InvalidMethod(method.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CorsPolicy {
+            permissive: false,
+            origins,
+            methods,
+            headers: parse_headers(&self.headers)?,
+            exposed_headers: parse_headers(&self.exposed_headers)?,
+            credentials: self.credentials,
+            max_age: self
+                .max_age
+                .map(|max_age| usize::try_from(max_age.as_secs()).unwrap_or(usize::MAX)),
+        })
+    }
+}
+
+/// Parse a list of header names.
+///
+/// # Parameters
+/// - `headers` - The header names to parse
+///
+/// # Returns
+/// The parsed header names.
+///
+/// # Errors
+/// If any of the header names are invalid.
+fn parse_headers(headers: &[String]) -> Result<Vec<HeaderName>, CorsPolicyError> {
+    headers
+        .iter()
+        .map(|name| {
+            HeaderName::try_from(name.trim())
+                .map_err(|_| CorsPolicyError::InvalidHeader(name.clone()))
+        })
+        .collect()
+}
+
+impl CorsPolicy {
+    /// Create a policy that allows any origin to make any request. This is only suitable for development.
+    ///
+    /// # Returns
+    /// The CORS policy.
+    #[must_use]
+    pub fn permissive() -> Self {
+        Self {
+            permissive: true,
+            ..Self::default()
+        }
+    }
+
+    /// Check whether an origin is allowed to make cross-origin requests.
+    ///
+    /// # Parameters
+    /// - `origin` - The origin to check
+    ///
+    /// # Returns
+    /// True if the origin is allowed. False if not.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        if self.permissive {
+            return true;
+        }
+
+        let origin = origin.to_ascii_lowercase();
+        self.origins.iter().any(|allowed| allowed.matches(&origin))
+    }
+
+    /// Build the Actix middleware that applies this policy.
+    ///
+    /// # Returns
+    /// The CORS middleware.
+    pub(crate) fn build_middleware(&self) -> Cors {
+        let exposed_headers = vec![
+            header::ETAG,
+            header::LOCATION,
+            header::LINK,
+            HeaderName::from_static(X_REQUEST_ID),
+            HeaderName::from_static(X_TRACE_ID),
+            HeaderName::from_static(RATELIMIT_LIMIT),
+            HeaderName::from_static(RATELIMIT_REMAINING),
+            HeaderName::from_static(RATELIMIT_RESET),
+            header::RETRY_AFTER,
+        ]
+        .into_iter()
+        .chain(self.exposed_headers.iter().cloned());
+
+        if self.permissive {
+            return Cors::default()
+                .allow_any_origin()
+                .allow_any_method()
+                .allow_any_header()
+                .expose_headers(exposed_headers);
+        }
+
+        let policy = self.clone();
+        let mut cors = Cors::default()
+            .allowed_origin_fn(move |origin, _| match origin.to_str() {
+                Ok(origin) => policy.allows_origin(origin),
+                Err(_) => false,
+            })
+            .allowed_methods(self.methods.clone())
+            .allowed_headers(self.headers.clone())
+            .expose_headers(exposed_headers)
+            .max_age(self.max_age);
+
+        if self.credentials {
+            cors = cors.supports_credentials();
+        }
+
+        cors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use test_case::test_case;
+
+    #[test_case("https://app.example.com" ; "exact")]
+    #[test_case("http://localhost:3000" ; "with port")]
+    #[test_case("HTTPS://App.Example.com" ; "mixed case")]
+    #[test_case("https://*.example.com" ; "wildcard")]
+    #[test_case("https://*.example.com:8443" ; "wildcard with port")]
+    fn valid_origin(origin: &str) {
+        let result = new().with_allowed_origins(vec![origin]).build();
+
+        check!(result.is_ok());
+    }
+
+    #[test_case("*" ; "any")]
+    #[test_case("" ; "blank")]
+    #[test_case("app.example.com" ; "no scheme")]
+    #[test_case("ftp://app.example.com" ; "unsupported scheme")]
+    #[test_case("https://app.example.com/" ; "trailing slash")]
+    #[test_case("https://app.example.com/path" ; "path")]
+    #[test_case("https://user@app.example.com" ; "user info")]
+    #[test_case("https://app.example.com:http" ; "invalid port")]
+    #[test_case("https://app..example.com" ; "empty label")]
+    #[test_case("https://*.com" ; "wildcard top level domain")]
+    #[test_case("https://app.*.example.com" ; "wildcard in middle")]
+    #[test_case("https://*example.com" ; "wildcard without dot")]
+    fn invalid_origin(origin: &str) {
+        let result = new().with_allowed_origins(vec![origin]).build();
+
+        let_assert!(Err(CorsPolicyError::InvalidOrigin(invalid)) = result);
+        check!(invalid == origin);
+    }
+
+    #[test_case("https://app.example.com", true ; "exact match")]
+    #[test_case("https://APP.example.com", true ; "exact match in different case")]
+    #[test_case("http://app.example.com", false ; "exact match with different scheme")]
+    #[test_case("https://app.example.com:8443", false ; "exact match with different port")]
+    #[test_case("https://api.example.org", true ; "subdomain")]
+    #[test_case("https://v1.api.example.org", true ; "nested subdomain")]
+    #[test_case("https://example.org", false ; "wildcard base domain")]
+    #[test_case("https://evilexample.org", false ; "wildcard suffix without dot")]
+    #[test_case("https://evil.com/.example.org", false ; "wildcard suffix after path")]
+    #[test_case("https://api.example.org:8443", false ; "subdomain with different port")]
+    #[test_case("http://api.example.org", false ; "subdomain with different scheme")]
+    #[test_case("https://other.com", false ; "unknown")]
+    fn allows_origin(origin: &str, expected: bool) {
+        let_assert!(
+            Ok(policy) = new()
+                .with_allowed_origins(vec!["https://app.example.com", "https://*.example.org"])
+                .build()
+        );
+
+        check!(policy.allows_origin(origin) == expected);
+    }
+
+    #[test]
+    fn default_allows_nothing() {
+        check!(!CorsPolicy::default().allows_origin("https://app.example.com"));
+    }
+
+    #[test]
+    fn permissive_allows_everything() {
+        check!(CorsPolicy::permissive().allows_origin("https://app.example.com"));
+    }
+
+    #[actix_rt::test]
+    async fn exposed_headers() {
+        use actix_web::{test, web, App, HttpResponse};
+
+        let_assert!(
+            Ok(policy) = new()
+                .with_allowed_origins(vec!["https://app.example.com"])
+                .with_exposed_headers(vec!["X-Custom"])
+                .build()
+        );
+
+        let mut app = test::init_service(
+            App::new()
+                .wrap(policy.build_middleware())
+                .default_service(web::route().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let response = test::call_service(
+            &mut app,
+            test::TestRequest::get()
+                .uri("/")
+                .header("origin", "https://app.example.com")
+                .to_request(),
+        )
+        .await;
+
+        let_assert!(Some(exposed) = response.headers().get("access-control-expose-headers"));
+        let mut exposed: Vec<String> = exposed
+            .to_str()
+            .unwrap()
+            .split(',')
+            .map(|header| header.trim().to_ascii_lowercase())
+            .collect();
+        exposed.sort();
+
+        check!(
+            exposed
+                == vec![
+                    "etag",
+                    "link",
+                    "location",
+                    "ratelimit-limit",
+                    "ratelimit-remaining",
+                    "ratelimit-reset",
+                    "retry-after",
+                    "x-custom",
+                    "x-request-id",
+                    "x-trace-id"
+                ]
+        );
+    }
+
+    #[test]
+    fn invalid_method() {
+        let result = new()
+            .with_allowed_methods(vec!["GET", "NOT A METHOD"])
+            .build();
+
+        check!(result.unwrap_err() == CorsPolicyError::InvalidMethod("NOT A METHOD".to_owned()));
+    }
+
+    #[test]
+    fn invalid_header() {
+        let result = new()
+            .with_allowed_headers(vec!["Authorization"])
+            .with_exposed_headers(vec!["X Custom"])
+            .build();
+
+        check!(result.unwrap_err() == CorsPolicyError::InvalidHeader("X Custom".to_owned()));
+    }
+}
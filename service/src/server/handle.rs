@@ -0,0 +1,184 @@
+use actix_web::dev::Server as ActixServer;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::net::SocketAddr;
+
+/// Handle to a running HTTP Server, allowing it to be stopped.
+#[derive(Clone)]
+pub struct ServerHandle {
+    /// The running Actix server.
+    server: ActixServer,
+    /// The address that the server is listening on.
+    address: SocketAddr,
+    /// The address that the server is listening on for plain HTTP requests to redirect to HTTPS, if any.
+    redirect_address: Option<SocketAddr>,
+    /// Future that resolves once the server has stopped.
+    stopped: Shared<BoxFuture<'static, ()>>,
+}
+
+impl ServerHandle {
+    /// Create a handle to a server that has just started running.
+    ///
+    /// # Parameters
+    /// - `server` - The running Actix server
+    /// - `address` - The address that the server is listening on
+    /// - `redirect_address` - The address that the server is listening on for requests to redirect to HTTPS, if any
+    pub(super) fn new(
+        server: ActixServer,
+        address: SocketAddr,
+        redirect_address: Option<SocketAddr>,
+    ) -> Self {
+        // Actix only notifies the waiters that registered before it was asked to stop, so register straight away by
+        // polling once rather than when somebody first waits.
+        let mut running = server.clone();
+        let _ = (&mut running).now_or_never();
+
+        let stopped = async move {
+            if let Err(e) = running.await {
+                tracing::error!(e = ?e, "HTTP server failed");
+            }
+        }
+        .boxed()
+        .shared();
+
+        Self {
+            server,
+            address,
+            redirect_address,
+            stopped,
+        }
+    }
+
+    /// Get the address that the server is listening on, which includes the actual port when the server was configured
+    /// to listen on port 0.
+    ///
+    /// # Returns
+    /// The address that the server is bound to.
+    #[must_use]
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Get the address that the server is listening on for plain HTTP requests to redirect to HTTPS.
+    ///
+    /// # Returns
+    /// The address that redirects are served from, if the server does redirect.
+    #[must_use]
+    pub fn redirect_address(&self) -> Option<SocketAddr> {
+        self.redirect_address
+    }
+
+    /// Stop the server.
+    ///
+    /// When stopping gracefully the server stops accepting new connections, and then waits for up to the shutdown
+    /// grace period for any in-flight requests to finish before closing the remaining connections.
+    ///
+    /// # Parameters
+    /// - `graceful` - Whether to drain the connections before stopping
+    pub async fn stop(&self, graceful: bool) {
+        tracing::info!(graceful = graceful, "Stopping HTTP server");
+        self.server.stop(graceful).await;
+        tracing::info!("Stopped HTTP server");
+    }
+
+    /// Wait for the server to stop, either because `stop` was called on a handle to it or because it failed.
+    pub async fn wait(&self) {
+        self.stopped.clone().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tls::testing::TestCertificate;
+    use assert2::{check, let_assert};
+    use std::time::Duration;
+
+    #[actix_rt::test]
+    async fn start_and_stop() {
+        let server = crate::server::component::new()
+            .build(0, prometheus::Registry::new())
+            .server
+            .start()
+            .unwrap();
+
+        let port = server.address().port();
+        check!(port != 0);
+
+        let url = format!("http://127.0.0.1:{}/unknown", port);
+        let_assert!(Ok(response) = reqwest::get(&url).await);
+        check!(response.status() == 404);
+
+        server.stop(true).await;
+        server.wait().await;
+
+        check!(reqwest::get(&url).await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn start_with_tls() {
+        let certificate = TestCertificate::generate();
+        let tls =
+            crate::server::tls::new(&certificate.certificate_path, &certificate.private_key_path)
+                .build()
+                .unwrap();
+
+        let server = crate::server::component::new()
+            .with_tls(tls)
+            .with_https_redirect(0, "localhost")
+            .with_hsts(Duration::from_secs(3600))
+            .build(0, prometheus::Registry::new())
+            .server
+            .start()
+            .unwrap();
+
+        let port = server.address().port();
+        let_assert!(Some(redirect_address) = server.redirect_address());
+
+        let client = reqwest::Client::builder()
+            .add_root_certificate(
+                reqwest::Certificate::from_pem(&certificate.certificate.to_pem().unwrap()).unwrap(),
+            )
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        let url = format!("https://localhost:{}/unknown", port);
+        let_assert!(Ok(response) = client.get(&url).send().await);
+        check!(response.status() == 404);
+        check!(response.headers().get("strict-transport-security").unwrap() == "max-age=3600");
+
+        let url = format!("http://localhost:{}/unknown?a=b", redirect_address.port());
+        let_assert!(Ok(response) = client.get(&url).send().await);
+        check!(response.status() == 308);
+        check!(
+            response.headers().get("location").unwrap()
+                == &format!("https://localhost:{}/unknown?a=b", port)
+        );
+
+        let_assert!(Ok(protocol) = actix_web::web::block(move || negotiate_protocol(port)).await);
+        check!(protocol == "h2");
+
+        server.stop(true).await;
+        server.wait().await;
+    }
+
+    /// Connect to the server over TLS and find out which protocol it chooses using ALPN.
+    fn negotiate_protocol(port: u16) -> Result<String, String> {
+        use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
+
+        let mut connector = SslConnector::builder(SslMethod::tls()).map_err(|e| e.to_string())?;
+        connector.set_verify(SslVerifyMode::NONE);
+        connector
+            .set_alpn_protos(b"\x02h2\x08http/1.1")
+            .map_err(|e| e.to_string())?;
+
+        let stream =
+            std::net::TcpStream::connect(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+        let stream = connector
+            .build()
+            .connect("localhost", stream)
+            .map_err(|e| e.to_string())?;
+
+        let protocol = stream.ssl().selected_alpn_protocol().unwrap_or_default();
+        Ok(String::from_utf8_lossy(protocol).into_owned())
+    }
+}
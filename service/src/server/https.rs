@@ -0,0 +1,213 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use actix_service::{Service, Transform};
+use actix_web::{
+    dev::{ServiceRequest, ServiceResponse},
+    http::{header, HeaderValue},
+    Error, HttpResponse,
+};
+use futures::future::{ok, Ready};
+use futures::Future;
+
+/// Middleware for redirecting requests that arrive over plain HTTP to HTTPS, and for telling clients to only use
+/// HTTPS in future by including a `Strict-Transport-Security` header on responses sent over HTTPS.
+#[derive(Clone)]
+pub struct HttpsRedirect {
+    /// The host name that clients use to reach the HTTPS server.
+    pub public_host: String,
+    /// The port that HTTPS is served on.
+    pub https_port: u16,
+    /// How long clients should remember to only use HTTPS for, if at all.
+    pub hsts_max_age: Option<Duration>,
+}
+
+impl<S, B> Transform<S> for HttpsRedirect
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = Middleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        let hsts = self
+            .hsts_max_age
+            .map(|max_age| format!("max-age={}", max_age.as_secs()))
+            .and_then(|hsts| HeaderValue::from_str(&hsts).ok());
+
+        ok(Middleware {
+            service,
+            public_host: self.public_host.clone(),
+            https_port: self.https_port,
+            hsts,
+        })
+    }
+}
+
+/// Actual middleware implementation.
+pub struct Middleware<S> {
+    service: S,
+    public_host: String,
+    https_port: u16,
+    hsts: Option<HeaderValue>,
+}
+
+impl<S, B> Service for Middleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        if !req.app_config().secure() {
+            let location = https_location(&req, &self.public_host, self.https_port);
+            tracing::debug!(location = ?location, "Redirecting to HTTPS");
+
+            let response = HttpResponse::PermanentRedirect()
+                .header(header::LOCATION, location)
+                .finish()
+                .into_body();
+            return Box::pin(ok(req.into_response(response)));
+        }
+
+        let hsts = self.hsts.clone();
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut response = fut.await?;
+
+            if let Some(hsts) = hsts {
+                response
+                    .headers_mut()
+                    .insert(header::STRICT_TRANSPORT_SECURITY, hsts);
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+/// Build the HTTPS URL for the same resource as the request.
+///
+/// The host always comes from the configuration rather than from the `Host` header of the request, since otherwise
+/// anyone able to set that header could redirect clients to a site of their choosing.
+///
+/// # Parameters
+/// - `req` - The request made over plain HTTP
+/// - `public_host` - The host name that clients use to reach the HTTPS server
+/// - `https_port` - The port that HTTPS is served on
+///
+/// # Returns
+/// The URL to redirect the client to.
+fn https_location(req: &ServiceRequest, public_host: &str, https_port: u16) -> String {
+    let path = req
+        .uri()
+        .path_and_query()
+        .map_or("/", |path_and_query| path_and_query.as_str());
+
+    if https_port == 443 {
+        format!("https://{}{}", public_host, path)
+    } else {
+        format!("https://{}:{}{}", public_host, https_port, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::tls::testing::TestCertificate;
+    use actix_web::{test, web, App};
+    use assert2::{check, let_assert};
+    use test_case::test_case;
+
+    #[test_case(443, "https://example.com/users/abc?q=1" ; "default port")]
+    #[test_case(8443, "https://example.com:8443/users/abc?q=1" ; "other port")]
+    #[actix_rt::test]
+    async fn redirect_plain_http(https_port: u16, expected: &str) {
+        let mut app = test::init_service(
+            App::new()
+                .wrap(HttpsRedirect {
+                    public_host: "example.com".to_owned(),
+                    https_port,
+                    hsts_max_age: Some(Duration::from_secs(3600)),
+                })
+                .route("/users/{id}", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let response = test::call_service(
+            &mut app,
+            test::TestRequest::get()
+                .uri("/users/abc?q=1")
+                .header(header::HOST, "attacker.example.org:8080")
+                .header("x-forwarded-host", "attacker.example.org")
+                .to_request(),
+        )
+        .await;
+
+        check!(response.status() == 308);
+        check!(response.headers().get(header::LOCATION).unwrap() == expected);
+        check!(response
+            .headers()
+            .get(header::STRICT_TRANSPORT_SECURITY)
+            .is_none());
+    }
+
+    #[actix_rt::test]
+    async fn secure_request_has_hsts() {
+        let certificate = TestCertificate::generate();
+        let tls =
+            crate::server::tls::new(&certificate.certificate_path, &certificate.private_key_path)
+                .build()
+                .unwrap();
+        let (acceptor, _) = tls.acceptor().unwrap();
+
+        let server = test::start_with(test::config().openssl(acceptor.build()), || {
+            App::new()
+                .wrap(HttpsRedirect {
+                    public_host: "example.com".to_owned(),
+                    https_port: 443,
+                    hsts_max_age: Some(Duration::from_secs(3600)),
+                })
+                .route("/users/{id}", web::get().to(HttpResponse::Ok))
+        });
+
+        let client = reqwest::Client::builder()
+            .add_root_certificate(
+                reqwest::Certificate::from_pem(&certificate.certificate.to_pem().unwrap()).unwrap(),
+            )
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+
+        let url = format!("https://localhost:{}/users/abc", server.addr().port());
+        let_assert!(Ok(response) = client.get(&url).send().await);
+
+        check!(response.status() == 200);
+        check!(response.headers().get(header::LOCATION).is_none());
+        check!(
+            response
+                .headers()
+                .get(header::STRICT_TRANSPORT_SECURITY)
+                .unwrap()
+                == "max-age=3600"
+        );
+    }
+}
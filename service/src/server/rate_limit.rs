@@ -0,0 +1,398 @@
+mod bucket;
+mod client;
+mod middleware;
+mod problems;
+
+pub use bucket::{RateLimitStatus, RATELIMIT_LIMIT, RATELIMIT_REMAINING, RATELIMIT_RESET};
+pub use client::ClientKey;
+pub use middleware::RateLimit;
+pub use problems::*;
+
+use actix_web::dev::ServiceRequest;
+use bucket::{Limit, TokenBucket};
+use client::IpNetwork;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    net::IpAddr,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// The number of buckets that can be tracked before some are discarded, to stop memory growing without bound.
+const MAX_TRACKED_BUCKETS: usize = 10_000;
+
+/// The number of buckets that are kept when some are discarded. Discarding down to this rather than to just below
+/// the maximum means that the buckets are only scanned once in a while, rather than on every new client.
+const RETAINED_BUCKETS: usize = MAX_TRACKED_BUCKETS * 3 / 4;
+
+/// Errors that can occur when configuring rate limits.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum RateLimitError {
+    #[error("The rate limit '{0}' is not of the form `/prefix=requests/seconds`")]
+    InvalidLimit(String),
+
+    #[error("The trusted proxy '{0}' is not a valid IP address or network")]
+    InvalidProxy(String),
+}
+
+/// A group of routes that share a rate limit, identified by the prefix of their paths.
+#[derive(Debug, Clone, PartialEq)]
+struct RouteGroup {
+    /// The prefix of the paths in the group, which always matches whole path segments.
+    prefix: String,
+    /// The rate limit for each client.
+    limit: Limit,
+}
+
+impl FromStr for RouteGroup {
+    type Err = RateLimitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RateLimitError::InvalidLimit(s.to_owned());
+
+        let mut parts = s.trim().splitn(2, '=');
+        let prefix = parts.next().unwrap_or_default().trim();
+        let mut limit = parts.next().ok_or_else(invalid)?.splitn(2, '/');
+        let requests: u32 = limit
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .parse()
+            .map_err(|_| invalid())?;
+        let seconds: u64 = limit
+            .next()
+            .ok_or_else(invalid)?
+            .trim()
+            .parse()
+            .map_err(|_| invalid())?;
+
+        if !prefix.starts_with('/') || requests == 0 || seconds == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            prefix: prefix.trim_end_matches('/').to_owned(),
+            limit: Limit {
+                requests,
+                period: Duration::from_secs(seconds),
+            },
+        })
+    }
+}
+
+impl RouteGroup {
+    /// Check whether a path is in this group.
+    ///
+    /// # Parameters
+    /// - `path` - The path of the request
+    ///
+    /// # Returns
+    /// True if the path is in the group. False if not.
+    fn matches(&self, path: &str) -> bool {
+        match path.strip_prefix(self.prefix.as_str()) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
+
+/// Applies token-bucket rate limits to clients, with a separate limit for each group of routes.
+///
+/// Requests to paths that aren't in any group aren't limited at all.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    /// The groups of routes, with the longest prefixes first so that the most specific group is used.
+    groups: Vec<RouteGroup>,
+    /// The networks of the proxies that are trusted to report the client address.
+    trusted_proxies: Vec<IpNetwork>,
+    /// The bucket for each client in each group, keyed by the index of the group.
+    buckets: Mutex<HashMap<(usize, ClientKey), TokenBucket>>,
+}
+
+/// Builder to help build a `RateLimiter`.
+#[derive(Default)]
+pub struct Builder {
+    limits: Vec<String>,
+    trusted_proxies: Vec<String>,
+}
+
+/// Create a new builder to build a rate limiter with.
+pub fn new() -> Builder {
+    Builder::default()
+}
+
+impl Builder {
+    /// Add rate limits for some groups of routes.
+    ///
+    /// # Parameters
+    /// - `limits` - The limits, each of the form `/prefix=requests/seconds`. For example, `/users=30/60` allows each
+    ///   client 30 requests every 60 seconds to paths starting `/users`. The prefix `/` matches every path.
+    pub fn with_limits<I, S>(mut self, limits: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.limits.extend(limits.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Trust some proxies to report the IP addresses of the clients that they forward requests for.
+    ///
+    /// # Parameters
+    /// - `trusted_proxies` - The IP addresses or networks of the proxies, such as `10.0.0.0/8`
+    pub fn with_trusted_proxies<I, S>(mut self, trusted_proxies: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.trusted_proxies
+            .extend(trusted_proxies.into_iter().map(Into::into));
+
+        self
+    }
+
+    /// Actually build the rate limiter, checking that the configuration is valid.
+    ///
+    /// # Returns
+    /// The rate limiter.
+    ///
+    /// # Errors
+    /// If any of the limits or trusted proxies are invalid.
+    pub fn build(self) -> Result<RateLimiter, RateLimitError> {
+        let mut groups = self
+            .limits
+            .iter()
+            .map(|limit| limit.parse())
+            .collect::<Result<Vec<RouteGroup>, _>>()?;
+        groups.sort_by_key(|group| Reverse(group.prefix.len()));
+
+        let trusted_proxies = self
+            .trusted_proxies
+            .iter()
+            .map(|proxy| proxy.parse())
+            .collect::<Result<_, _>>()?;
+
+        Ok(RateLimiter {
+            groups,
+            trusted_proxies,
+            buckets: Mutex::new(HashMap::new()),
+        })
+    }
+}
+
+impl RateLimiter {
+    /// Find the group of routes that a path is in.
+    ///
+    /// # Parameters
+    /// - `path` - The path of the request
+    ///
+    /// # Returns
+    /// The index of the group, if the path is in one.
+    fn group(&self, path: &str) -> Option<usize> {
+        self.groups.iter().position(|group| group.matches(path))
+    }
+
+    /// Determine the IP address of the client that made a request.
+    ///
+    /// # Parameters
+    /// - `req` - The request
+    ///
+    /// # Returns
+    /// The IP address of the client.
+    fn client_ip(&self, req: &ServiceRequest) -> IpAddr {
+        client::client_ip(req.peer_addr(), req.headers(), &self.trusted_proxies)
+    }
+
+    /// Check whether a client could make a request to a group of routes, without counting it against their limit.
+    ///
+    /// # Parameters
+    /// - `group` - The index of the group of routes
+    /// - `key` - The client
+    /// - `now` - The current time
+    ///
+    /// # Returns
+    /// Whether the request would be allowed, and the state of the client's limit.
+    fn peek(&self, group: usize, key: &ClientKey, now: Instant) -> RateLimitStatus {
+        let limit = &self.groups[group].limit;
+        let mut buckets = self.buckets.lock().unwrap();
+
+        match buckets.get_mut(&(group, key.clone())) {
+            Some(bucket) => bucket.peek(limit, now),
+            None => TokenBucket::new(limit, now).peek(limit, now),
+        }
+    }
+
+    /// Count a request by a client to a group of routes against their limit.
+    ///
+    /// # Parameters
+    /// - `group` - The index of the group of routes
+    /// - `key` - The client
+    /// - `now` - The current time
+    ///
+    /// # Returns
+    /// Whether the request is allowed, and the state of the client's limit afterwards.
+    fn take(&self, group: usize, key: ClientKey, now: Instant) -> RateLimitStatus {
+        let limit = &self.groups[group].limit;
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= MAX_TRACKED_BUCKETS {
+            self.discard(&mut buckets, now);
+        }
+
+        buckets
+            .entry((group, key))
+            .or_insert_with(|| TokenBucket::new(limit, now))
+            .take(limit, now)
+    }
+
+    /// Discard buckets until no more than `RETAINED_BUCKETS` remain.
+    ///
+    /// Buckets that have been idle for long enough to refill behave exactly like new ones, so these are discarded
+    /// first. If that isn't enough then the least recently used buckets are discarded as well, which gives those
+    /// clients their full allowance back early but keeps the memory used bounded.
+    ///
+    /// # Parameters
+    /// - `buckets` - The buckets to discard from
+    /// - `now` - The current time
+    fn discard(&self, buckets: &mut HashMap<(usize, ClientKey), TokenBucket>, now: Instant) {
+        let groups = &self.groups;
+        buckets.retain(|(group, _), bucket| !bucket.is_full(&groups[*group].limit, now));
+
+        if buckets.len() > RETAINED_BUCKETS {
+            let excess = buckets.len() - RETAINED_BUCKETS;
+            let mut by_use: Vec<_> = buckets
+                .iter()
+                .map(|(key, bucket)| (bucket.last_used(), key.clone()))
+                .collect();
+            by_use.select_nth_unstable_by_key(excess - 1, |(last_used, _)| *last_used);
+
+            for (_, key) in by_use.drain(..excess) {
+                buckets.remove(&key);
+            }
+            tracing::warn!(
+                discarded = excess,
+                "Discarded rate limit buckets that were still in use"
+            );
+        }
+
+        tracing::debug!(remaining = buckets.len(), "Discarded rate limit buckets");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorization::Principal;
+    use assert2::{check, let_assert};
+    use std::net::Ipv4Addr;
+    use test_case::test_case;
+
+    #[test_case("/=120/60", "", 120, 60 ; "everything")]
+    #[test_case("/users=30/60", "/users", 30, 60 ; "prefix")]
+    #[test_case(" /users/ = 30 / 60 ", "/users", 30, 60 ; "whitespace and trailing slash")]
+    fn parse_group(input: &str, prefix: &str, requests: u32, seconds: u64) {
+        let_assert!(Ok(group) = input.parse::<RouteGroup>());
+
+        check!(group.prefix == prefix);
+        check!(group.limit.requests == requests);
+        check!(group.limit.period == Duration::from_secs(seconds));
+    }
+
+    #[test_case("" ; "blank")]
+    #[test_case("/users" ; "no limit")]
+    #[test_case("users=30/60" ; "relative prefix")]
+    #[test_case("/users=30" ; "no period")]
+    #[test_case("/users=0/60" ; "no requests")]
+    #[test_case("/users=30/0" ; "no time")]
+    #[test_case("/users=many/60" ; "not a number")]
+    fn parse_invalid_group(input: &str) {
+        let_assert!(Err(RateLimitError::InvalidLimit(invalid)) = input.parse::<RouteGroup>());
+        check!(invalid == input);
+    }
+
+    #[test_case("/users/abc", Some(0) ; "most specific group")]
+    #[test_case("/users", Some(0) ; "exact prefix")]
+    #[test_case("/usersettings", Some(1) ; "partial segment")]
+    #[test_case("/", Some(1) ; "root")]
+    fn find_group(path: &str, expected: Option<usize>) {
+        let_assert!(Ok(limiter) = new().with_limits(vec!["/=120/60", "/users=30/60"]).build());
+
+        check!(limiter.group(path) == expected);
+    }
+
+    #[test]
+    fn no_groups() {
+        check!(RateLimiter::default().group("/users").is_none());
+    }
+
+    #[test]
+    fn invalid_proxy() {
+        let result = new().with_trusted_proxies(vec!["proxy"]).build();
+
+        check!(result.unwrap_err() == RateLimitError::InvalidProxy("proxy".to_owned()));
+    }
+
+    #[test]
+    fn clients_are_limited_separately() {
+        let_assert!(Ok(limiter) = new().with_limits(vec!["/=1/60"]).build());
+        let now = Instant::now();
+
+        let user = ClientKey::Principal(Principal::User("abc".to_owned()));
+        let ip = ClientKey::Ip("203.0.113.1".parse().unwrap());
+
+        check!(limiter.take(0, user.clone(), now).allowed);
+        check!(!limiter.peek(0, &user, now).allowed);
+        check!(!limiter.take(0, user, now).allowed);
+
+        check!(limiter.peek(0, &ip, now).allowed);
+        check!(limiter.take(0, ip, now).allowed);
+    }
+
+    #[test]
+    fn full_buckets_are_discarded() {
+        let_assert!(Ok(limiter) = new().with_limits(vec!["/=1/60"]).build());
+        let now = Instant::now();
+
+        for i in (0_u32..).take(MAX_TRACKED_BUCKETS) {
+            limiter.take(
+                0,
+                ClientKey::Ip(Ipv4Addr::from(0x0a00_0000 | i).into()),
+                now,
+            );
+        }
+        check!(limiter.buckets.lock().unwrap().len() == MAX_TRACKED_BUCKETS);
+
+        let later = now + Duration::from_secs(60);
+        limiter.take(0, ClientKey::Ip("203.0.113.1".parse().unwrap()), later);
+        check!(limiter.buckets.lock().unwrap().len() == 1);
+    }
+
+    #[test]
+    fn least_recently_used_buckets_are_discarded() {
+        let_assert!(Ok(limiter) = new().with_limits(vec!["/=1/60"]).build());
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+
+        let oldest = ClientKey::Ip("203.0.113.1".parse().unwrap());
+        limiter.take(0, oldest.clone(), now);
+        for i in (1_u32..).take(MAX_TRACKED_BUCKETS - 1) {
+            limiter.take(
+                0,
+                ClientKey::Ip(Ipv4Addr::from(0x0a00_0000 | i).into()),
+                later,
+            );
+        }
+        check!(limiter.buckets.lock().unwrap().len() == MAX_TRACKED_BUCKETS);
+
+        let newest = ClientKey::Ip("203.0.113.2".parse().unwrap());
+        limiter.take(0, newest.clone(), later);
+
+        let buckets = limiter.buckets.lock().unwrap();
+        check!(buckets.len() == RETAINED_BUCKETS + 1);
+        check!(!buckets.contains_key(&(0, oldest)));
+        check!(buckets.contains_key(&(0, newest)));
+    }
+}
@@ -0,0 +1,281 @@
+use actix_web::http::{HeaderMap, HeaderName, HeaderValue};
+use std::time::{Duration, Instant};
+
+/// The `RateLimit-Limit` header, giving the number of requests that the client may make.
+pub const RATELIMIT_LIMIT: &str = "ratelimit-limit";
+
+/// The `RateLimit-Remaining` header, giving the number of requests that the client has left.
+pub const RATELIMIT_REMAINING: &str = "ratelimit-remaining";
+
+/// The `RateLimit-Reset` header, giving the number of seconds until the client has its full allowance again.
+pub const RATELIMIT_RESET: &str = "ratelimit-reset";
+
+/// A rate limit, allowing a number of requests in each period of time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limit {
+    /// The number of requests allowed in each period, which is also the largest burst of requests allowed.
+    pub requests: u32,
+    /// The period of time.
+    pub period: Duration,
+}
+
+impl Limit {
+    /// Get the number of tokens that refill in a period of time.
+    ///
+    /// # Parameters
+    /// - `elapsed` - The period of time
+    ///
+    /// # Returns
+    /// The number of tokens.
+    fn tokens_in(&self, elapsed: Duration) -> f64 {
+        elapsed.as_secs_f64() * f64::from(self.requests) / self.period.as_secs_f64()
+    }
+
+    /// Get the time that it takes for a number of tokens to refill.
+    ///
+    /// # Parameters
+    /// - `tokens` - The number of tokens
+    ///
+    /// # Returns
+    /// The time that it takes.
+    fn time_for(&self, tokens: f64) -> Duration {
+        Duration::from_secs_f64(
+            self.period.as_secs_f64() * tokens.max(0.0) / f64::from(self.requests),
+        )
+    }
+}
+
+/// The outcome of checking a request against a rate limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitStatus {
+    /// Whether the request is allowed.
+    pub allowed: bool,
+    /// The number of requests allowed in each period.
+    pub limit: u32,
+    /// The number of requests that the client can make straight away.
+    pub remaining: u32,
+    /// The time until the client has its full allowance again.
+    pub reset: Duration,
+    /// The time until the client can make another request.
+    pub retry_after: Duration,
+}
+
+impl RateLimitStatus {
+    /// Add the `RateLimit-*` headers describing this status to a response.
+    ///
+    /// # Parameters
+    /// - `headers` - The headers of the response
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        headers.insert(
+            HeaderName::from_static(RATELIMIT_LIMIT),
+            HeaderValue::from(self.limit),
+        );
+        headers.insert(
+            HeaderName::from_static(RATELIMIT_REMAINING),
+            HeaderValue::from(self.remaining),
+        );
+        headers.insert(
+            HeaderName::from_static(RATELIMIT_RESET),
+            HeaderValue::from(whole_seconds(self.reset)),
+        );
+    }
+}
+
+/// Convert a duration into a number of seconds for use in a header, rounding up so that clients never retry too soon.
+///
+/// # Parameters
+/// - `duration` - The duration to convert
+///
+/// # Returns
+/// The number of seconds.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn whole_seconds(duration: Duration) -> u64 {
+    duration.as_secs_f64().ceil() as u64
+}
+
+/// A token bucket tracking how many requests one client can make. The bucket holds up to one token per request
+/// allowed in the period, refills continuously over the period, and each request takes one token.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    /// The tokens in the bucket, including partially refilled ones.
+    tokens: f64,
+    /// When the tokens were last updated, which is also when the bucket was last used.
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// Create a new, full bucket.
+    ///
+    /// # Parameters
+    /// - `limit` - The limit that the bucket applies
+    /// - `now` - The current time
+    pub fn new(limit: &Limit, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(limit.requests),
+            updated: now,
+        }
+    }
+
+    /// Add the tokens that have refilled since the bucket was last updated.
+    ///
+    /// # Parameters
+    /// - `limit` - The limit that the bucket applies
+    /// - `now` - The current time
+    fn refill(&mut self, limit: &Limit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens = (self.tokens + limit.tokens_in(elapsed)).min(f64::from(limit.requests));
+        self.updated = now;
+    }
+
+    /// Check whether a request would be allowed, without taking a token for it.
+    ///
+    /// # Parameters
+    /// - `limit` - The limit that the bucket applies
+    /// - `now` - The current time
+    ///
+    /// # Returns
+    /// Whether the request would be allowed, and the state of the bucket.
+    pub fn peek(&mut self, limit: &Limit, now: Instant) -> RateLimitStatus {
+        self.refill(limit, now);
+        self.status(limit, self.tokens >= 1.0)
+    }
+
+    /// Take a token for a request, if there is one.
+    ///
+    /// # Parameters
+    /// - `limit` - The limit that the bucket applies
+    /// - `now` - The current time
+    ///
+    /// # Returns
+    /// Whether the request is allowed, and the state of the bucket afterwards.
+    pub fn take(&mut self, limit: &Limit, now: Instant) -> RateLimitStatus {
+        self.refill(limit, now);
+
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+
+        self.status(limit, allowed)
+    }
+
+    /// Check whether the bucket is full, in which case it behaves exactly like a new one and doesn't need tracking.
+    /// This is the case once it has been idle for long enough to refill.
+    ///
+    /// # Parameters
+    /// - `limit` - The limit that the bucket applies
+    /// - `now` - The current time
+    ///
+    /// # Returns
+    /// True if the bucket is full. False if not.
+    pub fn is_full(&self, limit: &Limit, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens + limit.tokens_in(elapsed) >= f64::from(limit.requests)
+    }
+
+    /// Get when the bucket was last used.
+    ///
+    /// # Returns
+    /// The time of the last request checked against the bucket.
+    pub fn last_used(&self) -> Instant {
+        self.updated
+    }
+
+    /// Describe the current state of the bucket.
+    ///
+    /// # Parameters
+    /// - `limit` - The limit that the bucket applies
+    /// - `allowed` - Whether the request is allowed
+    ///
+    /// # Returns
+    /// The status of the bucket.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn status(&self, limit: &Limit, allowed: bool) -> RateLimitStatus {
+        RateLimitStatus {
+            allowed,
+            limit: limit.requests,
+            remaining: self.tokens.floor() as u32,
+            reset: limit.time_for(f64::from(limit.requests) - self.tokens),
+            retry_after: limit.time_for(1.0 - self.tokens),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::check;
+
+    const LIMIT: Limit = Limit {
+        requests: 2,
+        period: Duration::from_secs(10),
+    };
+
+    #[test]
+    fn take_until_empty() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(&LIMIT, now);
+
+        let first = bucket.take(&LIMIT, now);
+        check!(first.allowed);
+        check!(first.remaining == 1);
+        check!(first.reset == Duration::from_secs(5));
+
+        let second = bucket.take(&LIMIT, now);
+        check!(second.allowed);
+        check!(second.remaining == 0);
+        check!(second.reset == Duration::from_secs(10));
+
+        let third = bucket.take(&LIMIT, now);
+        check!(!third.allowed);
+        check!(third.remaining == 0);
+        check!(third.retry_after == Duration::from_secs(5));
+    }
+
+    #[test]
+    fn refill_over_time() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(&LIMIT, now);
+        bucket.take(&LIMIT, now);
+        bucket.take(&LIMIT, now);
+
+        check!(!bucket.peek(&LIMIT, now + Duration::from_secs(4)).allowed);
+
+        let status = bucket.take(&LIMIT, now + Duration::from_secs(5));
+        check!(status.allowed);
+        check!(status.remaining == 0);
+
+        check!(!bucket.is_full(&LIMIT, now + Duration::from_secs(10)));
+        check!(bucket.is_full(&LIMIT, now + Duration::from_secs(15)));
+        check!(bucket.last_used() == now + Duration::from_secs(5));
+    }
+
+    #[test]
+    fn peek_does_not_take() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(&LIMIT, now);
+
+        let status = bucket.peek(&LIMIT, now);
+        check!(status.allowed);
+        check!(status.remaining == 2);
+        check!(bucket.is_full(&LIMIT, now));
+    }
+
+    #[test]
+    fn headers() {
+        let status = RateLimitStatus {
+            allowed: true,
+            limit: 10,
+            remaining: 3,
+            reset: Duration::from_millis(4500),
+            retry_after: Duration::from_secs(0),
+        };
+
+        let mut headers = HeaderMap::new();
+        status.apply(&mut headers);
+
+        check!(headers.get("ratelimit-limit").unwrap() == "10");
+        check!(headers.get("ratelimit-remaining").unwrap() == "3");
+        check!(headers.get("ratelimit-reset").unwrap() == "5");
+    }
+}
@@ -0,0 +1,274 @@
+use super::RateLimitError;
+use crate::authorization::Principal;
+use actix_web::http::{header, HeaderMap};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    str::FromStr,
+};
+
+/// The header that standard proxies use to record the clients that they forward requests for.
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// The identity of a client that rate limits are applied to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientKey {
+    /// An authenticated principal, wherever they make requests from.
+    Principal(Principal),
+    /// An unauthenticated client, identified by their IP address. IPv6 clients are identified by their /64 network
+    /// instead, since each one usually has a whole /64 to themselves and could otherwise pick a new address for every
+    /// request.
+    Ip(IpAddr),
+}
+
+impl ClientKey {
+    /// Create the key for an unauthenticated client.
+    ///
+    /// # Parameters
+    /// - `ip` - The IP address of the client
+    ///
+    /// # Returns
+    /// The key, covering the whole /64 network for IPv6 addresses.
+    pub fn ip(ip: IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(_) => Self::Ip(ip),
+            IpAddr::V6(ip) => Self::Ip(IpAddr::V6(Ipv6Addr::from(
+                u128::from(ip) & 0xffff_ffff_ffff_ffff_0000_0000_0000_0000,
+            ))),
+        }
+    }
+}
+
+/// A network of IP addresses, such as `10.0.0.0/8`, or a single IP address.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpNetwork {
+    /// The address of the network.
+    address: IpAddr,
+    /// The number of leading bits of the address that identify the network.
+    prefix: u32,
+}
+
+impl FromStr for IpNetwork {
+    type Err = RateLimitError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RateLimitError::InvalidProxy(s.to_owned());
+
+        let mut parts = s.trim().splitn(2, '/');
+        let address: IpAddr = parts
+            .next()
+            .unwrap_or_default()
+            .parse()
+            .map_err(|_| invalid())?;
+        let max_prefix = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        let prefix = match parts.next() {
+            Some(prefix) => prefix.parse().map_err(|_| invalid())?,
+            None => max_prefix,
+        };
+
+        if prefix > max_prefix {
+            return Err(invalid());
+        }
+
+        Ok(Self { address, prefix })
+    }
+}
+
+impl IpNetwork {
+    /// Check whether an IP address is in this network.
+    ///
+    /// # Parameters
+    /// - `ip` - The IP address to check
+    ///
+    /// # Returns
+    /// True if the address is in the network. False if not.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.address, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix).unwrap_or(0);
+                u32::from(network) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix).unwrap_or(0);
+                u128::from(network) & mask == u128::from(*ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Determine the IP address of the client that made a request.
+///
+/// If the request came directly from a trusted proxy then the headers that it added are used to find the client. The
+/// proxies each append the address that they received the request from, so these are followed back from the most
+/// recent until one that isn't a trusted proxy is found. Anything before that could have been made up by the client.
+///
+/// # Parameters
+/// - `peer` - The address that the request was received from, if known
+/// - `headers` - The headers of the request
+/// - `trusted_proxies` - The networks of the proxies that are trusted to report the client address
+///
+/// # Returns
+/// The IP address of the client.
+pub fn client_ip(
+    peer: Option<SocketAddr>,
+    headers: &HeaderMap,
+    trusted_proxies: &[IpNetwork],
+) -> IpAddr {
+    let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|network| network.contains(ip));
+
+    let peer = peer.map_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED), |peer| peer.ip());
+    if !is_trusted(&peer) {
+        return peer;
+    }
+
+    let forwarded = forwarded_for(headers);
+    match forwarded.iter().rev().find(|ip| !is_trusted(ip)) {
+        Some(ip) => *ip,
+        None => forwarded.first().copied().unwrap_or(peer),
+    }
+}
+
+/// Get the chain of client addresses recorded by proxies, from the standard `Forwarded` header if present or else
+/// from the `X-Forwarded-For` header. Entries that aren't IP addresses, such as obfuscated identifiers, are skipped.
+///
+/// # Parameters
+/// - `headers` - The headers of the request
+///
+/// # Returns
+/// The addresses, starting with the original client.
+fn forwarded_for(headers: &HeaderMap) -> Vec<IpAddr> {
+    let values = |name| {
+        headers
+            .get_all(name)
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect::<Vec<_>>()
+    };
+
+    let forwarded = values(header::FORWARDED.as_str());
+    if forwarded.is_empty() {
+        values(X_FORWARDED_FOR)
+            .into_iter()
+            .filter_map(parse_node)
+            .collect()
+    } else {
+        forwarded
+            .into_iter()
+            .filter_map(|element| {
+                element.split(';').map(str::trim).find_map(|pair| {
+                    let mut parts = pair.splitn(2, '=');
+                    match (parts.next(), parts.next()) {
+                        (Some(name), Some(value)) if name.eq_ignore_ascii_case("for") => {
+                            Some(value)
+                        }
+                        _ => None,
+                    }
+                })
+            })
+            .filter_map(parse_node)
+            .collect()
+    }
+}
+
+/// Parse a single node from a forwarding header, which may be quoted and may include a port.
+///
+/// # Parameters
+/// - `node` - The node to parse
+///
+/// # Returns
+/// The IP address of the node, if it is one.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim_matches('"');
+
+    node.parse::<IpAddr>()
+        .or_else(|_| node.parse::<SocketAddr>().map(|address| address.ip()))
+        .or_else(|_| {
+            node.trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<IpAddr>()
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::HeaderValue;
+    use assert2::{check, let_assert};
+    use test_case::test_case;
+
+    #[test_case("203.0.113.1", "203.0.113.1" ; "IPv4")]
+    #[test_case("2001:db8:1:2:3:4:5:6", "2001:db8:1:2::" ; "IPv6")]
+    #[test_case("::ffff:203.0.113.1", "::" ; "IPv4-mapped IPv6")]
+    fn client_key_from_ip(ip: &str, expected: &str) {
+        check!(ClientKey::ip(ip.parse().unwrap()) == ClientKey::Ip(expected.parse().unwrap()));
+    }
+
+    #[test_case("10.0.0.0/8", "10.1.2.3", true ; "IPv4 network member")]
+    #[test_case("10.0.0.0/8", "11.1.2.3", false ; "IPv4 network non-member")]
+    #[test_case("10.1.2.3", "10.1.2.3", true ; "IPv4 address match")]
+    #[test_case("10.1.2.3", "10.1.2.4", false ; "IPv4 address mismatch")]
+    #[test_case("0.0.0.0/0", "192.168.0.1", true ; "IPv4 everything")]
+    #[test_case("fd00::/8", "fd12::1", true ; "IPv6 network member")]
+    #[test_case("fd00::/8", "fe80::1", false ; "IPv6 network non-member")]
+    #[test_case("10.0.0.0/8", "::1", false ; "mixed families")]
+    fn network_contains(network: &str, ip: &str, expected: bool) {
+        let_assert!(Ok(network) = network.parse::<IpNetwork>());
+
+        check!(network.contains(&ip.parse().unwrap()) == expected);
+    }
+
+    #[test_case("" ; "blank")]
+    #[test_case("localhost" ; "hostname")]
+    #[test_case("10.0.0.0/33" ; "prefix too long")]
+    #[test_case("10.0.0.0/x" ; "prefix not a number")]
+    fn invalid_network(network: &str) {
+        let_assert!(Err(RateLimitError::InvalidProxy(invalid)) = network.parse::<IpNetwork>());
+        check!(invalid == network);
+    }
+
+    #[test_case(None, "10.0.0.1" ; "no headers")]
+    #[test_case(Some(("x-forwarded-for", "203.0.113.1")), "203.0.113.1" ; "X-Forwarded-For")]
+    #[test_case(Some(("x-forwarded-for", "198.51.100.1, 203.0.113.1, 10.0.0.2")), "203.0.113.1" ; "X-Forwarded-For through several proxies")]
+    #[test_case(Some(("x-forwarded-for", "10.0.0.3, 10.0.0.2")), "10.0.0.3" ; "X-Forwarded-For only proxies")]
+    #[test_case(Some(("forwarded", "for=198.51.100.1, for=\"[2001:db8::1]:4711\";proto=https")), "2001:db8::1" ; "Forwarded")]
+    #[test_case(Some(("forwarded", "for=203.0.113.1:8080, for=unknown")), "203.0.113.1" ; "Forwarded with unknown node")]
+    fn client_ip_from_trusted_proxy(header: Option<(&'static str, &'static str)>, expected: &str) {
+        let mut headers = HeaderMap::new();
+        if let Some((name, value)) = header {
+            headers.insert(
+                actix_web::http::HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+
+        let ip = client_ip(
+            Some("10.0.0.1:1234".parse().unwrap()),
+            &headers,
+            &["10.0.0.0/8".parse().unwrap()],
+        );
+
+        check!(ip == expected.parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn client_ip_from_untrusted_peer() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            actix_web::http::HeaderName::from_static(X_FORWARDED_FOR),
+            HeaderValue::from_static("203.0.113.1"),
+        );
+
+        let ip = client_ip(
+            Some("198.51.100.1:1234".parse().unwrap()),
+            &headers,
+            &["10.0.0.0/8".parse().unwrap()],
+        );
+
+        check!(ip == "198.51.100.1".parse::<IpAddr>().unwrap());
+    }
+}
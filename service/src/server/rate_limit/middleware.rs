@@ -0,0 +1,319 @@
+use super::{ClientKey, RateLimitStatus, RateLimiter, TOO_MANY_REQUESTS};
+use crate::{
+    authorization::Authorization, http::problem::Problem, server::rate_limit::bucket::whole_seconds,
+};
+use actix_service::{Service, Transform};
+use actix_web::{
+    dev::{ServiceRequest, ServiceResponse},
+    error::ErrorInternalServerError,
+    http::{header, HeaderValue},
+    Error, FromRequest,
+};
+use futures::future::{ok, Ready};
+use futures::Future;
+use std::{
+    cell::RefCell,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+/// Middleware for applying rate limits to requests.
+///
+/// Requests with a valid access token are limited by the principal that they are for, and all other requests are
+/// limited by the IP address of the client. Every limited response includes the `RateLimit-*` headers, and requests
+/// over the limit are rejected with a `429 Too Many Requests` Problem.
+#[derive(Clone)]
+pub struct RateLimit(pub Arc<RateLimiter>);
+
+impl<S, B> Transform<S> for RateLimit
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = Middleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(Middleware {
+            service: Rc::new(RefCell::new(service)),
+            limiter: self.0.clone(),
+        })
+    }
+}
+
+/// Actual middleware implementation.
+pub struct Middleware<S> {
+    service: Rc<RefCell<S>>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, B> Service for Middleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.borrow_mut().poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        // The decoded path is used, the same as for routing, so that encoding the path can't avoid the limits.
+        let group = match self.limiter.group(req.match_info().path()) {
+            Some(group) => group,
+            None => return Box::pin(self.service.borrow_mut().call(req)),
+        };
+
+        let service = self.service.clone();
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            let ip = ClientKey::ip(limiter.client_ip(&req));
+
+            let (req, key) = if req.headers().contains_key(header::AUTHORIZATION) {
+                // Parsing the token can mean fetching keys from Auth0, so clients that are already over their limit
+                // are turned away first. Invalid tokens count against the IP address, so they can't be used to
+                // avoid this.
+                let status = limiter.peek(group, &ip, Instant::now());
+                if !status.allowed {
+                    return Ok(too_many_requests(req, &status));
+                }
+
+                let (http_req, payload) = req.into_parts();
+                let key = match Authorization::extract(&http_req).await {
+                    Ok(Authorization::Authorized(security_context)) => {
+                        ClientKey::Principal(security_context.principal)
+                    }
+                    _ => ip,
+                };
+                let req = ServiceRequest::from_parts(http_req, payload).map_err(|_| {
+                    ErrorInternalServerError("Request was retained while rate limiting")
+                })?;
+
+                (req, key)
+            } else {
+                (req, ip)
+            };
+
+            let status = limiter.take(group, key, Instant::now());
+            if !status.allowed {
+                return Ok(too_many_requests(req, &status));
+            }
+
+            let fut = service.borrow_mut().call(req);
+            let mut response = fut.await?;
+            status.apply(response.headers_mut());
+
+            Ok(response)
+        })
+    }
+}
+
+/// Build the response for a request that is over its rate limit.
+///
+/// # Parameters
+/// - `req` - The request
+/// - `status` - The status of the rate limit that the request is over
+///
+/// # Returns
+/// The `429 Too Many Requests` Problem response.
+fn too_many_requests<B>(req: ServiceRequest, status: &RateLimitStatus) -> ServiceResponse<B> {
+    tracing::debug!(status = ?status, "Request is over the rate limit");
+
+    let (req, _) = req.into_parts();
+    let mut response = Problem::from(TOO_MANY_REQUESTS).render(&req);
+    status.apply(response.headers_mut());
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(whole_seconds(status.retry_after).max(1)),
+    );
+
+    ServiceResponse::new(req, response.into_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::RouteConfigurer;
+    use actix_web::{test, web, App, HttpResponse};
+    use assert2::check;
+
+    async fn send(
+        limiter: &Arc<RateLimiter>,
+        requests: Vec<test::TestRequest>,
+    ) -> Vec<ServiceResponse> {
+        let authorization =
+            crate::authorization::component::new("https://example.auth0.com", "testAudience");
+
+        let mut app = test::init_service(
+            App::new()
+                .configure(|config| authorization.configure_routes(config))
+                .wrap(RateLimit(limiter.clone()))
+                .default_service(web::route().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let mut responses = vec![];
+        for request in requests {
+            responses.push(test::call_service(&mut app, request.to_request()).await);
+        }
+
+        responses
+    }
+
+    fn limiter(limits: Vec<&str>) -> Arc<RateLimiter> {
+        Arc::new(
+            crate::server::rate_limit::new()
+                .with_limits(limits)
+                .with_trusted_proxies(vec!["10.0.0.0/8"])
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn request(uri: &str, peer: &str) -> test::TestRequest {
+        test::TestRequest::get()
+            .uri(uri)
+            .peer_addr(peer.parse().unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn within_limit() {
+        let responses = send(
+            &limiter(vec!["/=2/60"]),
+            vec![request("/", "203.0.113.1:1234")],
+        )
+        .await;
+
+        check!(responses[0].status() == 200);
+        check!(responses[0].headers().get("ratelimit-limit").unwrap() == "2");
+        check!(responses[0].headers().get("ratelimit-remaining").unwrap() == "1");
+        check!(responses[0].headers().get("ratelimit-reset").unwrap() == "30");
+    }
+
+    #[actix_rt::test]
+    async fn over_limit() {
+        let responses = send(
+            &limiter(vec!["/=2/60"]),
+            vec![
+                request("/", "203.0.113.1:1234"),
+                request("/", "203.0.113.1:1234"),
+                request("/", "203.0.113.1:1234"),
+                request("/", "203.0.113.2:1234"),
+            ],
+        )
+        .await;
+
+        check!(responses[1].status() == 200);
+        check!(responses[2].status() == 429);
+        check!(responses[2].headers().get("content-type").unwrap() == "application/problem+json");
+        check!(responses[2].headers().get("retry-after").unwrap() == "30");
+        check!(responses[2].headers().get("ratelimit-remaining").unwrap() == "0");
+        check!(responses[3].status() == 200);
+    }
+
+    #[actix_rt::test]
+    async fn through_trusted_proxy() {
+        let responses = send(
+            &limiter(vec!["/=1/60"]),
+            vec![
+                request("/", "10.0.0.1:1234").header("x-forwarded-for", "203.0.113.1"),
+                request("/", "10.0.0.2:1234").header("x-forwarded-for", "203.0.113.2"),
+                request("/", "10.0.0.1:1234").header("x-forwarded-for", "203.0.113.1"),
+            ],
+        )
+        .await;
+
+        check!(responses[0].status() == 200);
+        check!(responses[1].status() == 200);
+        check!(responses[2].status() == 429);
+    }
+
+    #[actix_rt::test]
+    async fn route_groups() {
+        let responses = send(
+            &limiter(vec!["/users=1/60", "/problems=2/60"]),
+            vec![
+                request("/users/abc", "203.0.113.1:1234"),
+                request("/users/def", "203.0.113.1:1234"),
+                request("/problems", "203.0.113.1:1234"),
+                request("/problems/abc", "203.0.113.1:1234"),
+                request("/usersettings", "203.0.113.1:1234"),
+            ],
+        )
+        .await;
+
+        check!(responses[0].status() == 200);
+        check!(responses[1].status() == 429);
+        check!(responses[2].status() == 200);
+        check!(responses[3].status() == 200);
+        check!(responses[3].headers().get("ratelimit-limit").unwrap() == "2");
+        check!(responses[4].status() == 200);
+        check!(responses[4].headers().get("ratelimit-limit").is_none());
+    }
+
+    #[actix_rt::test]
+    async fn encoded_paths() {
+        let responses = send(
+            &limiter(vec!["/users=1/60"]),
+            vec![
+                request("/users/abc", "203.0.113.1:1234"),
+                request("/%75sers/abc", "203.0.113.1:1234"),
+            ],
+        )
+        .await;
+
+        check!(responses[0].status() == 200);
+        check!(responses[1].status() == 429);
+    }
+
+    #[actix_rt::test]
+    async fn ipv6_clients_are_limited_by_network() {
+        let responses = send(
+            &limiter(vec!["/=1/60"]),
+            vec![
+                request("/", "[2001:db8:1:2::1]:1234"),
+                request("/", "[2001:db8:1:2::2]:1234"),
+                request("/", "[2001:db8:1:3::1]:1234"),
+            ],
+        )
+        .await;
+
+        check!(responses[0].status() == 200);
+        check!(responses[1].status() == 429);
+        check!(responses[2].status() == 200);
+    }
+
+    #[actix_rt::test]
+    async fn invalid_tokens_count_against_ip() {
+        let responses = send(
+            &limiter(vec!["/=2/60"]),
+            vec![
+                request("/", "203.0.113.1:1234").header("authorization", "Bearer invalid"),
+                request("/", "203.0.113.1:1234").header("authorization", "Bearer invalid"),
+                request("/", "203.0.113.1:1234").header("authorization", "Bearer invalid"),
+                request("/", "203.0.113.1:1234"),
+            ],
+        )
+        .await;
+
+        check!(responses[0].status() == 200);
+        check!(responses[1].status() == 200);
+        check!(responses[2].status() == 429);
+        check!(responses[3].status() == 429);
+    }
+}
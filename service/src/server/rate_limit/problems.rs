@@ -0,0 +1,20 @@
+use crate::http::problem::{ProblemDocumentation, SimpleProblemType};
+use actix_http::http::StatusCode;
+
+/// Problem to indicate that the client has made too many requests and must wait before making any more.
+pub const TOO_MANY_REQUESTS: SimpleProblemType = SimpleProblemType {
+    problem_type: "/problems/too_many_requests",
+    problem_title: "Too many requests have been made",
+    status_code: StatusCode::TOO_MANY_REQUESTS,
+};
+
+/// Build the documentation of the problem types that rate limiting can return.
+///
+/// # Returns
+/// The documentation of the problem types.
+pub fn problem_documentation() -> Vec<ProblemDocumentation> {
+    vec![ProblemDocumentation::new(
+        &TOO_MANY_REQUESTS,
+        "The client has used up its allowance of requests to this group of routes. The `Retry-After` header gives the number of seconds to wait before trying again, and the `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` headers describe the allowance.",
+    )]
+}
@@ -2,11 +2,21 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use actix_service::{Service, Transform};
-use actix_web::{dev::ServiceRequest, dev::ServiceResponse, Error};
+use actix_web::{
+    dev::ServiceRequest,
+    dev::ServiceResponse,
+    http::{HeaderName, HeaderValue},
+    Error, HttpMessage,
+};
 use futures::future::{ok, Ready};
 use futures::Future;
 
+use crate::http::correlation::{Correlation, X_REQUEST_ID, X_TRACE_ID};
+
 /// Middleware for applying a tracing `Span` around the entire HTTP request, and tracking certain details on it.
+///
+/// This also determines the details needed to correlate the request with its traces, making them available to the
+/// rest of the request handling and returning them to the client as response headers.
 pub struct Span;
 
 impl<S, B> Transform<S> for Span
@@ -53,19 +63,32 @@ where
             "Request",
             http.method = req.method().as_str(),
             http.path = req.path(),
-            http.status_code = tracing::field::Empty
+            http.status_code = tracing::field::Empty,
+            http.request_id = tracing::field::Empty
         );
 
+        let correlation = Correlation::new(req.headers(), &span);
+        span.record("http.request_id", &correlation.request_id.as_str());
+        req.extensions_mut().insert(correlation.clone());
+
         let fut = self.service.call(req);
 
         Box::pin(async move {
             let span = span;
             let _enter = span.enter();
 
-            let response = fut.await?;
+            let mut response = fut.await?;
 
             span.record("http.status_code", &response.status().as_u16());
 
+            let headers = response.headers_mut();
+            if let Ok(request_id) = HeaderValue::from_str(&correlation.request_id) {
+                headers.insert(HeaderName::from_static(X_REQUEST_ID), request_id);
+            }
+            if let Some(Ok(trace_id)) = correlation.trace_id.as_deref().map(HeaderValue::from_str) {
+                headers.insert(HeaderName::from_static(X_TRACE_ID), trace_id);
+            }
+
             Ok(response)
         })
     }
@@ -0,0 +1,426 @@
+#[cfg(test)]
+pub mod testing;
+
+use openssl::{
+    error::ErrorStack,
+    ssl::{
+        select_next_proto, AlpnError, SniError, SslAcceptor, SslAcceptorBuilder, SslContext,
+        SslFiletype, SslMethod, SslVersion,
+    },
+};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+/// The ALPN protocols that the server supports, in order of preference.
+const ALPN_PROTOCOLS: &[u8] = b"\x02h2\x08http/1.1";
+
+/// Errors that can occur when configuring TLS.
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("The TLS certificate path and private key path must be provided together")]
+    Incomplete,
+
+    #[error("HTTP redirects and HSTS can only be used when TLS is configured")]
+    NotEnabled,
+
+    #[error("HTTP redirects need the public host name of the HTTPS server to redirect to")]
+    NoPublicHost,
+
+    #[error("The TLS protocol version '{0}' is not supported")]
+    UnsupportedVersion(String),
+
+    #[error("Failed to read '{path}': {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Failed to load the TLS certificate and private key: {0}")]
+    Invalid(#[from] ErrorStack),
+}
+
+/// The minimum version of TLS that clients may connect with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TlsVersion {
+    Tls12,
+    Tls13,
+}
+
+impl Default for TlsVersion {
+    fn default() -> Self {
+        TlsVersion::Tls12
+    }
+}
+
+impl FromStr for TlsVersion {
+    type Err = TlsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1.2" => Ok(TlsVersion::Tls12),
+            "1.3" => Ok(TlsVersion::Tls13),
+            _ => Err(TlsError::UnsupportedVersion(s.to_owned())),
+        }
+    }
+}
+
+impl From<TlsVersion> for SslVersion {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::Tls12 => SslVersion::TLS1_2,
+            TlsVersion::Tls13 => SslVersion::TLS1_3,
+        }
+    }
+}
+
+/// Configuration for terminating TLS in the HTTP Server.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// The path to the PEM file containing the certificate chain, starting with the server certificate.
+    certificate_path: PathBuf,
+    /// The path to the PEM file containing the private key.
+    private_key_path: PathBuf,
+    /// The minimum version of TLS to allow.
+    min_version: TlsVersion,
+    /// How often to check the certificate and private key for changes, if at all.
+    reload_interval: Option<Duration>,
+}
+
+/// Builder to help build the `TlsConfig`.
+pub struct Builder {
+    config: TlsConfig,
+}
+
+/// Create a new builder to build the TLS configuration with.
+///
+/// # Parameters
+/// - `certificate_path` - The path to the PEM file containing the certificate chain
+/// - `private_key_path` - The path to the PEM file containing the private key
+pub fn new<C, K>(certificate_path: C, private_key_path: K) -> Builder
+where
+    C: Into<PathBuf>,
+    K: Into<PathBuf>,
+{
+    Builder {
+        config: TlsConfig {
+            certificate_path: certificate_path.into(),
+            private_key_path: private_key_path.into(),
+            min_version: TlsVersion::default(),
+            reload_interval: None,
+        },
+    }
+}
+
+impl Builder {
+    /// Specify the minimum version of TLS that clients may connect with. If not specified then this is TLS 1.2.
+    ///
+    /// # Parameters
+    /// - `min_version` - The minimum version
+    pub fn with_min_version(mut self, min_version: TlsVersion) -> Self {
+        self.config.min_version = min_version;
+
+        self
+    }
+
+    /// Specify how often to check the certificate and private key files for changes, reloading them when they do
+    /// change. If not specified then they are only loaded at startup.
+    ///
+    /// # Parameters
+    /// - `reload_interval` - How often to check for changes
+    pub fn with_reload_interval(mut self, reload_interval: Duration) -> Self {
+        self.config.reload_interval = Some(reload_interval);
+
+        self
+    }
+
+    /// Actually build the TLS configuration, checking that the certificate and private key can be loaded.
+    ///
+    /// # Returns
+    /// The TLS configuration.
+    ///
+    /// # Errors
+    /// If the certificate or private key can't be loaded, or don't match each other.
+    pub fn build(self) -> Result<TlsConfig, TlsError> {
+        self.config.load()?;
+
+        Ok(self.config)
+    }
+}
+
+impl TlsConfig {
+    /// Load the certificate and private key into a new TLS acceptor, supporting HTTP/2 and HTTP/1.1 over ALPN.
+    ///
+    /// # Returns
+    /// The builder for the TLS acceptor.
+    ///
+    /// # Errors
+    /// If the certificate or private key can't be loaded, or don't match each other.
+    fn load(&self) -> Result<SslAcceptorBuilder, TlsError> {
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls())?;
+        acceptor.set_min_proto_version(Some(self.min_version.into()))?;
+
+        check_readable(&self.certificate_path)?;
+        acceptor.set_certificate_chain_file(&self.certificate_path)?;
+        check_readable(&self.private_key_path)?;
+        acceptor.set_private_key_file(&self.private_key_path, SslFiletype::PEM)?;
+        acceptor.check_private_key()?;
+
+        acceptor.set_alpn_select_callback(|_, client| {
+            select_next_proto(ALPN_PROTOCOLS, client).ok_or(AlpnError::NOACK)
+        });
+
+        Ok(acceptor)
+    }
+
+    /// Build the TLS acceptor for the HTTP Server to use, along with the reloader that keeps its certificate up to
+    /// date if reloading is enabled.
+    ///
+    /// # Returns
+    /// The builder for the TLS acceptor, and the certificate reloader if there is one.
+    ///
+    /// # Errors
+    /// If the certificate or private key can't be loaded, or don't match each other.
+    pub(super) fn acceptor(
+        &self,
+    ) -> Result<(SslAcceptorBuilder, Option<CertificateReloader>), TlsError> {
+        let mut acceptor = self.load()?;
+
+        let reloader = match self.reload_interval {
+            Some(interval) => {
+                let reloader = CertificateReloader::new(self.clone(), interval)?;
+
+                // Every connection starts with the certificate from when the server started, so swap in the latest
+                // one as soon as the client says hello.
+                let current = reloader.current.clone();
+                acceptor.set_servername_callback(move |ssl, _| {
+                    let context = current.read().map_err(|_| SniError::ALERT_FATAL)?;
+                    ssl.set_ssl_context(&context)
+                        .map_err(|_| SniError::ALERT_FATAL)
+                });
+
+                Some(reloader)
+            }
+            None => None,
+        };
+
+        Ok((acceptor, reloader))
+    }
+}
+
+/// Check that a file can be read, so that errors name the file rather than just coming from OpenSSL.
+///
+/// # Parameters
+/// - `path` - The path to the file
+///
+/// # Errors
+/// If the file can't be read.
+fn check_readable(path: &Path) -> Result<(), TlsError> {
+    std::fs::File::open(path).map_err(|source| TlsError::Read {
+        path: path.to_owned(),
+        source,
+    })?;
+
+    Ok(())
+}
+
+/// Get when the certificate and private key files were last modified.
+///
+/// # Parameters
+/// - `config` - The TLS configuration naming the files
+///
+/// # Returns
+/// The modification times of the certificate and private key.
+///
+/// # Errors
+/// If either file can't be read.
+fn modified(config: &TlsConfig) -> Result<(SystemTime, SystemTime), TlsError> {
+    let modified = |path: &Path| {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|source| TlsError::Read {
+                path: path.to_owned(),
+                source,
+            })
+    };
+
+    Ok((
+        modified(&config.certificate_path)?,
+        modified(&config.private_key_path)?,
+    ))
+}
+
+/// Watches the certificate and private key files, reloading them when they change.
+pub(super) struct CertificateReloader {
+    /// The TLS configuration naming the files.
+    config: TlsConfig,
+    /// How often to check the files for changes.
+    interval: Duration,
+    /// When the files that are currently loaded were last modified.
+    modified: (SystemTime, SystemTime),
+    /// The TLS context containing the currently loaded certificate and private key.
+    current: Arc<RwLock<SslContext>>,
+}
+
+impl CertificateReloader {
+    /// Create a new reloader, starting from the current certificate and private key.
+    ///
+    /// # Parameters
+    /// - `config` - The TLS configuration naming the files
+    /// - `interval` - How often to check the files for changes
+    ///
+    /// # Errors
+    /// If the certificate or private key can't be loaded.
+    fn new(config: TlsConfig, interval: Duration) -> Result<Self, TlsError> {
+        let modified = modified(&config)?;
+        let current = config.load()?.build().into_context();
+
+        Ok(Self {
+            config,
+            interval,
+            modified,
+            current: Arc::new(RwLock::new(current)),
+        })
+    }
+
+    /// Check the files for changes forever, reloading them whenever they do change.
+    pub(super) async fn run(mut self) {
+        let mut interval = actix_rt::time::interval(self.interval);
+
+        loop {
+            interval.tick().await;
+            self.reload_if_changed();
+        }
+    }
+
+    /// Reload the certificate and private key if either file has changed since they were last loaded.
+    ///
+    /// If the new files can't be loaded, for example because only one of them has been replaced so far, then the
+    /// existing certificate is kept and loading is tried again next time.
+    fn reload_if_changed(&mut self) {
+        let modified = match modified(&self.config) {
+            Ok(modified) => modified,
+            Err(e) => {
+                tracing::warn!(e = ?e, "Failed to check the TLS certificate for changes");
+                return;
+            }
+        };
+
+        if modified == self.modified {
+            return;
+        }
+
+        match self.config.load() {
+            Ok(acceptor) => {
+                let context = acceptor.build().into_context();
+                match self.current.write() {
+                    Ok(mut current) => {
+                        *current = context;
+                        self.modified = modified;
+                        tracing::info!(path = ?self.config.certificate_path, "Reloaded TLS certificate");
+                    }
+                    Err(e) => tracing::error!(e = ?e, "Failed to replace TLS certificate"),
+                }
+            }
+            Err(e) => tracing::error!(e = ?e, "Failed to reload TLS certificate"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+    use test_case::test_case;
+
+    #[test_case("1.2", TlsVersion::Tls12 ; "TLS 1.2")]
+    #[test_case("1.3", TlsVersion::Tls13 ; "TLS 1.3")]
+    fn parse_version(input: &str, expected: TlsVersion) {
+        let_assert!(Ok(version) = input.parse::<TlsVersion>());
+        check!(version == expected);
+    }
+
+    #[test_case("1.1" ; "TLS 1.1")]
+    #[test_case("TLSv1.2" ; "prefixed")]
+    #[test_case("" ; "blank")]
+    fn parse_unsupported_version(input: &str) {
+        let_assert!(Err(TlsError::UnsupportedVersion(version)) = input.parse::<TlsVersion>());
+        check!(version == input);
+    }
+
+    #[test]
+    fn build_valid() {
+        let certificate = testing::TestCertificate::generate();
+
+        let result = new(&certificate.certificate_path, &certificate.private_key_path)
+            .with_min_version(TlsVersion::Tls13)
+            .build();
+
+        check!(result.is_ok());
+    }
+
+    #[test]
+    fn build_missing_certificate() {
+        let certificate = testing::TestCertificate::generate();
+        let missing = certificate.certificate_path.with_file_name("missing.pem");
+
+        let result = new(&missing, &certificate.private_key_path).build();
+
+        let_assert!(Err(TlsError::Read { path, .. }) = result);
+        check!(path == missing);
+    }
+
+    #[test]
+    fn build_mismatched_key() {
+        let certificate = testing::TestCertificate::generate();
+        let other = testing::TestCertificate::generate();
+
+        let result = new(&certificate.certificate_path, &other.private_key_path).build();
+
+        let_assert!(Err(TlsError::Invalid(_)) = result);
+    }
+
+    #[test]
+    fn reload_changed_certificate() {
+        let mut certificate = testing::TestCertificate::generate();
+        let_assert!(
+            Ok(config) = new(&certificate.certificate_path, &certificate.private_key_path).build()
+        );
+        let_assert!(Ok(mut reloader) = CertificateReloader::new(config, Duration::from_secs(1)));
+
+        reloader.reload_if_changed();
+        check!(current_serial(&reloader) == certificate.serial());
+
+        certificate.replace();
+        reloader.reload_if_changed();
+        check!(current_serial(&reloader) == certificate.serial());
+    }
+
+    #[test]
+    fn reload_keeps_certificate_when_invalid() {
+        let certificate = testing::TestCertificate::generate();
+        let_assert!(
+            Ok(config) = new(&certificate.certificate_path, &certificate.private_key_path).build()
+        );
+        let_assert!(Ok(mut reloader) = CertificateReloader::new(config, Duration::from_secs(1)));
+
+        std::fs::write(&certificate.private_key_path, "Not a key").unwrap();
+        reloader.reload_if_changed();
+
+        check!(current_serial(&reloader) == certificate.serial());
+    }
+
+    fn current_serial(reloader: &CertificateReloader) -> Vec<u8> {
+        let current = reloader.current.read().unwrap();
+        current
+            .certificate()
+            .unwrap()
+            .serial_number()
+            .to_bn()
+            .unwrap()
+            .to_vec()
+    }
+}
@@ -0,0 +1,97 @@
+use openssl::{
+    asn1::Asn1Time,
+    bn::{BigNum, MsbOption},
+    ec::{EcGroup, EcKey},
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::PKey,
+    x509::{extension::SubjectAlternativeName, X509Name, X509},
+};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// A self-signed certificate for `localhost`, written to a temporary directory. Only used for testing.
+pub struct TestCertificate {
+    /// The directory containing the files, which is deleted when this is dropped.
+    _dir: TempDir,
+    /// The path to the certificate file.
+    pub certificate_path: PathBuf,
+    /// The path to the private key file.
+    pub private_key_path: PathBuf,
+    /// The certificate itself.
+    pub certificate: X509,
+}
+
+impl TestCertificate {
+    /// Generate a new certificate and private key.
+    ///
+    /// # Returns
+    /// The certificate.
+    pub fn generate() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut certificate = Self {
+            certificate_path: dir.path().join("certificate.pem"),
+            private_key_path: dir.path().join("private_key.pem"),
+            _dir: dir,
+            certificate: X509::builder().unwrap().build(),
+        };
+        certificate.replace();
+
+        certificate
+    }
+
+    /// Replace the files with a newly generated certificate and private key.
+    pub fn replace(&mut self) {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509Name::builder().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, "localhost")
+            .unwrap();
+        let name = name.build();
+
+        let mut serial = BigNum::new().unwrap();
+        serial.rand(64, MsbOption::MAYBE_ZERO, false).unwrap();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder
+            .set_serial_number(&serial.to_asn1_integer().unwrap())
+            .unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        let alternative_names = SubjectAlternativeName::new()
+            .dns("localhost")
+            .ip("127.0.0.1")
+            .build(&builder.x509v3_context(None, None))
+            .unwrap();
+        builder.append_extension(alternative_names).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        let certificate = builder.build();
+
+        std::fs::write(&self.certificate_path, certificate.to_pem().unwrap()).unwrap();
+        std::fs::write(
+            &self.private_key_path,
+            key.private_key_to_pem_pkcs8().unwrap(),
+        )
+        .unwrap();
+
+        self.certificate = certificate;
+    }
+
+    /// Get the serial number of the current certificate.
+    ///
+    /// # Returns
+    /// The serial number, as big-endian bytes.
+    pub fn serial(&self) -> Vec<u8> {
+        self.certificate.serial_number().to_bn().unwrap().to_vec()
+    }
+}
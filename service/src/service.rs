@@ -1,8 +1,17 @@
 #[cfg(test)]
 pub mod testing;
 
-use crate::settings::Settings;
+use crate::{
+    server::{
+        cors::{CorsPolicy, CorsPolicyError},
+        rate_limit::{RateLimitError, RateLimiter},
+        tls::{TlsConfig, TlsError, TlsVersion},
+        ServerHandle,
+    },
+    settings::{Settings, SettingsError},
+};
 use prometheus::Registry;
+use std::time::Duration;
 
 /// The complete New Landing service.
 pub struct Service {
@@ -18,9 +27,16 @@ impl Service {
     ///
     /// # Returns
     /// The service itself.
-    pub async fn new(cfg: Settings) -> Self {
+    ///
+    /// # Errors
+    /// If the configuration settings are invalid.
+    pub async fn new(cfg: Settings) -> Result<Self, SettingsError> {
         tracing::debug!("Building New Landing");
 
+        let cors = cors_policy(&cfg)?;
+        let tls = tls_config(&cfg)?;
+        let rate_limiter = rate_limiter(&cfg)?;
+
         let prometheus = Registry::new();
 
         let authentication =
@@ -30,24 +46,378 @@ impl Service {
             &cfg.auth0_client_id,
             &cfg.auth0_client_secret,
         );
-        let home = crate::home::component::new().build();
+        let home = crate::home::component::new()
+            .with_curie("nl", "/rels/nl:{rel}")
+            .with_relation("nl:user", "A single user, identified by their ID")
+            .with_link_header("self")
+            .with_contributor(users.clone())
+            .build();
+        let health = crate::health::component::new()
+            .with_check("authorization", authentication.clone())
+            .with_check("users", users.clone())
+            .build();
+        let problems = crate::health::problem_documentation()
+            .into_iter()
+            .chain(crate::server::rate_limit::problem_documentation())
+            .fold(
+                crate::problems::component::new(),
+                crate::problems::component::Builder::with_problem,
+            )
+            .build();
+        let schemas = crate::schemas::component::new()
+            .with_routes(home.clone())
+            .with_routes(users.clone())
+            .build();
+        let openapi = crate::openapi::component::new()
+            .with_routes(home.clone())
+            .with_routes(users.clone())
+            .with_routes(problems.clone())
+            .with_routes(schemas.clone())
+            .with_routes(health.clone())
+            .with_routes(authentication.clone())
+            .build();
 
-        let server = crate::server::component::new()
+        let mut server = crate::server::component::new()
             .with_routes(home)
             .with_routes(users)
+            .with_routes(problems)
+            .with_routes(schemas)
+            .with_routes(health)
             .with_routes(authentication)
-            .build(cfg.port, prometheus);
+            .with_routes(openapi)
+            .with_development_mode(cfg.development_mode)
+            .with_cors_policy(cors)
+            .with_rate_limiter(rate_limiter)
+            .with_shutdown_grace_period(Duration::from_secs(cfg.shutdown_grace_period));
+        if let Some(tls) = tls {
+            server = server.with_tls(tls);
+            if let (Some(redirect_port), Some(public_host)) =
+                (cfg.http_redirect_port, &cfg.https_public_host)
+            {
+                server = server.with_https_redirect(redirect_port, public_host);
+            }
+            if let Some(hsts_max_age) = cfg.hsts_max_age {
+                server = server.with_hsts(Duration::from_secs(hsts_max_age));
+            }
+        }
+        let server = server.build(cfg.port, prometheus);
 
         tracing::debug!("Built New Landing");
 
-        Self {
+        Ok(Self {
             server: server.server,
-        }
+        })
     }
 
     /// Start the service running.
-    pub async fn start(self) {
+    ///
+    /// # Returns
+    /// The handle to the running service, which can be used to stop it.
+    ///
+    /// # Errors
+    /// Any error from binding the HTTP Server to its port.
+    pub fn start(self) -> std::io::Result<ServerHandle> {
         tracing::info!("Starting New Landing");
-        self.server.start().await;
+        self.server.start()
+    }
+}
+
+/// Build the CORS policy described by the configuration settings.
+///
+/// # Parameters
+/// - `cfg` - The configuration settings for the service
+///
+/// # Returns
+/// The CORS policy.
+///
+/// # Errors
+/// If the CORS settings are invalid, or ask for the permissive policy outside of development mode.
+fn cors_policy(cfg: &Settings) -> Result<CorsPolicy, CorsPolicyError> {
+    if cfg.cors_permissive {
+        if !cfg.development_mode {
+            return Err(CorsPolicyError::PermissiveOutsideDevelopment);
+        }
+
+        tracing::warn!("Allowing all cross-origin requests");
+        return Ok(CorsPolicy::permissive());
+    }
+
+    let mut builder = crate::server::cors::new()
+        .with_allowed_origins(split_list(&cfg.cors_allowed_origins))
+        .with_allowed_methods(split_list(&cfg.cors_allowed_methods))
+        .with_allowed_headers(split_list(&cfg.cors_allowed_headers))
+        .with_exposed_headers(split_list(&cfg.cors_exposed_headers))
+        .with_credentials(cfg.cors_allow_credentials);
+    if let Some(max_age) = cfg.cors_max_age {
+        builder = builder.with_max_age(Duration::from_secs(max_age));
+    }
+
+    builder.build()
+}
+
+/// Build the TLS configuration described by the configuration settings.
+///
+/// # Parameters
+/// - `cfg` - The configuration settings for the service
+///
+/// # Returns
+/// The TLS configuration, if TLS is enabled.
+///
+/// # Errors
+/// If the TLS settings are incomplete or invalid, or if the certificate can't be loaded.
+fn tls_config(cfg: &Settings) -> Result<Option<TlsConfig>, TlsError> {
+    if cfg.http_redirect_port.is_some() && cfg.https_public_host.is_none() {
+        return Err(TlsError::NoPublicHost);
+    }
+
+    let (certificate_path, private_key_path) =
+        match (&cfg.tls_certificate_path, &cfg.tls_private_key_path) {
+            (Some(certificate_path), Some(private_key_path)) => {
+                (certificate_path, private_key_path)
+            }
+            (None, None) => {
+                if cfg.http_redirect_port.is_some() || cfg.hsts_max_age.is_some() {
+                    return Err(TlsError::NotEnabled);
+                }
+                return Ok(None);
+            }
+            _ => return Err(TlsError::Incomplete),
+        };
+
+    let mut builder = crate::server::tls::new(certificate_path, private_key_path)
+        .with_min_version(cfg.tls_min_protocol_version.parse::<TlsVersion>()?);
+    if cfg.tls_reload_interval > 0 {
+        builder = builder.with_reload_interval(Duration::from_secs(cfg.tls_reload_interval));
+    }
+
+    builder.build().map(Some)
+}
+
+/// Build the rate limiter described by the configuration settings.
+///
+/// # Parameters
+/// - `cfg` - The configuration settings for the service
+///
+/// # Returns
+/// The rate limiter.
+///
+/// # Errors
+/// If any of the rate limits or trusted proxies are invalid.
+fn rate_limiter(cfg: &Settings) -> Result<RateLimiter, RateLimitError> {
+    crate::server::rate_limit::new()
+        .with_limits(split_list(&cfg.rate_limits))
+        .with_trusted_proxies(split_list(&cfg.trusted_proxies))
+        .build()
+}
+
+/// Split a comma-separated setting into its values, ignoring any that are blank.
+///
+/// # Parameters
+/// - `value` - The setting to split
+///
+/// # Returns
+/// The individual values.
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::{check, let_assert};
+
+    fn settings() -> Settings {
+        Settings {
+            port: 0,
+            auth0_domain: "https://example.auth0.com".to_owned(),
+            auth0_audience: "testAudience".to_owned(),
+            auth0_client_id: "testAuth0ClientId".to_owned(),
+            auth0_client_secret: "testAuth0ClientSecret".to_owned(),
+            development_mode: false,
+            shutdown_grace_period: 0,
+            cors_allowed_origins: " https://app.example.com, https://*.example.org ,".to_owned(),
+            cors_allowed_methods: "GET,POST".to_owned(),
+            cors_allowed_headers: "Authorization".to_owned(),
+            cors_exposed_headers: String::new(),
+            cors_allow_credentials: true,
+            cors_max_age: Some(600),
+            cors_permissive: false,
+            tls_certificate_path: None,
+            tls_private_key_path: None,
+            tls_min_protocol_version: "1.2".to_owned(),
+            tls_reload_interval: 0,
+            http_redirect_port: None,
+            https_public_host: None,
+            hsts_max_age: None,
+            rate_limits: "/=120/60, /users=30/60".to_owned(),
+            trusted_proxies: "10.0.0.0/8".to_owned(),
+        }
+    }
+
+    #[test]
+    fn cors_policy_from_settings() {
+        let_assert!(Ok(policy) = cors_policy(&settings()));
+
+        check!(policy.allows_origin("https://app.example.com"));
+        check!(policy.allows_origin("https://api.example.org"));
+        check!(!policy.allows_origin("https://other.com"));
+    }
+
+    #[actix_rt::test]
+    async fn cors_policy_allows_conditional_requests_by_default() {
+        use actix_web::{test, web, App, HttpResponse};
+
+        let cfg = Settings {
+            cors_allowed_methods: crate::settings::DEFAULT_CORS_ALLOWED_METHODS.to_owned(),
+            cors_allowed_headers: crate::settings::DEFAULT_CORS_ALLOWED_HEADERS.to_owned(),
+            ..settings()
+        };
+        let_assert!(Ok(policy) = cors_policy(&cfg));
+
+        let mut app = test::init_service(
+            App::new()
+                .wrap(policy.build_middleware())
+                .default_service(web::route().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let response = test::call_service(
+            &mut app,
+            test::TestRequest::default()
+                .method(actix_web::http::Method::OPTIONS)
+                .uri("/users/abc")
+                .header("origin", "https://app.example.com")
+                .header("access-control-request-method", "GET")
+                .header("access-control-request-headers", "if-none-match")
+                .to_request(),
+        )
+        .await;
+
+        check!(response.status() == 200);
+        let_assert!(Some(allowed) = response.headers().get("access-control-allow-headers"));
+        check!(allowed
+            .to_str()
+            .unwrap()
+            .to_ascii_lowercase()
+            .contains("if-none-match"));
+    }
+
+    #[test]
+    fn cors_policy_invalid_origin() {
+        let cfg = Settings {
+            cors_allowed_origins: "https://app.example.com,*".to_owned(),
+            ..settings()
+        };
+
+        check!(cors_policy(&cfg).unwrap_err() == CorsPolicyError::InvalidOrigin("*".to_owned()));
+    }
+
+    #[test]
+    fn cors_policy_permissive_in_development() {
+        let cfg = Settings {
+            development_mode: true,
+            cors_permissive: true,
+            ..settings()
+        };
+
+        let_assert!(Ok(policy) = cors_policy(&cfg));
+        check!(policy.allows_origin("https://other.com"));
+    }
+
+    #[test]
+    fn tls_config_disabled() {
+        let_assert!(Ok(config) = tls_config(&settings()));
+        check!(config.is_none());
+    }
+
+    #[test]
+    fn tls_config_enabled() {
+        let certificate = crate::server::tls::testing::TestCertificate::generate();
+        let cfg = Settings {
+            tls_certificate_path: Some(certificate.certificate_path.display().to_string()),
+            tls_private_key_path: Some(certificate.private_key_path.display().to_string()),
+            tls_min_protocol_version: "1.3".to_owned(),
+            ..settings()
+        };
+
+        let_assert!(Ok(Some(_)) = tls_config(&cfg));
+    }
+
+    #[test]
+    fn tls_config_incomplete() {
+        let cfg = Settings {
+            tls_certificate_path: Some("certificate.pem".to_owned()),
+            ..settings()
+        };
+
+        let_assert!(Err(TlsError::Incomplete) = tls_config(&cfg));
+    }
+
+    #[test]
+    fn tls_config_redirect_without_tls() {
+        let cfg = Settings {
+            http_redirect_port: Some(8080),
+            https_public_host: Some("example.com".to_owned()),
+            ..settings()
+        };
+
+        let_assert!(Err(TlsError::NotEnabled) = tls_config(&cfg));
+    }
+
+    #[test]
+    fn tls_config_redirect_without_public_host() {
+        let certificate = crate::server::tls::testing::TestCertificate::generate();
+        let cfg = Settings {
+            tls_certificate_path: Some(certificate.certificate_path.display().to_string()),
+            tls_private_key_path: Some(certificate.private_key_path.display().to_string()),
+            http_redirect_port: Some(8080),
+            ..settings()
+        };
+
+        let_assert!(Err(TlsError::NoPublicHost) = tls_config(&cfg));
+    }
+
+    #[test]
+    fn cors_policy_permissive_outside_development() {
+        let cfg = Settings {
+            cors_permissive: true,
+            ..settings()
+        };
+
+        check!(cors_policy(&cfg).unwrap_err() == CorsPolicyError::PermissiveOutsideDevelopment);
+    }
+
+    #[test]
+    fn rate_limiter_from_settings() {
+        let_assert!(Ok(_) = rate_limiter(&settings()));
+    }
+
+    #[test]
+    fn rate_limiter_invalid_limit() {
+        let cfg = Settings {
+            rate_limits: "/users=lots".to_owned(),
+            ..settings()
+        };
+
+        check!(
+            rate_limiter(&cfg).unwrap_err()
+                == RateLimitError::InvalidLimit("/users=lots".to_owned())
+        );
+    }
+
+    #[test]
+    fn rate_limiter_invalid_proxy() {
+        let cfg = Settings {
+            trusted_proxies: "10.0.0.0/33".to_owned(),
+            ..settings()
+        };
+
+        check!(
+            rate_limiter(&cfg).unwrap_err()
+                == RateLimitError::InvalidProxy("10.0.0.0/33".to_owned())
+        );
     }
 }
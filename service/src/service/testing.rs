@@ -1,7 +1,15 @@
+use actix_http::http::Method;
 use actix_http::Request;
-use actix_web::App;
+use actix_web::{web::route, App};
 
 use super::Service;
+use crate::{
+    http::{
+        problem::{configure_extractors, not_found, ProblemNegotiation},
+        routes,
+    },
+    server::span::Span,
+};
 
 impl Service {
     /// Inject a request into the server. Only used for testing.
@@ -12,7 +20,12 @@ impl Service {
     /// # Returns
     /// The response from injecting the request.
     pub async fn inject(&self, req: Request) -> TestResponse {
-        let mut app = App::new();
+        let mut app = App::new()
+            .wrap(ProblemNegotiation)
+            .wrap(Span)
+            .app_data(self.server.problem_config)
+            .configure(configure_extractors)
+            .default_service(route().to(not_found));
         for c in &self.server.routes {
             app = app.configure(move |server_config| {
                 c.configure_routes(server_config);
@@ -32,6 +45,25 @@ impl Service {
             body,
         }
     }
+
+    /// Find the HTTP Method and path pattern of every route registered with the server. Only used for testing.
+    ///
+    /// # Returns
+    /// The routes, sorted and without duplicates.
+    #[must_use]
+    pub fn registered_routes(&self) -> Vec<(Method, String)> {
+        let mut routes = routes::record(|| {
+            for c in &self.server.routes {
+                App::new().configure(move |server_config| {
+                    c.configure_routes(server_config);
+                });
+            }
+        });
+        routes.sort_by(|a, b| (&a.1, a.0.as_str()).cmp(&(&b.1, b.0.as_str())));
+        routes.dedup();
+
+        routes
+    }
 }
 
 /// Representation of the response to injecting a test request
@@ -1,5 +1,14 @@
+use crate::server::{cors::CorsPolicyError, rate_limit::RateLimitError, tls::TlsError};
 use serde::Deserialize;
 
+/// The default HTTP methods allowed in cross-origin requests.
+pub const DEFAULT_CORS_ALLOWED_METHODS: &str = "GET,HEAD,OPTIONS,PATCH,PUT";
+
+/// The default request headers allowed in cross-origin requests, including those needed for conditional requests
+/// since they aren't CORS-safelisted.
+pub const DEFAULT_CORS_ALLOWED_HEADERS: &str =
+    "Accept,Authorization,Content-Type,If-Match,If-None-Match";
+
 /// The actual settings as loaded from the environment.
 #[derive(Debug, Deserialize)]
 pub struct Settings {
@@ -8,4 +17,67 @@ pub struct Settings {
     pub auth0_audience: String,
     pub auth0_client_id: String,
     pub auth0_client_secret: String,
+    #[serde(default)]
+    pub development_mode: bool,
+    pub shutdown_grace_period: u64,
+    /// Comma-separated origins allowed to make cross-origin requests, which may include wildcard subdomain patterns
+    /// such as `https://*.example.com`.
+    #[serde(default)]
+    pub cors_allowed_origins: String,
+    /// Comma-separated HTTP methods allowed in cross-origin requests.
+    pub cors_allowed_methods: String,
+    /// Comma-separated request headers allowed in cross-origin requests.
+    pub cors_allowed_headers: String,
+    /// Comma-separated response headers to expose to cross-origin clients, in addition to the standard ones.
+    #[serde(default)]
+    pub cors_exposed_headers: String,
+    #[serde(default)]
+    pub cors_allow_credentials: bool,
+    /// How long, in seconds, clients may cache preflight responses for.
+    #[serde(default)]
+    pub cors_max_age: Option<u64>,
+    /// Allow any cross-origin request at all. Only accepted in development mode.
+    #[serde(default)]
+    pub cors_permissive: bool,
+    /// The path to the PEM file containing the TLS certificate chain. If not provided then plain HTTP is served.
+    #[serde(default)]
+    pub tls_certificate_path: Option<String>,
+    /// The path to the PEM file containing the TLS private key.
+    #[serde(default)]
+    pub tls_private_key_path: Option<String>,
+    /// The minimum version of TLS to allow - either `1.2` or `1.3`.
+    pub tls_min_protocol_version: String,
+    /// How often, in seconds, to check the TLS certificate and private key for changes. Zero disables reloading.
+    pub tls_reload_interval: u64,
+    /// The port to listen for plain HTTP on, redirecting every request to HTTPS.
+    #[serde(default)]
+    pub http_redirect_port: Option<u16>,
+    /// The host name that clients use to reach the HTTPS server, which plain HTTP requests are redirected to.
+    /// Required if `http_redirect_port` is provided.
+    #[serde(default)]
+    pub https_public_host: Option<String>,
+    /// How long, in seconds, clients should remember to only use HTTPS for.
+    #[serde(default)]
+    pub hsts_max_age: Option<u64>,
+    /// Comma-separated rate limits for groups of routes, each of the form `/prefix=requests/seconds`. Requests are
+    /// counted per authenticated principal, or per client IP address for anonymous requests.
+    #[serde(default)]
+    pub rate_limits: String,
+    /// Comma-separated IP addresses or networks of the proxies trusted to report the client IP address, through the
+    /// `Forwarded` or `X-Forwarded-For` headers.
+    #[serde(default)]
+    pub trusted_proxies: String,
+}
+
+/// Errors that can occur when the settings are invalid.
+#[derive(Debug, thiserror::Error)]
+pub enum SettingsError {
+    #[error("Invalid CORS settings: {0}")]
+    Cors(#[from] CorsPolicyError),
+
+    #[error("Invalid TLS settings: {0}")]
+    Tls(#[from] TlsError),
+
+    #[error("Invalid rate limit settings: {0}")]
+    RateLimit(#[from] RateLimitError),
 }
@@ -1,6 +1,8 @@
 mod access_token;
 mod domain;
 mod get_user;
+mod health;
+mod update_user;
 
 pub use access_token::{ClientId, ClientSecret};
 pub use domain::Domain;
@@ -105,6 +105,9 @@ impl Retriever {
                 entry.expires = SystemTime::now() + Duration::from_secs(expiry - 10); // Expire 10 seconds earlier than we were told, to be safe.
 
                 tracing::debug!(entry = ?entry, "Caching access token");
+            } else {
+                // The expired token is no use to Auth0 any more, so don't let it hide the failure.
+                entry.token = None;
             }
         } else {
             tracing::debug!(entry = ?entry, "Using cached access token");
@@ -113,6 +116,13 @@ impl Retriever {
         entry.token.clone()
     }
 
+    /// Mark the cached value as expired, without clearing it. Only used for testing.
+    #[cfg(test)]
+    pub fn expire_cache(&self) {
+        let lock = self.cache.lock().unwrap();
+        lock.borrow_mut().expires = SystemTime::UNIX_EPOCH;
+    }
+
     /// Clear the cached value.
     /// This allows subsequent calls to `get_access_token()` to instead fetch a new one from Auth0.
     #[tracing::instrument(skip(self))]
@@ -356,6 +366,42 @@ mod tests {
         m.assert();
     }
 
+    #[actix_rt::test]
+    async fn get_access_token_expired_failure() {
+        let _ = env_logger::try_init();
+
+        let sut = Retriever::new(
+            Domain::new(mockito::server_url()),
+            ClientId("testClientId".to_owned()),
+            ClientSecret("testClientSecret".to_owned()),
+        );
+
+        let m = mock("POST", "/oauth/token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+              "access_token":"testAccessToken",
+              "expires_in":86400,
+              "token_type":"Bearer"
+            }"#,
+            )
+            .create();
+        let_assert!(Some(_) = sut.get_access_token().await);
+        m.assert();
+        drop(m);
+
+        sut.expire_cache();
+
+        let m = mock("POST", "/oauth/token")
+            .with_status(503)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{}"#)
+            .create();
+        check!(sut.get_access_token().await == None);
+        m.assert();
+    }
+
     #[actix_rt::test]
     async fn get_access_token_clear_cache() {
         let _ = env_logger::try_init();
@@ -54,6 +54,32 @@ impl UserRepository {
 
         tracing::debug!(auth0_user = ?auth0_user, "Retrieved user details");
 
+        Some(auth0_user.into())
+    }
+}
+
+/// Representation of a user as retrieved from Auth0.
+#[derive(Debug, Deserialize)]
+pub(super) struct Auth0User {
+    pub user_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub name: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub identities: Vec<Auth0Identity>,
+}
+
+/// Representation of a single user identity as retrieved from Auth0.
+#[derive(Debug, Deserialize)]
+pub(super) struct Auth0Identity {
+    #[serde(rename(deserialize = "isSocial"))]
+    pub is_social: bool,
+    pub provider: String,
+}
+
+impl From<Auth0User> for UserResource {
+    fn from(auth0_user: Auth0User) -> Self {
         let social_provider = auth0_user.identities.into_iter().find_map(|i| {
             if i.is_social {
                 Some(i.provider)
@@ -62,7 +88,7 @@ impl UserRepository {
             }
         });
 
-        let user = UserResource {
+        UserResource {
             identity: Identity {
                 id: auth0_user.user_id.parse().unwrap(),
                 version: base64::encode(format!("{}", auth0_user.updated_at)),
@@ -75,32 +101,10 @@ impl UserRepository {
                 email_verified: auth0_user.email_verified,
                 social_provider,
             },
-        };
-
-        Some(user)
+        }
     }
 }
 
-/// Representation of a user as retrieved from Auth0.
-#[derive(Debug, Deserialize)]
-struct Auth0User {
-    pub user_id: String,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
-    pub name: String,
-    pub email: String,
-    pub email_verified: bool,
-    pub identities: Vec<Auth0Identity>,
-}
-
-/// Representation of a single user identity as retrieved from Auth0.
-#[derive(Debug, Deserialize)]
-struct Auth0Identity {
-    #[serde(rename(deserialize = "isSocial"))]
-    pub is_social: bool,
-    pub provider: String,
-}
-
 #[cfg(test)]
 mod tests {
     use crate::users::auth0::{ClientId, ClientSecret, Domain, UserRepository};
@@ -0,0 +1,125 @@
+use super::UserRepository;
+
+impl UserRepository {
+    /// Check that an access token for working with Auth0 can be obtained.
+    ///
+    /// A cached access token is used if there is one, so that the check doesn't request a new token on every probe.
+    ///
+    /// # Returns
+    /// Details of why an access token couldn't be obtained, if it couldn't.
+    pub async fn check_health(&self) -> Result<(), String> {
+        match self.access_token_retriever.get_access_token().await {
+            Some(_) => Ok(()),
+            None => Err("Failed to obtain an access token for the Auth0 Management API".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::users::auth0::{ClientId, ClientSecret, Domain, UserRepository};
+    use assert2::{check, let_assert};
+    use mockito::mock;
+
+    fn repository() -> UserRepository {
+        UserRepository::new(
+            Domain::new(mockito::server_url()),
+            ClientId::new("testClientId"),
+            ClientSecret::new("testClientSecret"),
+        )
+    }
+
+    #[actix_rt::test]
+    async fn healthy() {
+        let _ = env_logger::try_init();
+
+        let m = mock("POST", "/oauth/token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "access_token":"testAccessToken",
+                "expires_in":86400,
+                "token_type":"Bearer"
+            }"#,
+            )
+            .create();
+
+        check!(repository().check_health().await.is_ok());
+
+        m.assert();
+    }
+
+    #[actix_rt::test]
+    async fn healthy_from_cache() {
+        let _ = env_logger::try_init();
+
+        let m = mock("POST", "/oauth/token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "access_token":"testAccessToken",
+                "expires_in":86400,
+                "token_type":"Bearer"
+            }"#,
+            )
+            .expect(1)
+            .create();
+
+        let sut = repository();
+        check!(sut.check_health().await.is_ok());
+        check!(sut.check_health().await.is_ok());
+
+        m.assert();
+    }
+
+    #[actix_rt::test]
+    async fn unhealthy_when_token_expired() {
+        let _ = env_logger::try_init();
+
+        let sut = repository();
+
+        let m = mock("POST", "/oauth/token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "access_token":"testAccessToken",
+                "expires_in":86400,
+                "token_type":"Bearer"
+            }"#,
+            )
+            .create();
+        check!(sut.check_health().await.is_ok());
+        m.assert();
+        drop(m);
+
+        sut.access_token_retriever.expire_cache();
+
+        let m = mock("POST", "/oauth/token")
+            .with_status(503)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{}"#)
+            .create();
+        let_assert!(Err(detail) = sut.check_health().await);
+        check!(detail == "Failed to obtain an access token for the Auth0 Management API");
+        m.assert();
+    }
+
+    #[actix_rt::test]
+    async fn unhealthy() {
+        let _ = env_logger::try_init();
+
+        let m = mock("POST", "/oauth/token")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{}"#)
+            .create();
+
+        let_assert!(Err(detail) = repository().check_health().await);
+        check!(detail == "Failed to obtain an access token for the Auth0 Management API");
+
+        m.assert();
+    }
+}
@@ -0,0 +1,180 @@
+use super::{get_user::Auth0User, UserRepository};
+use crate::users::{UserId, UserResource};
+use reqwest::StatusCode;
+use serde::Serialize;
+
+impl UserRepository {
+    /// Change the display name of a user in Auth0.
+    ///
+    /// # Parameters
+    /// - `id` - The ID of the user, as understood by Auth0.
+    /// - `display_name` - The new display name of the user.
+    ///
+    /// # Returns
+    /// The updated user, or `None` if it couldn't be updated.
+    #[tracing::instrument(skip(self))]
+    pub async fn update_display_name(
+        &self,
+        id: UserId,
+        display_name: String,
+    ) -> Option<UserResource> {
+        let access_token = self.access_token_retriever.get_access_token().await?;
+
+        let url = self
+            .domain
+            .build_url_template("/api/v2/users/{id}")
+            .set("id", id)
+            .build();
+
+        let auth0_user = {
+            let span = tracing::trace_span!(
+                "Auth0 Request",
+                http.url = url.as_str(),
+                http.status_code = tracing::field::Empty
+            );
+            let _enter = span.enter();
+
+            let response = self
+                .client
+                .patch(&url)
+                .bearer_auth(access_token)
+                .json(&Auth0UserUpdate { name: display_name })
+                .send()
+                .await
+                .unwrap();
+
+            span.record("http.status_code", &response.status().as_u16());
+
+            if response.status() == StatusCode::OK {
+                let auth0_user: Auth0User = response.json().await.unwrap();
+                Some(auth0_user)
+            } else {
+                tracing::warn!("Failed to update user in Auth0");
+                None
+            }
+        }?;
+
+        tracing::debug!(auth0_user = ?auth0_user, "Updated user details");
+
+        Some(auth0_user.into())
+    }
+}
+
+/// Representation of the changes to make to a user in Auth0.
+#[derive(Debug, Serialize)]
+struct Auth0UserUpdate {
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::users::auth0::{ClientId, ClientSecret, Domain, UserRepository};
+    use assert2::{check, let_assert};
+    use mockito::{mock, Matcher};
+    use serde_json::json;
+
+    #[actix_rt::test]
+    async fn unknown_user() {
+        let _ = env_logger::try_init();
+
+        let access_token_mock = mock("POST", "/oauth/token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "access_token":"testAccessToken",
+                "scope":"read:users update:users delete:users read:users_app_metadata update:users_app_metadata delete:users_app_metadata",
+                "expires_in":86400,
+                "token_type":"Bearer"
+            }"#)
+        .create();
+        let users_mock = mock("PATCH", "/api/v2/users/unknownuserid")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "error": "Not Found",
+                "errorCode": "inexistent_user",
+                "message": "The user does not exist.",
+                "statusCode": 404
+            }"#,
+            )
+            .create();
+
+        let sut = UserRepository::new(
+            Domain::new(mockito::server_url()),
+            ClientId::new("testClientId"),
+            ClientSecret::new("testClientSecret"),
+        );
+
+        let user = sut
+            .update_display_name("unknownuserid".parse().unwrap(), "New Name".to_owned())
+            .await;
+
+        check!(user.is_none());
+
+        access_token_mock.assert();
+        users_mock.assert();
+    }
+
+    #[actix_rt::test]
+    async fn known_user() {
+        let _ = env_logger::try_init();
+
+        let access_token_mock = mock("POST", "/oauth/token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{
+                "access_token":"testAccessToken",
+                "scope":"read:users update:users delete:users read:users_app_metadata update:users_app_metadata delete:users_app_metadata",
+                "expires_in":86400,
+                "token_type":"Bearer"
+            }"#)
+        .create();
+        let users_mock = mock("PATCH", "/api/v2/users/auth0%7C6044f85d48fea20070575673")
+            .match_header("authorization", "Bearer testAccessToken")
+            .match_body(Matcher::Json(json!({"name": "New Name"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                "created_at": "2021-03-07T15:59:25.064Z",
+                "email": "testuser@example.com",
+                "email_verified": true,
+                "identities": [
+                    {
+                        "connection": "Username-Password-Authentication",
+                        "isSocial": false,
+                        "provider": "auth0",
+                        "user_id": "6044f85d48fea20070575673"
+                    }
+                ],
+                "name": "New Name",
+                "nickname": "testuser",
+                "updated_at": "2021-03-08T09:12:45.123Z",
+                "user_id": "auth0|6044f85d48fea20070575673"
+            }"#,
+            )
+            .create();
+
+        let sut = UserRepository::new(
+            Domain::new(mockito::server_url()),
+            ClientId::new("testClientId"),
+            ClientSecret::new("testClientSecret"),
+        );
+
+        let user = sut
+            .update_display_name(
+                "auth0|6044f85d48fea20070575673".parse().unwrap(),
+                "New Name".to_owned(),
+            )
+            .await;
+
+        let_assert!(Some(user) = user);
+        check!(user.identity.id == "auth0|6044f85d48fea20070575673");
+        check!(user.data.display_name == "New Name");
+        check!(user.data.email == "testuser@example.com");
+
+        access_token_mock.assert();
+        users_mock.assert();
+    }
+}
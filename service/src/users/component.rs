@@ -1,14 +1,23 @@
 use super::{
     auth0::{ClientId, ClientSecret, Domain, UserRepository},
-    GetUserUseCase,
+    GetUserUseCase, UpdateUserUseCase,
+};
+use crate::{
+    authorization::Authorization,
+    health::HealthCheck,
+    home::LinkContributor,
+    http::{hal::Link, openapi::Operation, schema::Schema},
+    server::RouteConfigurer,
 };
-use crate::server::RouteConfigurer;
 use actix_web::web::ServiceConfig;
+use async_trait::async_trait;
 use std::sync::Arc;
 
 /// Users component for working with users, working in terms of Auth0.
 pub struct Component {
+    repository: Arc<UserRepository>,
     get_user_use_case: Arc<GetUserUseCase>,
+    update_user_use_case: Arc<UpdateUserUseCase>,
 }
 
 /// Create a new instance of the Users component
@@ -26,14 +35,16 @@ where
     I: Into<String>,
     S: Into<String>,
 {
-    let repository = UserRepository::new(
+    let repository = Arc::new(UserRepository::new(
         Domain::new(domain),
         ClientId::new(client_id),
         ClientSecret::new(client_secret),
-    );
+    ));
 
     let component = Component {
-        get_user_use_case: Arc::new(GetUserUseCase::new(repository)),
+        get_user_use_case: Arc::new(GetUserUseCase::new(repository.clone())),
+        update_user_use_case: Arc::new(UpdateUserUseCase::new(repository.clone())),
+        repository,
     };
 
     Arc::new(component)
@@ -42,6 +53,29 @@ where
 impl RouteConfigurer for Component {
     fn configure_routes(&self, config: &mut ServiceConfig) {
         config.data(self.get_user_use_case.clone());
+        config.data(self.update_user_use_case.clone());
         super::http::configure_routes(config);
     }
+
+    fn describe_operations(&self) -> Vec<Operation> {
+        super::http::operations()
+    }
+
+    fn describe_schemas(&self) -> Vec<Schema> {
+        super::http::schemas()
+    }
+}
+
+#[async_trait]
+impl LinkContributor for Component {
+    async fn generate_links(&self, _authorization: &Authorization) -> Vec<(String, Link)> {
+        vec![("nl:user".to_owned(), Link::templated("/users/{id}"))]
+    }
+}
+
+#[async_trait(?Send)]
+impl HealthCheck for Component {
+    async fn check_health(&self) -> Result<(), String> {
+        self.repository.check_health().await
+    }
 }
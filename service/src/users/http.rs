@@ -1,12 +1,120 @@
-use actix_web::web::{get, resource, ServiceConfig};
+use crate::http::{
+    hal::APPLICATION_HAL_JSON,
+    openapi::{hal_schema, Operation, BEARER_AUTH},
+    patch::{APPLICATION_JSON_PATCH_JSON, APPLICATION_MERGE_PATCH_JSON},
+    problem::{
+        BAD_REQUEST, FORBIDDEN, INTERNAL_SERVER_ERROR, MALFORMED_PATCH, NOT_ACCEPTABLE, NOT_FOUND,
+        PAYLOAD_TOO_LARGE, PRECONDITION_FAILED, PRECONDITION_REQUIRED, UNAUTHORIZED,
+        UNPROCESSABLE_PATCH, UNSUPPORTED_MEDIA_TYPE, VALIDATION_FAILED,
+    },
+    routes,
+    schema::{JsonSchema, Schema},
+};
+use actix_http::http::{Method, StatusCode};
+use actix_web::web::ServiceConfig;
+use model::{UserModel, UserProfileModel, USER_SCHEMA};
+use serde_json::json;
 
 mod get;
 mod model;
+mod patch;
+mod put;
+mod update;
 
 /// Configure the HTTP routes for working with users.
 ///
 /// # Parameters
 /// - `config` - The HTTP Server configuration to register the routes with.
 pub fn configure_routes(config: &mut ServiceConfig) {
-    config.service(resource("/users/{userId}").route(get().to(get::handle)));
+    routes::new("/users/{userId}")
+        .with_route(Method::GET, get::handle)
+        .with_route(Method::PATCH, patch::handle)
+        .with_route(Method::PUT, put::handle)
+        .register(config);
+}
+
+/// Describe the HTTP routes for working with users.
+///
+/// # Returns
+/// The operations of every route registered by `configure_routes`.
+pub fn operations() -> Vec<Operation> {
+    vec![
+        Operation::new(Method::GET, "/users/{userId}", "getUser")
+            .with_summary("Get a single user by their ID")
+            .with_tag("users")
+            .with_path_parameter("userId", "The ID of the user")
+            .with_projection()
+            .with_response(
+                StatusCode::OK,
+                "The user, including an edit template if it is the caller's own profile",
+                APPLICATION_HAL_JSON.name,
+                hal_schema(UserModel::json_schema()["properties"].clone()),
+            )
+            .with_problem(&UNAUTHORIZED)
+            .with_problem(&NOT_FOUND)
+            .with_problem(&NOT_ACCEPTABLE)
+            .with_security(BEARER_AUTH, &[])
+            .with_anonymous_access(),
+        Operation::new(Method::PATCH, "/users/{userId}", "patchUser")
+            .with_summary("Change the profile of the caller")
+            .with_tag("users")
+            .with_path_parameter("userId", "The ID of the user, which must be the caller")
+            .with_request_body(
+                APPLICATION_MERGE_PATCH_JSON,
+                UserProfileModel::json_schema(),
+            )
+            .with_request_body(
+                APPLICATION_JSON_PATCH_JSON,
+                json!({"type": "array", "items": {"type": "object"}}),
+            )
+            .with_response(
+                StatusCode::OK,
+                "The updated user",
+                APPLICATION_HAL_JSON.name,
+                hal_schema(UserModel::json_schema()["properties"].clone()),
+            )
+            .with_problem(&MALFORMED_PATCH)
+            .with_problem(&UNAUTHORIZED)
+            .with_problem(&FORBIDDEN)
+            .with_problem(&NOT_FOUND)
+            .with_problem(&NOT_ACCEPTABLE)
+            .with_problem(&PRECONDITION_FAILED)
+            .with_problem(&PAYLOAD_TOO_LARGE)
+            .with_problem(&UNSUPPORTED_MEDIA_TYPE)
+            .with_problem(&UNPROCESSABLE_PATCH)
+            .with_problem(&VALIDATION_FAILED)
+            .with_problem(&INTERNAL_SERVER_ERROR)
+            .with_security(BEARER_AUTH, &[]),
+        Operation::new(Method::PUT, "/users/{userId}", "replaceUser")
+            .with_summary("Replace the profile of the caller")
+            .with_tag("users")
+            .with_path_parameter("userId", "The ID of the user, which must be the caller")
+            .with_request_body("application/json", UserProfileModel::json_schema())
+            .with_response(
+                StatusCode::OK,
+                "The updated user",
+                APPLICATION_HAL_JSON.name,
+                hal_schema(UserModel::json_schema()["properties"].clone()),
+            )
+            .with_problem(&BAD_REQUEST)
+            .with_problem(&UNAUTHORIZED)
+            .with_problem(&FORBIDDEN)
+            .with_problem(&NOT_FOUND)
+            .with_problem(&NOT_ACCEPTABLE)
+            .with_problem(&PRECONDITION_FAILED)
+            .with_problem(&PAYLOAD_TOO_LARGE)
+            .with_problem(&UNSUPPORTED_MEDIA_TYPE)
+            .with_problem(&VALIDATION_FAILED)
+            .with_problem(&PRECONDITION_REQUIRED)
+            .with_problem(&INTERNAL_SERVER_ERROR)
+            .with_security(BEARER_AUTH, &[]),
+    ]
+}
+
+/// Describe the schemas of the documents returned by the routes for working with users.
+///
+/// # Returns
+/// The schemas of the documents.
+pub fn schemas() -> Vec<Schema> {
+    vec![Schema::of::<UserModel>(USER_SCHEMA)]
 }
@@ -1,3 +1,4 @@
+use crate::authorization::Authorization;
 use crate::http::{hal::HalRespondable, problem::Problem, problem::NOT_FOUND, Response};
 use crate::users::{GetUserUseCase, UserId};
 use actix_web::web::{Data, Path};
@@ -8,16 +9,18 @@ use std::sync::Arc;
 /// # Parameters
 /// - `path` - The parsed URL path, containing the requested user ID
 /// - `get_user_use_case` - The use case to use for getting user records
+/// - `authorization` - The authorization of the caller
 ///
 /// # Returns
 /// The HTTP Response. Either the user as a HAL document or else a Problem indicting why the user couldn't be loaded.
 pub async fn handle(
     path: Path<String>,
     get_user_use_case: Data<Arc<GetUserUseCase>>,
+    authorization: Authorization,
 ) -> Result<Response<HalRespondable>, Problem> {
     let user_id = path.0.parse::<UserId>().map_err(|e| {
         tracing::warn!(e = ?e, "Failed to parse User ID");
-        Problem::from(NOT_FOUND)
+        Problem::from(NOT_FOUND).with_source(&e)
     })?;
 
     let user = get_user_use_case
@@ -25,5 +28,8 @@ pub async fn handle(
         .await
         .ok_or_else(|| Problem::from(NOT_FOUND))?;
 
-    Ok(user.into())
+    Ok(Response(HalRespondable::from_resource(
+        user,
+        &authorization,
+    )))
 }
@@ -1,45 +1,114 @@
 use crate::{
+    authorization::{Authorization, Principal},
     http::{
-        hal::{HalDocument, HalRespondable},
-        Response,
+        hal::{HalResourceData, Property, PropertyType, Template},
+        schema::{schema_path, JsonSchema},
+        validation::{Validate, Validator},
     },
-    users::UserResource,
+    users::{UserData, UserId},
 };
-use actix_http::http::{
-    header::{CacheControl, CacheDirective, ETag, EntityTag},
-    StatusCode,
-};
-use serde::Serialize;
+use actix_http::http::Method;
+use serde::{Deserialize, Serialize};
+
+/// The name of the schema describing users.
+pub const USER_SCHEMA: &str = "user";
 
 /// Representation of a User on the HTTP API.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UserModel {
+    /// The name to display for the user.
     pub display_name: String,
+    /// The email address of the user.
+    #[schema(format = "email")]
     pub email: String,
+    /// Whether or not the email address is verified.
     pub email_verified: bool,
+    /// If the user is registered with a social provider then which one.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub social_provider: Option<String>,
 }
 
-impl From<UserResource> for Response<HalRespondable> {
-    fn from(user: UserResource) -> Self {
-        let hal_document = HalDocument::new(UserModel {
-            display_name: user.data.display_name,
-            email: user.data.email,
-            email_verified: user.data.email_verified,
-            social_provider: user.data.social_provider,
-        })
-        .with_link("self", user.identity.id);
-
-        let respondable = HalRespondable::from(hal_document)
-            .with_status_code(StatusCode::OK)
-            .with_header(CacheControl(vec![
-                CacheDirective::Public,
-                CacheDirective::MaxAge(3600),
-            ]))
-            .with_header(ETag(EntityTag::strong(user.identity.version)));
-
-        Response(respondable)
+/// Representation of the parts of a User that can be changed on their own profile.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UserProfileModel {
+    /// The name to display for the user.
+    pub display_name: String,
+}
+
+impl From<&UserData> for UserProfileModel {
+    fn from(user: &UserData) -> Self {
+        Self {
+            display_name: user.display_name.clone(),
+        }
+    }
+}
+
+impl Validate for UserProfileModel {
+    fn validate(&self, validator: &mut Validator) {
+        validator.required("displayName", &self.display_name);
+    }
+}
+
+/// Check whether the caller is the user with the given ID, and so can see and change their own profile.
+///
+/// # Parameters
+/// - `id` - The ID of the user
+/// - `authorization` - The authorization of the caller
+///
+/// # Returns
+/// True if the caller is the user. False if not, including if the caller is unauthorized.
+pub fn is_own_profile(id: &UserId, authorization: &Authorization) -> bool {
+    match authorization {
+        Authorization::Authorized(security_context) => match &security_context.principal {
+            Principal::User(user_id) => user_id.parse::<UserId>().ok().as_ref() == Some(id),
+        },
+        Authorization::Unauthorized => false,
+    }
+}
+
+impl HalResourceData<UserId> for UserData {
+    type Model = UserModel;
+
+    fn templates(
+        &self,
+        id: &UserId,
+        authorization: &Authorization,
+    ) -> Vec<(&'static str, Template)> {
+        if is_own_profile(id, authorization) {
+            let template = Template::new(&Method::PATCH)
+                .with_title("Edit profile")
+                .with_content_type("application/merge-patch+json")
+                .with_property(
+                    Property::new("displayName")
+                        .with_prompt("Display Name")
+                        .with_type(PropertyType::Text)
+                        .required()
+                        .with_length(Some(1), None)
+                        .with_value(&self.display_name),
+                );
+
+            vec![("edit", template)]
+        } else {
+            vec![]
+        }
+    }
+
+    fn optional_fields(&self) -> Vec<&'static str> {
+        vec!["socialProvider"]
+    }
+
+    fn profile(&self) -> Option<String> {
+        Some(schema_path(USER_SCHEMA))
+    }
+
+    fn into_model(self) -> Self::Model {
+        UserModel {
+            display_name: self.display_name,
+            email: self.email,
+            email_verified: self.email_verified,
+            social_provider: self.social_provider,
+        }
     }
 }
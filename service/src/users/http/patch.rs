@@ -0,0 +1,43 @@
+use super::{model::UserProfileModel, update::update_profile};
+use crate::authorization::Authorization;
+use crate::http::{
+    hal::HalRespondable, patch::PatchDocument, preconditions::Preconditions, problem::Problem,
+    Response,
+};
+use crate::users::{GetUserUseCase, UpdateUserUseCase};
+use actix_web::web::{Data, Path};
+use std::sync::Arc;
+
+/// Apply a patch to the profile of the requested user, which must be the caller, and return the updated user
+///
+/// # Parameters
+/// - `path` - The parsed URL path, containing the requested user ID
+/// - `authorization` - The authorization of the caller
+/// - `preconditions` - The preconditions that the request was made with
+/// - `patch_document` - The patch to apply to the profile of the user
+/// - `get_user_use_case` - The use case to use for getting user records
+/// - `update_user_use_case` - The use case to use for changing user records
+///
+/// # Returns
+/// The HTTP Response. Either the updated user as a HAL document or else a Problem indicting why the user couldn't be
+/// updated.
+pub async fn handle(
+    path: Path<String>,
+    authorization: Authorization,
+    preconditions: Preconditions,
+    patch_document: PatchDocument,
+    get_user_use_case: Data<Arc<GetUserUseCase>>,
+    update_user_use_case: Data<Arc<UpdateUserUseCase>>,
+) -> Result<Response<HalRespondable>, Problem> {
+    update_profile(
+        &path.0,
+        &authorization,
+        &get_user_use_case,
+        &update_user_use_case,
+        |user| {
+            preconditions.check(user)?;
+            patch_document.apply(&UserProfileModel::from(&user.data))
+        },
+    )
+    .await
+}
@@ -0,0 +1,46 @@
+use super::{model::UserProfileModel, update::update_profile};
+use crate::authorization::Authorization;
+use crate::http::{
+    hal::HalRespondable, preconditions::Preconditions, problem::Problem, validation::Validated,
+    Response,
+};
+use crate::users::{GetUserUseCase, UpdateUserUseCase};
+use actix_web::web::{Data, Path};
+use std::sync::Arc;
+
+/// Replace the profile of the requested user, which must be the caller, and return the updated user
+///
+/// Replacing the whole profile would silently discard any concurrent change, so the request must be conditional on
+/// the current version of the user.
+///
+/// # Parameters
+/// - `path` - The parsed URL path, containing the requested user ID
+/// - `authorization` - The authorization of the caller
+/// - `preconditions` - The preconditions that the request was made with
+/// - `body` - The new profile of the user
+/// - `get_user_use_case` - The use case to use for getting user records
+/// - `update_user_use_case` - The use case to use for changing user records
+///
+/// # Returns
+/// The HTTP Response. Either the updated user as a HAL document or else a Problem indicting why the user couldn't be
+/// updated.
+pub async fn handle(
+    path: Path<String>,
+    authorization: Authorization,
+    preconditions: Preconditions,
+    body: Validated<UserProfileModel>,
+    get_user_use_case: Data<Arc<GetUserUseCase>>,
+    update_user_use_case: Data<Arc<UpdateUserUseCase>>,
+) -> Result<Response<HalRespondable>, Problem> {
+    update_profile(
+        &path.0,
+        &authorization,
+        &get_user_use_case,
+        &update_user_use_case,
+        |user| {
+            preconditions.require(user)?;
+            Ok(body.0)
+        },
+    )
+    .await
+}
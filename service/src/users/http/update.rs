@@ -0,0 +1,69 @@
+use super::model::{is_own_profile, UserProfileModel};
+use crate::authorization::Authorization;
+use crate::http::{
+    hal::HalRespondable,
+    problem::{Problem, FORBIDDEN, INTERNAL_SERVER_ERROR, NOT_FOUND, UNAUTHORIZED},
+    Response,
+};
+use crate::users::{GetUserUseCase, UpdateUserUseCase, UserId, UserResource};
+
+/// Change the profile of the requested user, which must be the caller, and return the updated user
+///
+/// The user is fetched, checked by `change` and updated while holding the lock for that user, so that two concurrent
+/// changes made against the same version of the user can't both pass their preconditions and silently overwrite one
+/// another. Auth0 has no conditional update, so this only protects against changes made through this instance of the
+/// service.
+///
+/// # Parameters
+/// - `user_id` - The requested user ID, as provided in the URL path
+/// - `authorization` - The authorization of the caller
+/// - `get_user_use_case` - The use case to use for getting user records
+/// - `update_user_use_case` - The use case to use for changing user records
+/// - `change` - Produce the new profile of the user from their current state
+///
+/// # Returns
+/// The HTTP Response. Either the updated user as a HAL document or else a Problem indicting why the user couldn't be
+/// updated.
+pub async fn update_profile<F>(
+    user_id: &str,
+    authorization: &Authorization,
+    get_user_use_case: &GetUserUseCase,
+    update_user_use_case: &UpdateUserUseCase,
+    change: F,
+) -> Result<Response<HalRespondable>, Problem>
+where
+    F: FnOnce(&UserResource) -> Result<UserProfileModel, Problem>,
+{
+    if let Authorization::Unauthorized = authorization {
+        return Err(Problem::from(UNAUTHORIZED));
+    }
+
+    let user_id = user_id.parse::<UserId>().map_err(|e| {
+        tracing::warn!(e = ?e, "Failed to parse User ID");
+        Problem::from(NOT_FOUND).with_source(&e)
+    })?;
+
+    if !is_own_profile(&user_id, authorization) {
+        tracing::warn!(user_id = ?user_id, "Attempt to change the profile of a different user");
+        return Err(Problem::from(FORBIDDEN));
+    }
+
+    let id = &user_id;
+    let user = update_user_use_case
+        .exclusively(id, || async move {
+            let user = get_user_use_case
+                .get_user_by_id(id.clone())
+                .await
+                .ok_or_else(|| Problem::from(NOT_FOUND))?;
+
+            let profile = change(&user)?;
+
+            update_user_use_case
+                .update_display_name(id.clone(), profile.display_name)
+                .await
+                .ok_or_else(|| Problem::from(INTERNAL_SERVER_ERROR))
+        })
+        .await?;
+
+    Ok(Response(HalRespondable::from_resource(user, authorization)))
+}
@@ -3,7 +3,7 @@ use std::str::FromStr;
 use uritemplate::{IntoTemplateVar, TemplateVar, UriTemplate};
 
 /// Representation of the ID of a user.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct UserId(String);
 
 /// Errors that can occur when parsing a User ID from a string.
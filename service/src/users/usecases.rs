@@ -1,3 +1,5 @@
 mod get_user;
+mod update_user;
 
 pub use get_user::*;
+pub use update_user::*;
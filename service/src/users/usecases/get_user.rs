@@ -1,9 +1,10 @@
 use crate::users::{auth0::UserRepository, UserId, UserResource};
+use std::sync::Arc;
 
 /// Use Case for getting user records.
 pub struct GetUserUseCase {
     /// The repository of user data.
-    repository: UserRepository,
+    repository: Arc<UserRepository>,
 }
 
 impl GetUserUseCase {
@@ -11,7 +12,7 @@ impl GetUserUseCase {
     ///
     /// # Parameters
     /// - `repository` - The repository of user data
-    pub fn new(repository: UserRepository) -> Self {
+    pub fn new(repository: Arc<UserRepository>) -> Self {
         Self { repository }
     }
 
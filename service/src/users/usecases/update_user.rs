@@ -0,0 +1,152 @@
+use crate::users::{auth0::UserRepository, UserId, UserResource};
+use futures::{lock::Mutex as AsyncMutex, Future};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Use Case for changing user records.
+pub struct UpdateUserUseCase {
+    /// The repository of user data.
+    repository: Arc<UserRepository>,
+    /// The locks for the users that are currently being changed.
+    locks: Mutex<HashMap<UserId, Arc<AsyncMutex<()>>>>,
+}
+
+impl UpdateUserUseCase {
+    /// Create a new instance of the use case.
+    ///
+    /// # Parameters
+    /// - `repository` - The repository of user data
+    pub fn new(repository: Arc<UserRepository>) -> Self {
+        Self {
+            repository,
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Run a change to the user with the provided ID, waiting for any other change to the same user to finish first.
+    ///
+    /// Auth0 has no way to make an update conditional on the current version of the user, so checking the version
+    /// and then writing the change can't be done atomically there. Doing both inside this means that two concurrent
+    /// changes made against the same version can't both succeed, as long as they are made by this instance of the
+    /// service.
+    ///
+    /// # Parameters
+    /// - `id` - The ID of the user
+    /// - `change` - The change to make, including checking the current version of the user
+    ///
+    /// # Returns
+    /// The result of the change.
+    pub async fn exclusively<F, Fut, T>(&self, id: &UserId, change: F) -> T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let lock = self
+            .locks
+            .lock()
+            .unwrap()
+            .entry(id.clone())
+            .or_default()
+            .clone();
+
+        let result = {
+            let _guard = lock.lock().await;
+            change().await
+        };
+
+        // Nobody else can get hold of the lock while the map is locked, so it can be discarded once this was the
+        // last user of it.
+        let mut locks = self.locks.lock().unwrap();
+        drop(lock);
+        if let Some(lock) = locks.get(id) {
+            if Arc::strong_count(lock) == 1 {
+                locks.remove(id);
+            }
+        }
+
+        result
+    }
+
+    /// Change the display name of the user with the provided ID
+    ///
+    /// # Parameters
+    /// - `id` - The ID of the user
+    /// - `display_name` - The new display name of the user
+    ///
+    /// # Returns
+    /// The updated details of the user, or `None` if the user couldn't be updated.
+    #[tracing::instrument(skip(self))]
+    pub async fn update_display_name(
+        &self,
+        id: UserId,
+        display_name: String,
+    ) -> Option<UserResource> {
+        self.repository.update_display_name(id, display_name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::users::auth0::{ClientId, ClientSecret, Domain};
+    use assert2::check;
+    use std::{sync::atomic::AtomicU32, sync::atomic::Ordering, time::Duration};
+
+    fn use_case() -> UpdateUserUseCase {
+        UpdateUserUseCase::new(Arc::new(UserRepository::new(
+            Domain::new("https://example.auth0.com"),
+            ClientId::new("testClientId"),
+            ClientSecret::new("testClientSecret"),
+        )))
+    }
+
+    /// Make a change conditional on the current version, taking some time between checking it and writing the change.
+    async fn update(version: &AtomicU32, expected: u32) -> Result<(), u32> {
+        let current = version.load(Ordering::SeqCst);
+        actix_rt::time::delay_for(Duration::from_millis(10)).await;
+
+        if current == expected {
+            version.store(current + 1, Ordering::SeqCst);
+            Ok(())
+        } else {
+            Err(current)
+        }
+    }
+
+    #[actix_rt::test]
+    async fn concurrent_changes_to_same_user() {
+        let sut = use_case();
+        let id: UserId = "auth0|user".parse().unwrap();
+        let version = AtomicU32::new(1);
+
+        let (first, second) = futures::join!(
+            sut.exclusively(&id, || update(&version, 1)),
+            sut.exclusively(&id, || update(&version, 1))
+        );
+
+        check!(first == Ok(()));
+        check!(second == Err(2));
+        check!(version.load(Ordering::SeqCst) == 2);
+        check!(sut.locks.lock().unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn concurrent_changes_to_different_users() {
+        let sut = use_case();
+        let first_id: UserId = "auth0|first".parse().unwrap();
+        let second_id: UserId = "auth0|second".parse().unwrap();
+        let version = AtomicU32::new(1);
+
+        let (first, second) = futures::join!(
+            sut.exclusively(&first_id, || update(&version, 1)),
+            sut.exclusively(&second_id, || update(&version, 1))
+        );
+
+        // Changes to different users don't wait for each other, so both see the original version.
+        check!(first == Ok(()));
+        check!(second == Ok(()));
+        check!(sut.locks.lock().unwrap().is_empty());
+    }
+}